@@ -0,0 +1,35 @@
+#!/usr/bin/env bash
+
+# Sign the release artifacts with minisign, the launcher verifies the downloaded update packages
+# with the public key embedded at build time (LXMCL_UPDATE_PUBLIC_KEY).
+#
+# Usage: sign_update_packages.sh <artifact files...>
+#
+# Required environment variables:
+# - LXMCL_UPDATE_SECRET_KEY: content of the minisign secret key file
+# - LXMCL_UPDATE_SECRET_KEY_PASSWORD: password of the secret key
+
+set -euo pipefail
+
+if [ -z "${LXMCL_UPDATE_SECRET_KEY:-}" ]; then
+  echo "❌ LXMCL_UPDATE_SECRET_KEY secret is not set"
+  exit 1
+fi
+
+if ! command -v minisign >/dev/null 2>&1; then
+  sudo apt-get update
+  sudo apt-get install -y minisign
+fi
+
+KEY_FILE="$(mktemp)"
+trap 'rm -f "$KEY_FILE"' EXIT
+printf '%s\n' "$LXMCL_UPDATE_SECRET_KEY" > "$KEY_FILE"
+
+for file in "$@"; do
+  case "$file" in
+    *.minisig) continue ;;
+  esac
+  # keep the default trusted comment, the launcher checks the file name in it
+  printf '%s\n' "${LXMCL_UPDATE_SECRET_KEY_PASSWORD:-}" | minisign -S -s "$KEY_FILE" -m "$file"
+  echo "✅ Signed $(basename "$file")"
+done
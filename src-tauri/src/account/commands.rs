@@ -5,9 +5,12 @@ use crate::account::helpers::authlib_injector::info::{
 };
 use crate::account::helpers::authlib_injector::jar::check_authlib_jar;
 use crate::account::helpers::authlib_injector::{self};
+use crate::account::helpers::skin::validate_texture_image;
+use crate::account::helpers::token_refresh::{refresh_player_info, save_refreshed_player};
 use crate::account::helpers::{microsoft, misc, offline};
 use crate::account::models::{
-  AccountError, AccountInfo, AuthServer, DeviceAuthResponseInfo, Player, PlayerInfo, PlayerType,
+  AccountError, AccountInfo, AuthServer, CapeInfo, DeviceAuthResponseInfo, Player, PlayerInfo,
+  PlayerType, Texture,
 };
 use crate::error::LXMCLResult;
 use crate::launcher_config::models::LauncherConfig;
@@ -385,6 +388,105 @@ pub fn update_player_skin_offline_preset(
   Ok(())
 }
 
+fn get_player_info_by_id(app: &AppHandle, player_id: &str) -> LXMCLResult<PlayerInfo> {
+  let account_binding = app.state::<Mutex<AccountInfo>>();
+  let account_state = account_binding.lock()?;
+  account_state
+    .players
+    .iter()
+    .find(|player| player.id == player_id)
+    .cloned()
+    .ok_or(AccountError::NotFound.into())
+}
+
+// save the textures of a player, changed on the auth server.
+fn save_player_textures(
+  app: &AppHandle,
+  player_id: String,
+  textures: Vec<Texture>,
+) -> LXMCLResult<()> {
+  let account_binding = app.state::<Mutex<AccountInfo>>();
+  let mut account_state = account_binding.lock()?;
+  let player = account_state
+    .get_player_by_id_mut(player_id)
+    .ok_or(AccountError::NotFound)?;
+  player.textures = textures;
+  account_state.save()?;
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn upload_player_texture(
+  app: AppHandle,
+  player_id: String,
+  texture_type: String,
+  file_path: String,
+  model: String,
+) -> LXMCLResult<()> {
+  let player = get_player_info_by_id(&app, &player_id)?;
+  let image_bytes = tokio::fs::read(&file_path)
+    .await
+    .map_err(|_| AccountError::TextureError)?;
+  validate_texture_image(&image_bytes, &texture_type)?;
+
+  let textures = match player.player_type {
+    // capes of Microsoft accounts can only be selected from the owned ones
+    PlayerType::Microsoft if texture_type == "SKIN" => {
+      microsoft::texture::upload_skin(&app, &player, image_bytes, &model).await?
+    }
+    PlayerType::ThirdParty => {
+      authlib_injector::texture::upload_texture(&app, &player, &texture_type, image_bytes, &model)
+        .await?
+    }
+    _ => return Err(AccountError::Invalid.into()),
+  };
+  save_player_textures(&app, player_id, textures)
+}
+
+#[tauri::command]
+pub async fn reset_player_texture(
+  app: AppHandle,
+  player_id: String,
+  texture_type: String,
+) -> LXMCLResult<()> {
+  let player = get_player_info_by_id(&app, &player_id)?;
+  let textures = match (&player.player_type, texture_type.as_str()) {
+    (PlayerType::Microsoft, "SKIN") => microsoft::texture::reset_skin(&app, &player).await?,
+    (PlayerType::Microsoft, "CAPE") => microsoft::texture::select_cape(&app, &player, None).await?,
+    (PlayerType::ThirdParty, "SKIN" | "CAPE") => {
+      authlib_injector::texture::reset_texture(&app, &player, &texture_type).await?
+    }
+    _ => return Err(AccountError::Invalid.into()),
+  };
+  save_player_textures(&app, player_id, textures)
+}
+
+#[tauri::command]
+pub async fn retrieve_player_cape_list(
+  app: AppHandle,
+  player_id: String,
+) -> LXMCLResult<Vec<CapeInfo>> {
+  let player = get_player_info_by_id(&app, &player_id)?;
+  if player.player_type != PlayerType::Microsoft {
+    return Err(AccountError::Invalid.into());
+  }
+  microsoft::texture::retrieve_capes(&app, &player).await
+}
+
+#[tauri::command]
+pub async fn select_player_cape(
+  app: AppHandle,
+  player_id: String,
+  cape_id: Option<String>,
+) -> LXMCLResult<()> {
+  let player = get_player_info_by_id(&app, &player_id)?;
+  if player.player_type != PlayerType::Microsoft {
+    return Err(AccountError::Invalid.into());
+  }
+  let textures = microsoft::texture::select_cape(&app, &player, cape_id).await?;
+  save_player_textures(&app, player_id, textures)
+}
+
 #[tauri::command]
 pub async fn delete_player(app: AppHandle, player_id: String) -> LXMCLResult<()> {
   {
@@ -423,45 +525,9 @@ pub async fn delete_player(app: AppHandle, player_id: String) -> LXMCLResult<()>
 
 #[tauri::command]
 pub async fn refresh_player(app: AppHandle, player_id: String) -> LXMCLResult<()> {
-  let account_binding = app.state::<Mutex<AccountInfo>>();
-
-  let cloned_account_state = account_binding.lock()?.clone();
-
-  let player = cloned_account_state
-    .players
-    .iter()
-    .find(|player| player.id == player_id)
-    .ok_or(AccountError::NotFound)?;
-
-  let refreshed_player = match player.player_type {
-    PlayerType::ThirdParty => {
-      let auth_server = AuthServer::from(get_auth_server_info_by_url(
-        &app,
-        player.auth_server_url.clone().unwrap_or_default(),
-      )?);
-
-      authlib_injector::common::refresh(&app, player, &auth_server).await?
-    }
-
-    PlayerType::Microsoft => microsoft::oauth::refresh(&app, player).await?,
-
-    PlayerType::Offline => {
-      return Err(AccountError::Invalid.into());
-    }
-  };
-
-  let mut account_state = account_binding.lock()?;
-
-  if let Some(player) = account_state
-    .players
-    .iter_mut()
-    .find(|player| player.id == player_id)
-  {
-    *player = refreshed_player;
-    account_state.save()?;
-  }
-
-  Ok(())
+  let player = get_player_info_by_id(&app, &player_id)?;
+  let refreshed_player = refresh_player_info(&app, &player).await?;
+  save_refreshed_player(&app, &player_id, refreshed_player)
 }
 
 #[tauri::command]
@@ -1,3 +1,13 @@
+use std::time::Duration;
+
 pub const ACCOUNTS_FILE_NAME: &str = "sjmcl.account.json";
+pub const CREDENTIAL_KEY_FILE_NAME: &str = "sjmcl.account.key";
 pub const DEFAULT_POLLING_INTERVAL: u64 = 5;
 pub const TEXTURE_ROLES: [&str; 2] = ["steve", "alex"];
+
+pub const TOKEN_REFRESH_FAILED_EVENT: &str = "account:token-refresh-failed";
+pub const TOKEN_REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+// refresh the JWT tokens this long before they expire
+pub const TOKEN_REFRESH_AHEAD: Duration = Duration::from_secs(60 * 60);
+// tokens without expiry info are validated against the auth server at this interval
+pub const OPAQUE_TOKEN_VALIDATE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
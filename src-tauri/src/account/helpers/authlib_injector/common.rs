@@ -43,6 +43,27 @@ pub async fn parse_profile(
 ) -> LXMCLResult<PlayerInfo> {
   let uuid = Uuid::parse_str(&profile.id).map_err(|_| AccountError::ParseError)?;
   let name = profile.name.clone();
+  let textures = parse_profile_textures(app, profile).await?;
+
+  PlayerInfo {
+    id: "".to_string(),
+    uuid,
+    name: name.to_string(),
+    player_type: PlayerType::ThirdParty,
+    auth_account,
+    access_token,
+    refresh_token,
+    textures,
+    auth_server_url,
+  }
+  .with_generated_id()
+  .with_sealed_tokens()
+}
+
+pub async fn parse_profile_textures(
+  app: &AppHandle,
+  profile: &MinecraftProfile,
+) -> LXMCLResult<Vec<Texture>> {
   let mut textures: Vec<Texture> = vec![];
 
   if let Some(texture_info_base64) = profile
@@ -80,21 +101,7 @@ pub async fn parse_profile(
     // this player didn't have a texture, use preset Steve skin instead
     textures = load_preset_skin(app, "steve".to_string())?;
   }
-
-  Ok(
-    PlayerInfo {
-      id: "".to_string(),
-      uuid,
-      name: name.to_string(),
-      player_type: PlayerType::ThirdParty,
-      auth_account,
-      access_token,
-      refresh_token,
-      textures,
-      auth_server_url,
-    }
-    .with_generated_id(),
-  )
+  Ok(textures)
 }
 
 pub async fn validate(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<bool> {
@@ -106,7 +113,7 @@ pub async fn validate(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<bool>
       player.auth_server_url.clone().unwrap_or_default()
     ))
     .json(&json!({
-      "accessToken": player.access_token.clone()
+      "accessToken": player.plain_access_token()?
     }))
     .send()
     .await
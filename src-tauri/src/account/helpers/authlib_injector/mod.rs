@@ -6,3 +6,4 @@ pub mod jar;
 pub mod models;
 pub mod oauth;
 pub mod password;
+pub mod texture;
@@ -202,10 +202,7 @@ pub async fn refresh(
     .post(&openid_configuration.token_endpoint)
     .form(&[
       ("client_id", client_id.clone().unwrap_or_default()),
-      (
-        "refresh_token",
-        player.refresh_token.clone().unwrap_or_default(),
-      ),
+      ("refresh_token", player.plain_refresh_token()?),
       ("grant_type", "refresh_token".to_string()),
     ])
     .send()
@@ -112,7 +112,7 @@ pub async fn refresh(
       player.auth_server_url.clone().unwrap_or_default()
     ))
     .json(&YggdrasilSession {
-      access_token: player.access_token.clone().unwrap_or_default(),
+      access_token: player.plain_access_token()?,
       selected_profile: if is_new_bind {
         Some(YggdrasilProfile {
           id: player.uuid.as_simple().to_string(),
@@ -0,0 +1,82 @@
+use crate::account::helpers::authlib_injector::common::{parse_profile_textures, retrieve_profile};
+use crate::account::models::{AccountError, PlayerInfo, Texture};
+use crate::error::LXMCLResult;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest::{self, multipart, RequestBuilder, StatusCode};
+
+// ref: https://github.com/yushijinhun/authlib-injector/wiki/Yggdrasil-服务端技术规范#材质上传
+fn get_texture_endpoint(player: &PlayerInfo, texture_type: &str) -> String {
+  format!(
+    "{}/api/user/profile/{}/{}",
+    player
+      .auth_server_url
+      .clone()
+      .unwrap_or_default()
+      .trim_end_matches('/'),
+    player.uuid.simple(),
+    texture_type.to_lowercase()
+  )
+}
+
+// the texture API answers with 204 No Content, fetch the profile again for the updated textures.
+async fn send_texture_request(
+  app: &AppHandle,
+  player: &PlayerInfo,
+  request: RequestBuilder,
+) -> LXMCLResult<Vec<Texture>> {
+  let response = request
+    .header(
+      "Authorization",
+      format!("Bearer {}", player.plain_access_token()?),
+    )
+    .send()
+    .await
+    .map_err(|_| AccountError::NetworkError)?;
+  match response.status() {
+    status if status.is_success() => {}
+    StatusCode::UNAUTHORIZED => return Err(AccountError::Expired.into()),
+    StatusCode::BAD_REQUEST => return Err(AccountError::TextureError.into()),
+    _ => return Err(AccountError::NetworkError.into()),
+  }
+
+  let profile = retrieve_profile(
+    app,
+    player.auth_server_url.clone().unwrap_or_default(),
+    player.uuid.simple().to_string(),
+  )
+  .await?;
+  parse_profile_textures(app, &profile).await
+}
+
+// texture_type: "SKIN" or "CAPE", model: "slim" for the Alex model (only for skins).
+pub async fn upload_texture(
+  app: &AppHandle,
+  player: &PlayerInfo,
+  texture_type: &str,
+  image_bytes: Vec<u8>,
+  model: &str,
+) -> LXMCLResult<Vec<Texture>> {
+  let client = app.state::<reqwest::Client>();
+  let file_part = multipart::Part::bytes(image_bytes)
+    .file_name(format!("{}.png", texture_type.to_lowercase()))
+    .mime_str("image/png")
+    .map_err(|_| AccountError::TextureError)?;
+  let mut form = multipart::Form::new();
+  if texture_type == "SKIN" {
+    form = form.text("model", if model == "slim" { "slim" } else { "" });
+  }
+  let request = client
+    .put(get_texture_endpoint(player, texture_type))
+    .multipart(form.part("file", file_part));
+  send_texture_request(app, player, request).await
+}
+
+pub async fn reset_texture(
+  app: &AppHandle,
+  player: &PlayerInfo,
+  texture_type: &str,
+) -> LXMCLResult<Vec<Texture>> {
+  let client = app.state::<reqwest::Client>();
+  let request = client.delete(get_texture_endpoint(player, texture_type));
+  send_texture_request(app, player, request).await
+}
@@ -0,0 +1,149 @@
+use crate::account::constants::CREDENTIAL_KEY_FILE_NAME;
+use crate::account::models::AccountError;
+use crate::error::LXMCLResult;
+use crate::APP_DATA_DIR;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose;
+use base64::Engine;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::sync::OnceLock;
+
+// Tokens are kept sealed (in memory and in the accounts file) as
+// `enc:v1:<base64(nonce || ciphertext)>`, encrypted with AES-256-GCM by a random key
+// created once per installation, only readable by the current user.
+const SEALED_TOKEN_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+
+static CREDENTIAL_KEY: OnceLock<Key<Aes256Gcm>> = OnceLock::new();
+
+fn load_or_create_key() -> LXMCLResult<Key<Aes256Gcm>> {
+  let key_path = APP_DATA_DIR
+    .get()
+    .ok_or(AccountError::CredentialError)?
+    .join(CREDENTIAL_KEY_FILE_NAME);
+  match fs::read(&key_path) {
+    Ok(bytes) if bytes.len() == 32 => return Ok(*Key::<Aes256Gcm>::from_slice(&bytes)),
+    Ok(_) => log::warn!("credential key file is corrupted, creating a new one"),
+    Err(e) if e.kind() != ErrorKind::NotFound => {
+      log::warn!("credential key file is unreadable ({e}), creating a new one")
+    }
+    Err(_) => {}
+  }
+
+  // tokens sealed by a lost key can no longer be decrypted, the players have to log in again.
+  let key = Aes256Gcm::generate_key(OsRng);
+  if let Some(parent) = key_path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  // write to a temporary file first so a crash never leaves a truncated key behind.
+  // on windows the app data dir is already per-user, so no extra permission is set there.
+  let tmp_path = key_path.with_extension("tmp");
+  let mut options = fs::OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::OpenOptionsExt;
+    options.mode(0o600);
+  }
+  let mut file = options.open(&tmp_path)?;
+  file.write_all(&key)?;
+  file.sync_all()?;
+  drop(file);
+  fs::rename(&tmp_path, &key_path)?;
+  Ok(key)
+}
+
+fn get_cipher() -> LXMCLResult<Aes256Gcm> {
+  if CREDENTIAL_KEY.get().is_none() {
+    let _ = CREDENTIAL_KEY.set(load_or_create_key()?);
+  }
+  let key = CREDENTIAL_KEY.get().ok_or(AccountError::CredentialError)?;
+  Ok(Aes256Gcm::new(key))
+}
+
+pub fn is_sealed_token(token: &str) -> bool {
+  token.starts_with(SEALED_TOKEN_PREFIX)
+}
+
+pub fn seal_token(token: &str) -> LXMCLResult<String> {
+  if token.is_empty() || is_sealed_token(token) {
+    return Ok(token.to_string());
+  }
+  let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+  let ciphertext = get_cipher()?
+    .encrypt(&nonce, token.as_bytes())
+    .map_err(|_| AccountError::CredentialError)?;
+  let mut payload = nonce.to_vec();
+  payload.extend(ciphertext);
+  Ok(format!(
+    "{}{}",
+    SEALED_TOKEN_PREFIX,
+    general_purpose::STANDARD.encode(payload)
+  ))
+}
+
+// plaintext tokens (not migrated yet) are returned as is.
+pub fn unseal_token(token: &str) -> LXMCLResult<String> {
+  let Some(encoded) = token.strip_prefix(SEALED_TOKEN_PREFIX) else {
+    return Ok(token.to_string());
+  };
+  let payload = general_purpose::STANDARD
+    .decode(encoded)
+    .map_err(|_| AccountError::CredentialError)?;
+  if payload.len() < NONCE_LEN {
+    return Err(AccountError::CredentialError.into());
+  }
+  let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+  let plaintext = get_cipher()?
+    .decrypt(Nonce::from_slice(nonce), ciphertext)
+    .map_err(|_| AccountError::CredentialError)?;
+  String::from_utf8(plaintext).map_err(|_| AccountError::CredentialError.into())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn init_key() {
+    let _ = CREDENTIAL_KEY.set(Aes256Gcm::generate_key(OsRng));
+  }
+
+  #[test]
+  fn seal_round_trip() {
+    init_key();
+    let sealed = seal_token("secret-token").unwrap();
+    assert!(is_sealed_token(&sealed));
+    assert!(!sealed.contains("secret-token"));
+    assert_eq!(unseal_token(&sealed).unwrap(), "secret-token");
+    // sealing twice does not double-encrypt
+    assert_eq!(seal_token(&sealed).unwrap(), sealed);
+  }
+
+  #[test]
+  fn plaintext_and_empty_tokens_pass_through() {
+    init_key();
+    assert_eq!(seal_token("").unwrap(), "");
+    assert_eq!(unseal_token("").unwrap(), "");
+    assert_eq!(unseal_token("legacy-token").unwrap(), "legacy-token");
+  }
+
+  #[test]
+  fn tampered_tokens_are_rejected() {
+    init_key();
+    let sealed = seal_token("secret-token").unwrap();
+    let mut payload = general_purpose::STANDARD
+      .decode(sealed.strip_prefix(SEALED_TOKEN_PREFIX).unwrap())
+      .unwrap();
+    *payload.last_mut().unwrap() ^= 1;
+    let tampered = format!(
+      "{}{}",
+      SEALED_TOKEN_PREFIX,
+      general_purpose::STANDARD.encode(payload)
+    );
+    assert!(unseal_token(&tampered).is_err());
+    assert!(unseal_token("enc:v1:not base64").is_err());
+    assert!(unseal_token("enc:v1:AAAA").is_err());
+  }
+}
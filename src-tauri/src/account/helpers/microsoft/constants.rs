@@ -3,6 +3,11 @@ pub static SCOPE: &str = "XboxLive.signin offline_access";
 pub static DEVICE_AUTH_ENDPOINT: &str =
   "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
 pub static PROFILE_ENDPOINT: &str = "https://api.minecraftservices.com/minecraft/profile";
+pub static SKINS_ENDPOINT: &str = "https://api.minecraftservices.com/minecraft/profile/skins";
+pub static ACTIVE_SKIN_ENDPOINT: &str =
+  "https://api.minecraftservices.com/minecraft/profile/skins/active";
+pub static ACTIVE_CAPE_ENDPOINT: &str =
+  "https://api.minecraftservices.com/minecraft/profile/capes/active";
 pub static XSTS_AUTH_ENDPOINT: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
 pub static MINECRAFT_TOKEN_ENDPOINT: &str =
   "https://api.minecraftservices.com/authentication/login_with_xbox";
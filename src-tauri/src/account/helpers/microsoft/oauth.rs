@@ -132,7 +132,7 @@ async fn fetch_minecraft_token(
   Ok(response["access_token"].as_str().unwrap_or("").to_string())
 }
 
-async fn fetch_minecraft_profile(
+pub async fn fetch_minecraft_profile(
   app: &AppHandle,
   minecraft_token: String,
 ) -> LXMCLResult<MinecraftProfile> {
@@ -158,7 +158,27 @@ async fn parse_profile(app: &AppHandle, tokens: &OAuthTokens) -> LXMCLResult<Pla
   let (xsts_userhash, xsts_token) = fetch_xsts_token(app, xbl_token).await?;
   let minecraft_token = fetch_minecraft_token(app, xsts_userhash, xsts_token).await?;
   let profile = fetch_minecraft_profile(app, minecraft_token.clone()).await?;
+  let textures = parse_profile_textures(app, &profile).await?;
 
+  PlayerInfo {
+    id: "".to_string(),
+    uuid: Uuid::from_str(&profile.id).map_err(|_| AccountError::ParseError)?,
+    name: profile.name.clone(),
+    player_type: PlayerType::Microsoft,
+    auth_account: Some(profile.name.clone()),
+    access_token: Some(minecraft_token.clone()),
+    refresh_token: Some(tokens.refresh_token.clone()),
+    textures,
+    auth_server_url: None,
+  }
+  .with_generated_id()
+  .with_sealed_tokens()
+}
+
+pub async fn parse_profile_textures(
+  app: &AppHandle,
+  profile: &MinecraftProfile,
+) -> LXMCLResult<Vec<Texture>> {
   let mut textures = vec![];
   if let Some(skins) = &profile.skins {
     for skin in skins {
@@ -189,21 +209,7 @@ async fn parse_profile(app: &AppHandle, tokens: &OAuthTokens) -> LXMCLResult<Pla
     // this player didn't have a texture, use preset Steve skin instead
     textures = load_preset_skin(app, "steve".to_string())?;
   }
-
-  Ok(
-    PlayerInfo {
-      id: "".to_string(),
-      uuid: Uuid::from_str(&profile.id).map_err(|_| AccountError::ParseError)?,
-      name: profile.name.clone(),
-      player_type: PlayerType::Microsoft,
-      auth_account: Some(profile.name.clone()),
-      access_token: Some(minecraft_token.clone()),
-      refresh_token: Some(tokens.refresh_token.clone()),
-      textures,
-      auth_server_url: None,
-    }
-    .with_generated_id(),
-  )
+  Ok(textures)
 }
 
 pub async fn login(app: &AppHandle, auth_info: DeviceAuthResponseInfo) -> LXMCLResult<PlayerInfo> {
@@ -224,10 +230,7 @@ pub async fn refresh(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<Player
     .post(OAUTH_TOKEN_ENDPOINT)
     .form(&[
       ("client_id", CLIENT_ID),
-      (
-        "refresh_token",
-        player.refresh_token.clone().unwrap_or_default().as_str(),
-      ),
+      ("refresh_token", player.plain_refresh_token()?.as_str()),
       ("grant_type", "refresh_token"),
     ])
     .send()
@@ -252,7 +255,7 @@ pub async fn validate(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<bool>
     .get(PROFILE_ENDPOINT)
     .header(
       "Authorization",
-      format!("Bearer {}", player.access_token.clone().unwrap_or_default()),
+      format!("Bearer {}", player.plain_access_token()?),
     )
     .send()
     .await
@@ -0,0 +1,123 @@
+use crate::account::helpers::microsoft::constants::{
+  ACTIVE_CAPE_ENDPOINT, ACTIVE_SKIN_ENDPOINT, PROFILE_ENDPOINT, SKINS_ENDPOINT,
+};
+use crate::account::helpers::microsoft::models::MinecraftProfile;
+use crate::account::helpers::microsoft::oauth::parse_profile_textures;
+use crate::account::helpers::misc::fetch_image;
+use crate::account::models::{AccountError, CapeInfo, PlayerInfo, Texture};
+use crate::error::LXMCLResult;
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest::{self, multipart, RequestBuilder, StatusCode};
+
+// the profile API answers texture changes with the updated profile.
+async fn send_profile_request(
+  app: &AppHandle,
+  request: RequestBuilder,
+) -> LXMCLResult<Vec<Texture>> {
+  let response = request
+    .send()
+    .await
+    .map_err(|_| AccountError::NetworkError)?;
+  match response.status() {
+    status if status.is_success() => {}
+    StatusCode::UNAUTHORIZED => return Err(AccountError::Expired.into()),
+    StatusCode::BAD_REQUEST => return Err(AccountError::TextureError.into()),
+    _ => return Err(AccountError::NetworkError.into()),
+  }
+  let profile = response
+    .json::<MinecraftProfile>()
+    .await
+    .map_err(|_| AccountError::ParseError)?;
+  parse_profile_textures(app, &profile).await
+}
+
+fn bearer_token(player: &PlayerInfo) -> LXMCLResult<String> {
+  Ok(format!("Bearer {}", player.plain_access_token()?))
+}
+
+// model: "slim" for the Alex model, otherwise the classic (Steve) model.
+pub async fn upload_skin(
+  app: &AppHandle,
+  player: &PlayerInfo,
+  image_bytes: Vec<u8>,
+  model: &str,
+) -> LXMCLResult<Vec<Texture>> {
+  let client = app.state::<reqwest::Client>();
+  let variant = if model == "slim" { "slim" } else { "classic" };
+  let file_part = multipart::Part::bytes(image_bytes)
+    .file_name("skin.png")
+    .mime_str("image/png")
+    .map_err(|_| AccountError::TextureError)?;
+  let form = multipart::Form::new()
+    .text("variant", variant)
+    .part("file", file_part);
+  let request = client
+    .post(SKINS_ENDPOINT)
+    .header("Authorization", bearer_token(player)?)
+    .multipart(form);
+  send_profile_request(app, request).await
+}
+
+pub async fn reset_skin(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<Vec<Texture>> {
+  let client = app.state::<reqwest::Client>();
+  let request = client
+    .delete(ACTIVE_SKIN_ENDPOINT)
+    .header("Authorization", bearer_token(player)?);
+  send_profile_request(app, request).await
+}
+
+pub async fn retrieve_capes(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<Vec<CapeInfo>> {
+  let client = app.state::<reqwest::Client>();
+  let response = client
+    .get(PROFILE_ENDPOINT)
+    .header("Authorization", bearer_token(player)?)
+    .send()
+    .await
+    .map_err(|_| AccountError::NetworkError)?;
+  if response.status() == StatusCode::UNAUTHORIZED {
+    return Err(AccountError::Expired.into());
+  }
+  let profile = response
+    .json::<MinecraftProfile>()
+    .await
+    .map_err(|_| AccountError::NoMinecraftProfile)?;
+
+  let mut capes = vec![];
+  for cape in profile.capes.unwrap_or_default() {
+    let Some(id) = cape.id else {
+      continue;
+    };
+    let image = match fetch_image(app, cape.url).await {
+      Ok(image) => Some(image),
+      Err(e) => {
+        log::warn!("failed to fetch the image of cape {id}: {}", e.0);
+        None
+      }
+    };
+    capes.push(CapeInfo {
+      id,
+      alias: cape.alias.unwrap_or_default(),
+      image,
+      is_active: cape.state == "ACTIVE",
+    });
+  }
+  Ok(capes)
+}
+
+// show one of the owned capes, or hide the cape if `cape_id` is None.
+pub async fn select_cape(
+  app: &AppHandle,
+  player: &PlayerInfo,
+  cape_id: Option<String>,
+) -> LXMCLResult<Vec<Texture>> {
+  let client = app.state::<reqwest::Client>();
+  let request = match cape_id {
+    Some(cape_id) => client
+      .put(ACTIVE_CAPE_ENDPOINT)
+      .json(&json!({ "capeId": cape_id })),
+    None => client.delete(ACTIVE_CAPE_ENDPOINT),
+  }
+  .header("Authorization", bearer_token(player)?);
+  send_profile_request(app, request).await
+}
@@ -3,6 +3,8 @@ use crate::account::models::{
   AccountError, AccountInfo, DeviceAuthResponseInfo, OAuthErrorResponse, OAuthTokens, PlayerInfo,
 };
 use crate::error::LXMCLResult;
+use crate::instance::models::misc::PlayerBinding;
+use crate::launch::models::LaunchError;
 use crate::launcher_config::models::LauncherConfig;
 use crate::storage::Storage;
 use crate::utils::image::{decode_image, ImageWrapper};
@@ -54,6 +56,51 @@ pub fn get_selected_player_info(app: &AppHandle) -> LXMCLResult<PlayerInfo> {
   Ok(player_info.clone())
 }
 
+/// The player to launch the instance with, following its player binding (if any).
+pub fn get_instance_player_info(
+  app: &AppHandle,
+  binding: Option<&PlayerBinding>,
+) -> LXMCLResult<PlayerInfo> {
+  let Some(binding) = binding else {
+    return get_selected_player_info(app);
+  };
+  let account_binding = app.state::<Mutex<AccountInfo>>();
+  let account_state = account_binding.lock()?;
+
+  if let Some(player_id) = &binding.player_id {
+    return account_state
+      .players
+      .iter()
+      .find(|player| player.id == *player_id)
+      .cloned()
+      .ok_or(LaunchError::BoundPlayerNotFound.into());
+  }
+
+  let matches_binding = |player: &&PlayerInfo| {
+    binding
+      .player_type
+      .as_ref()
+      .map_or(true, |player_type| player.player_type == *player_type)
+      && binding
+        .auth_server_url
+        .as_ref()
+        .map_or(true, |url| player.auth_server_url.as_ref() == Some(url))
+  };
+  let selected_player_id = {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let config_state = config_binding.lock()?;
+    config_state.states.shared.selected_player_id.clone()
+  };
+  // prefer the selected player, if it meets the requirement
+  let mut candidates = account_state.players.iter().filter(matches_binding);
+  let first_candidate = candidates.clone().next();
+  candidates
+    .find(|player| player.id == selected_player_id)
+    .or(first_candidate)
+    .cloned()
+    .ok_or(LaunchError::NoPlayerMatchesBinding.into())
+}
+
 pub async fn check_full_login_availability(app: &AppHandle) -> LXMCLResult<()> {
   let loc_flag = is_china_mainland_ip(app).await;
 
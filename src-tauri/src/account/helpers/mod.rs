@@ -1,5 +1,7 @@
 pub mod authlib_injector;
+pub mod credential;
 pub mod microsoft;
 pub mod misc;
 pub mod offline;
 pub mod skin;
+pub mod token_refresh;
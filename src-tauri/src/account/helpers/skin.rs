@@ -1,4 +1,7 @@
-use image::RgbaImage;
+use crate::account::models::AccountError;
+use crate::error::LXMCLResult;
+use image::{ImageFormat, ImageReader, RgbaImage};
+use std::io::Cursor;
 
 pub fn draw_avatar(size: u32, img: &RgbaImage) -> RgbaImage {
   let (skin_width, _) = img.dimensions();
@@ -52,3 +55,27 @@ fn draw_image_section(
     }
   }
 }
+
+// Check an image to upload as a skin or cape: it must be a PNG, of 64x32 (legacy skin and cape)
+// or 64x64 (skin), or an integer multiple of them (HD textures). Legacy 22x17 capes are accepted.
+pub fn validate_texture_image(bytes: &[u8], texture_type: &str) -> LXMCLResult<()> {
+  let reader = ImageReader::with_format(Cursor::new(bytes), ImageFormat::Png);
+  let (width, height) = reader
+    .into_dimensions()
+    .map_err(|_| AccountError::TextureError)?;
+  if width == 0 || height == 0 {
+    return Err(AccountError::TextureError.into());
+  }
+
+  let is_valid = match texture_type {
+    "SKIN" => width % 64 == 0 && (height == width || height * 2 == width),
+    "CAPE" => {
+      (width % 64 == 0 && height * 2 == width) || (width % 22 == 0 && height * 22 == width * 17)
+    }
+    _ => false,
+  };
+  if !is_valid {
+    return Err(AccountError::TextureError.into());
+  }
+  Ok(())
+}
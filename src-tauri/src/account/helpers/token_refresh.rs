@@ -0,0 +1,184 @@
+use crate::account::constants::{
+  OPAQUE_TOKEN_VALIDATE_INTERVAL, TOKEN_REFRESH_AHEAD, TOKEN_REFRESH_CHECK_INTERVAL,
+  TOKEN_REFRESH_FAILED_EVENT,
+};
+use crate::account::helpers::authlib_injector::info::get_auth_server_info_by_url;
+use crate::account::helpers::{authlib_injector, microsoft};
+use crate::account::models::{
+  AccountError, AccountInfo, AuthServer, PlayerInfo, PlayerType, TokenRefreshFailedPayload,
+};
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::storage::Storage;
+use jsonwebtoken::{decode, DecodingKey, Validation};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+#[derive(Deserialize)]
+struct ExpiryClaims {
+  exp: Option<u64>,
+}
+
+// refresh state of a player, tracked by the scheduler only (not saved)
+#[derive(Default)]
+struct PlayerRefreshState {
+  last_validated_at: u64,
+  // the sealed tokens which failed to refresh, the player is skipped until re-login.
+  // both are kept, as players logged in with a password have no refresh token.
+  failed_tokens: Option<(Option<String>, Option<String>)>,
+}
+
+fn get_sealed_tokens(player: &PlayerInfo) -> (Option<String>, Option<String>) {
+  (player.access_token.clone(), player.refresh_token.clone())
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs())
+    .unwrap_or_default()
+}
+
+/// Expiry (UNIX timestamp) of the player's access token, if it is a JWT with the `exp` claim.
+/// Microsoft tokens are JWTs, Yggdrasil tokens may be opaque strings.
+pub fn get_token_expiry(player: &PlayerInfo) -> Option<u64> {
+  // the claims are only read to schedule the refresh, the signature is not checked
+  let mut validation = Validation::default();
+  validation.insecure_disable_signature_validation();
+  validation.validate_exp = false;
+  validation.validate_aud = false;
+  validation.required_spec_claims.clear();
+  decode::<ExpiryClaims>(
+    &player.plain_access_token().ok()?,
+    &DecodingKey::from_secret(&[]),
+    &validation,
+  )
+  .ok()?
+  .claims
+  .exp
+}
+
+/// Refresh the tokens of an online player, using its login method.
+pub async fn refresh_player_info(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<PlayerInfo> {
+  match player.player_type {
+    PlayerType::ThirdParty => {
+      let auth_server = AuthServer::from(get_auth_server_info_by_url(
+        app,
+        player.auth_server_url.clone().unwrap_or_default(),
+      )?);
+      authlib_injector::common::refresh(app, player, &auth_server).await
+    }
+    PlayerType::Microsoft => microsoft::oauth::refresh(app, player).await,
+    PlayerType::Offline => Err(AccountError::Invalid.into()),
+  }
+}
+
+/// Replace the saved player with the refreshed one.
+pub fn save_refreshed_player(
+  app: &AppHandle,
+  player_id: &str,
+  refreshed_player: PlayerInfo,
+) -> LXMCLResult<()> {
+  let account_binding = app.state::<Mutex<AccountInfo>>();
+  let mut account_state = account_binding.lock()?;
+  if let Some(player) = account_state
+    .players
+    .iter_mut()
+    .find(|player| player.id == player_id)
+  {
+    *player = refreshed_player;
+    account_state.save()?;
+  }
+  Ok(())
+}
+
+async fn is_token_valid(app: &AppHandle, player: &PlayerInfo) -> LXMCLResult<bool> {
+  match player.player_type {
+    PlayerType::ThirdParty => authlib_injector::common::validate(app, player).await,
+    PlayerType::Microsoft => microsoft::oauth::validate(app, player).await,
+    PlayerType::Offline => Ok(true),
+  }
+}
+
+// the auth server rejected the tokens, the user needs to log in again.
+// other errors (e.g. network or parse errors) are retried on the next check.
+fn is_permanent_failure(error: &LXMCLError) -> bool {
+  [AccountError::Expired, AccountError::Invalid]
+    .iter()
+    .any(|kind| error.0 == kind.to_string())
+}
+
+async fn check_player(app: &AppHandle, player: &PlayerInfo, state: &mut PlayerRefreshState) {
+  let now = now_secs();
+  let is_due = match get_token_expiry(player) {
+    Some(exp) => exp <= now + TOKEN_REFRESH_AHEAD.as_secs(),
+    None => {
+      if now < state.last_validated_at + OPAQUE_TOKEN_VALIDATE_INTERVAL.as_secs() {
+        return;
+      }
+      match is_token_valid(app, player).await {
+        Ok(true) => {
+          state.last_validated_at = now;
+          false
+        }
+        Ok(false) => true,
+        Err(_) => return,
+      }
+    }
+  };
+  if !is_due {
+    return;
+  }
+
+  match refresh_player_info(app, player).await {
+    Ok(refreshed_player) => {
+      state.last_validated_at = now;
+      let _ = save_refreshed_player(app, &player.id, refreshed_player);
+    }
+    Err(error) if is_permanent_failure(&error) => {
+      state.failed_tokens = Some(get_sealed_tokens(player));
+      let _ = app.emit_to(
+        "main",
+        TOKEN_REFRESH_FAILED_EVENT,
+        TokenRefreshFailedPayload {
+          player_id: player.id.clone(),
+          player_name: player.name.clone(),
+          reason: error.0,
+        },
+      );
+    }
+    Err(_) => {}
+  }
+}
+
+/// Check the tokens of all online players periodically, and refresh them before they expire.
+/// An event is emitted when a refresh fails permanently, the player has to log in again then.
+pub async fn run_token_refresh_scheduler(app: &AppHandle) {
+  let mut states: HashMap<String, PlayerRefreshState> = HashMap::new();
+  loop {
+    let players = match app.state::<Mutex<AccountInfo>>().lock() {
+      Ok(account_state) => account_state.players.clone(),
+      Err(_) => Vec::new(),
+    };
+    states.retain(|id, _| players.iter().any(|player| player.id == *id));
+
+    for player in players
+      .iter()
+      .filter(|player| player.player_type != PlayerType::Offline)
+    {
+      let state = states.entry(player.id.clone()).or_default();
+      if let Some(failed_tokens) = &state.failed_tokens {
+        if *failed_tokens == get_sealed_tokens(player) {
+          continue;
+        }
+        // logged in again since the failure
+        state.failed_tokens = None;
+      }
+      check_player(app, player, state).await;
+    }
+
+    tokio::time::sleep(TOKEN_REFRESH_CHECK_INTERVAL).await;
+  }
+}
@@ -1,6 +1,8 @@
 use crate::account::constants::ACCOUNTS_FILE_NAME;
 use crate::account::helpers::authlib_injector::constants::PRESET_AUTH_SERVERS;
+use crate::account::helpers::credential::{is_sealed_token, seal_token, unseal_token};
 use crate::account::helpers::skin::draw_avatar;
+use crate::error::LXMCLResult;
 use crate::storage::Storage;
 use crate::utils::image::ImageWrapper;
 use crate::APP_DATA_DIR;
@@ -29,6 +31,17 @@ pub struct Texture {
   pub preset: Option<String>,
 }
 
+// a cape owned by a Microsoft account
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapeInfo {
+  pub id: String,
+  pub alias: String,
+  // None if the image could not be fetched, the cape can still be selected
+  pub image: Option<ImageWrapper>,
+  pub is_active: bool,
+}
+
 // only for the client
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -103,6 +116,34 @@ impl PlayerInfo {
     self.id = format!("{}:{}:{}", self.name, server_identity, self.uuid);
     self
   }
+
+  /// Encrypt the tokens, they are kept sealed in memory and in the accounts file
+  pub fn with_sealed_tokens(mut self) -> LXMCLResult<Self> {
+    for token in [&mut self.access_token, &mut self.refresh_token]
+      .into_iter()
+      .flatten()
+    {
+      *token = seal_token(token)?;
+    }
+    Ok(self)
+  }
+
+  /// Decrypted access token, empty if absent.
+  /// Fails if it cannot be decrypted (e.g. key file lost), the player has to log in again then.
+  pub fn plain_access_token(&self) -> LXMCLResult<String> {
+    self
+      .access_token
+      .as_deref()
+      .map_or(Ok(String::new()), unseal_token)
+  }
+
+  /// Decrypted refresh token, empty if absent.
+  pub fn plain_refresh_token(&self) -> LXMCLResult<String> {
+    self
+      .refresh_token
+      .as_deref()
+      .map_or(Ok(String::new()), unseal_token)
+  }
 }
 
 impl From<Player> for PlayerInfo {
@@ -132,6 +173,14 @@ impl PartialEq for PlayerInfo {
 
 impl Eq for PlayerInfo {}
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRefreshFailedPayload {
+  pub player_id: String,
+  pub player_name: String,
+  pub reason: String,
+}
+
 #[derive(Deserialize)]
 // received from auth server, do not need camel case
 pub struct DeviceAuthResponse {
@@ -253,6 +302,26 @@ impl AccountInfo {
   pub fn get_player_by_id_mut(&mut self, id: String) -> Option<&mut PlayerInfo> {
     self.players.iter_mut().find(|player| player.id == id)
   }
+
+  /// Seal the plaintext tokens saved by previous versions, returns true if any has been migrated
+  pub fn migrate_plaintext_tokens(&mut self) -> LXMCLResult<bool> {
+    let has_plaintext = self.players.iter().any(|player| {
+      [&player.access_token, &player.refresh_token]
+        .into_iter()
+        .flatten()
+        .any(|token| !token.is_empty() && !is_sealed_token(token))
+    });
+    if !has_plaintext {
+      return Ok(false);
+    }
+    self.players = self
+      .players
+      .iter()
+      .cloned()
+      .map(PlayerInfo::with_sealed_tokens)
+      .collect::<LXMCLResult<Vec<_>>>()?;
+    Ok(true)
+  }
 }
 
 impl Storage for AccountInfo {
@@ -275,6 +344,7 @@ pub enum AccountError {
   NoDownloadApi,
   SaveError,
   NoMinecraftProfile,
+  CredentialError,
 }
 
 impl std::error::Error for AccountError {}
@@ -0,0 +1,285 @@
+use crate::account::commands::{refresh_player, retrieve_player_list};
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::instance::commands::retrieve_instance_list;
+use crate::instance::models::misc::ModLoaderType;
+use crate::launch::commands::{
+  check_game_files, launch_game, select_suitable_jre, validate_game_files, validate_selected_player,
+};
+use crate::launch::constants::GAME_PROCESS_EXIT_EVENT;
+use crate::launch::models::{GameProcessExitPayload, LaunchError, LaunchingState};
+use crate::launcher_config::helpers::updater::get_build_version;
+use crate::launcher_config::models::LauncherConfig;
+use crate::tasks::background::monitor_background_process;
+use crate::tasks::events::GEventStatus;
+use crate::tasks::wait::{wait_for_task_group, watch_task_group_events};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Listener, Manager};
+
+const USAGE: &str = "Usage:
+  lxmcl list-instances
+  lxmcl list-players
+  lxmcl launch <instance-id> [--player <player-id>] [--world <name>] [--server <address>]
+  lxmcl version
+  lxmcl help";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+  Help,
+  Version,
+  ListInstances,
+  ListPlayers,
+  Launch {
+    instance_id: String,
+    player_id: Option<String>,
+    quick_play_singleplayer: Option<String>,
+    quick_play_multiplayer: Option<String>,
+  },
+  Invalid(String),
+}
+
+impl CliCommand {
+  // returns None if the arguments are not a command line invocation (e.g. a deep link url),
+  // then the launcher starts with GUI as usual.
+  pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Option<Self> {
+    let command = match args.next()?.as_str() {
+      "help" | "--help" | "-h" => CliCommand::Help,
+      "version" | "--version" | "-V" => CliCommand::Version,
+      "list-instances" => CliCommand::ListInstances,
+      "list-players" => CliCommand::ListPlayers,
+      "launch" => {
+        let Some(instance_id) = args.next() else {
+          return Some(CliCommand::Invalid("missing instance id".to_string()));
+        };
+        let mut player_id = None;
+        let mut quick_play_singleplayer = None;
+        let mut quick_play_multiplayer = None;
+        while let Some(option) = args.next() {
+          let target = match option.as_str() {
+            "--player" => &mut player_id,
+            "--world" => &mut quick_play_singleplayer,
+            "--server" => &mut quick_play_multiplayer,
+            _ => return Some(CliCommand::Invalid(format!("unknown option '{option}'"))),
+          };
+          match args.next() {
+            Some(value) => *target = Some(value),
+            None => return Some(CliCommand::Invalid(format!("missing value of '{option}'"))),
+          }
+        }
+        CliCommand::Launch {
+          instance_id,
+          player_id,
+          quick_play_singleplayer,
+          quick_play_multiplayer,
+        }
+      }
+      _ => return None,
+    };
+    Some(command)
+  }
+}
+
+// execute the command, returns the exit code of the launcher process.
+pub async fn execute_cli_command(app: &AppHandle, command: CliCommand) -> i32 {
+  let result = match command {
+    CliCommand::Help => {
+      println!("{USAGE}");
+      Ok(0)
+    }
+    CliCommand::Version => {
+      println!("LXMCL {}", get_build_version());
+      Ok(0)
+    }
+    CliCommand::Invalid(reason) => {
+      eprintln!("Error: {reason}\n\n{USAGE}");
+      Ok(2)
+    }
+    CliCommand::ListInstances => list_instances(app).await.map(|_| 0),
+    CliCommand::ListPlayers => list_players(app).map(|_| 0),
+    CliCommand::Launch {
+      instance_id,
+      player_id,
+      quick_play_singleplayer,
+      quick_play_multiplayer,
+    } => {
+      launch_instance(
+        app,
+        instance_id,
+        player_id,
+        quick_play_singleplayer,
+        quick_play_multiplayer,
+      )
+      .await
+    }
+  };
+
+  result.unwrap_or_else(|e| {
+    eprintln!("Error: {}", e.0);
+    1
+  })
+}
+
+async fn list_instances(app: &AppHandle) -> LXMCLResult<()> {
+  let mut instances = retrieve_instance_list(app.clone()).await?;
+  instances.sort_by(|a, b| a.name.cmp(&b.name));
+  for instance in instances {
+    let mod_loader = match instance.mod_loader.loader_type {
+      ModLoaderType::Unknown => String::new(),
+      loader_type => format!("{} {}", loader_type, instance.mod_loader.version),
+    };
+    println!(
+      "{}\t{}\t{}\t{}",
+      instance.id, instance.name, instance.version, mod_loader
+    );
+  }
+  Ok(())
+}
+
+fn list_players(app: &AppHandle) -> LXMCLResult<()> {
+  let selected_player_id = {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let config_state = config_binding.lock()?;
+    config_state.states.shared.selected_player_id.clone()
+  };
+  for player in retrieve_player_list(app.clone())? {
+    println!(
+      "{}{}\t{}\t{:?}",
+      if player.id == selected_player_id {
+        "* "
+      } else {
+        ""
+      },
+      player.id,
+      player.name,
+      player.player_type
+    );
+  }
+  Ok(())
+}
+
+// validate the game files, download the incomplete ones and validate again.
+async fn patch_game_files(
+  app: &AppHandle,
+  task_group_events: &Mutex<HashMap<String, GEventStatus>>,
+) -> LXMCLResult<()> {
+  if let Some(task_group) = check_game_files(
+    app,
+    &app.state::<Mutex<LauncherConfig>>(),
+    &app.state::<Mutex<Vec<LaunchingState>>>(),
+  )
+  .await?
+  {
+    println!("[Download] Downloading missing game files");
+    wait_for_task_group(app, &task_group, task_group_events, |progress| {
+      println!(
+        "[Download] {}/{} files, current {:.1}/{:.1} MiB",
+        progress.finished_files,
+        progress.total_files,
+        progress.current_size as f64 / 1048576.0,
+        progress.total_size as f64 / 1048576.0
+      );
+    })
+    .await?;
+    println!("[Download] Completed");
+    validate_game_files(app.clone(), app.state(), app.state()).await?;
+  }
+  Ok(())
+}
+
+async fn launch_instance(
+  app: &AppHandle,
+  instance_id: String,
+  player_id: Option<String>,
+  quick_play_singleplayer: Option<String>,
+  quick_play_multiplayer: Option<String>,
+) -> LXMCLResult<i32> {
+  // the task monitor is needed to download missing game files
+  let app_handle = app.clone();
+  tauri::async_runtime::spawn(async move {
+    monitor_background_process(app_handle).await;
+  });
+
+  retrieve_instance_list(app.clone()).await?;
+  // select the player only for this launch, without saving the launcher config
+  if let Some(player_id) = player_id {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let mut config_state = config_binding.lock()?;
+    config_state.states.shared.selected_player_id = player_id;
+  }
+
+  println!("[1/4] Selecting Java runtime");
+  select_suitable_jre(
+    app.clone(),
+    instance_id,
+    app.state(),
+    app.state(),
+    app.state(),
+  )
+  .await?;
+  let launching_id = {
+    let launching_queue_state = app.state::<Mutex<Vec<LaunchingState>>>();
+    let launching_queue = launching_queue_state.lock()?;
+    let launching = launching_queue
+      .last()
+      .ok_or(LaunchError::LaunchingStateNotFound)?;
+    println!("Using Java: {}", launching.selected_java.exec_path);
+    launching.id
+  };
+
+  println!("[2/4] Validating game files");
+  // listen before scheduling, the group may fail before we start waiting for it
+  let (listener, task_group_events) = watch_task_group_events(app);
+  let patch_result = patch_game_files(app, &task_group_events).await;
+  app.unlisten(listener);
+  patch_result?;
+
+  println!("[3/4] Validating selected player");
+  let validation = validate_selected_player(app.clone(), app.state()).await?;
+  if !validation.is_valid {
+    // the player chosen in the validation, it may be bound to the instance
+    let player_name = {
+      let launching_queue_state = app.state::<Mutex<Vec<LaunchingState>>>();
+      let launching_queue = launching_queue_state.lock()?;
+      launching_queue
+        .last()
+        .and_then(|launching| launching.selected_player.as_ref())
+        .map(|player| player.name.clone())
+        .ok_or(LaunchError::LaunchingStateNotFound)?
+    };
+    println!("Refreshing the login of player {player_name}");
+    refresh_player(app.clone(), validation.player_id).await?;
+    if !validate_selected_player(app.clone(), app.state())
+      .await?
+      .is_valid
+    {
+      return Err(LXMCLError(
+        "the login of the selected player is expired, please log in again in the launcher"
+          .to_string(),
+      ));
+    }
+  }
+
+  println!("[4/4] Launching game");
+  let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<Option<i32>>();
+  let exit_tx = Mutex::new(Some(exit_tx));
+  app.listen_any(GAME_PROCESS_EXIT_EVENT, move |event| {
+    if let Ok(payload) = serde_json::from_str::<GameProcessExitPayload>(event.payload()) {
+      if payload.id == launching_id {
+        if let Some(tx) = exit_tx.lock().unwrap().take() {
+          let _ = tx.send(payload.exit_code);
+        }
+      }
+    }
+  });
+  launch_game(
+    app.clone(),
+    app.state(),
+    quick_play_singleplayer,
+    quick_play_multiplayer,
+  )
+  .await?;
+
+  let exit_code = exit_rx.await.ok().flatten().unwrap_or(1);
+  println!("Game exited with code {exit_code}");
+  Ok(exit_code)
+}
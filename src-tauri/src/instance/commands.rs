@@ -4,36 +4,58 @@ use crate::instance::helpers::client_json::{replace_native_libraries, McClientIn
 use crate::instance::helpers::game_version::{compare_game_versions, get_major_game_version};
 use crate::instance::helpers::loader::common::{execute_processors, install_mod_loader};
 use crate::instance::helpers::loader::forge::InstallProfile;
+use crate::instance::helpers::loader::liteloader::install_liteloader;
+use crate::instance::helpers::loader::optifine::{finish_optifine_install, install_optifine};
 use crate::instance::helpers::misc::{
   get_instance_game_config, get_instance_subdir_path_by_id, get_instance_subdir_paths,
   refresh_and_update_instances, unify_instance_name,
 };
 use crate::instance::helpers::modpack::curseforge::CurseForgeManifest;
-use crate::instance::helpers::modpack::misc::{extract_overrides, ModpackMetaInfo};
+use crate::instance::helpers::modpack::misc::{
+  extract_overrides, write_modpack_archive, ModpackMetaInfo,
+};
 use crate::instance::helpers::modpack::modrinth::ModrinthManifest;
 use crate::instance::helpers::modpack::multimc::MultiMcManifest;
 use crate::instance::helpers::mods::common::{
   add_local_mod_translations, get_mod_info_from_dir, get_mod_info_from_jar,
 };
-use crate::instance::helpers::options_txt::get_zh_hans_lang_tag;
+use crate::instance::helpers::mods::dependency::{
+  check_mod_dependency_graph, fetch_remote_mod_by_mod_id, get_builtin_mod_versions,
+  read_dependency_nodes,
+};
+use crate::instance::helpers::options_txt::{
+  get_zh_hans_lang_tag, uses_legacy_key_codes, OptionsTxt,
+};
 use crate::instance::helpers::resourcepack::{
   load_resourcepack_from_dir, load_resourcepack_from_zip,
 };
-use crate::instance::helpers::server::{load_servers_info_from_path, query_server_status};
-use crate::instance::helpers::world::{level_data_to_world_info, load_level_data_from_path};
+use crate::instance::helpers::server::{parse_server_list_file, query_server_status, ServersDat};
+use crate::instance::helpers::world::{
+  level_data_to_world_info, load_level_data_from_path, save_level_data_patch,
+};
+use crate::instance::helpers::world_backup::{
+  extract_world_backup, is_world_in_use, list_world_backups, remove_expired_world_backups,
+  write_world_backup,
+};
 use crate::instance::models::misc::{
-  GameServerInfo, Instance, InstanceError, InstanceSubdirType, InstanceSummary, LocalModInfo,
-  ModLoader, ModLoaderStatus, ModLoaderType, ResourcePackInfo, SchematicInfo, ScreenshotInfo,
-  ShaderPackInfo,
+  GameOptions, GameServerEntry, GameServerInfo, Instance, InstanceError, InstanceSubdirType,
+  InstanceSummary, LocalModInfo, ModDependencyIssue, ModDependencyIssueType, ModLoader,
+  ModLoaderStatus, ModLoaderType, OptiFine, OptionsTemplate, OptionsTemplateList, PlayerBinding,
+  ResourcePackInfo, SchematicInfo, ScreenshotInfo, ShaderPackInfo,
 };
-use crate::instance::models::world::base::WorldInfo;
-use crate::instance::models::world::level::LevelData;
+use crate::instance::models::world::base::{WorldBackupInfo, WorldInfo};
+use crate::instance::models::world::level::{LevelData, LevelDataPatch};
 use crate::launch::helpers::file_validator::{get_invalid_assets, get_invalid_library_files};
+use crate::launch::helpers::process_monitor::is_instance_running;
 use crate::launcher_config::helpers::misc::get_global_game_config;
+use crate::launcher_config::helpers::shared_store::get_shared_store_dir;
 use crate::launcher_config::models::{GameConfig, GameDirectory, LauncherConfig};
 use crate::partial::{PartialError, PartialUpdate};
 use crate::resource::helpers::misc::get_source_priority_list;
-use crate::resource::models::{GameClientResourceInfo, ModLoaderResourceInfo};
+use crate::resource::models::{
+  GameClientResourceInfo, ModLoaderResourceInfo, OptiFineResourceInfo, OtherResourceSource,
+  ResourceError,
+};
 use crate::storage::{load_json_async, save_json_async, Storage};
 use crate::tasks::commands::schedule_progressive_task_group;
 use crate::tasks::download::DownloadParam;
@@ -86,6 +108,7 @@ pub async fn retrieve_instance_list(app: AppHandle) -> LXMCLResult<Vec<InstanceS
       version_path: instance.version_path.clone(),
       version: instance.version.clone(),
       mod_loader: instance.mod_loader.clone(),
+      optifine: instance.optifine.clone(),
       // skip fallback remote fetch in `get_major_game_version` and `compare_game_versions` to avoid instance list load delay.
       // ref: https://github.com/UNIkeEN/SJMCL/pull/799
       major_version: get_major_game_version(&app, &instance.version, false).await,
@@ -94,6 +117,7 @@ pub async fn retrieve_instance_list(app: AppHandle) -> LXMCLResult<Vec<InstanceS
         .is_ge(),
       use_spec_game_config: instance.use_spec_game_config,
       is_version_isolated,
+      player_binding: instance.player_binding.clone(),
     });
   }
   Ok(summary_list)
@@ -135,6 +159,8 @@ pub async fn update_instance_config(
       if value && instance.spec_game_config.is_none() {
         instance.spec_game_config = Some(get_global_game_config(&app));
       }
+    } else if key_path == "player_binding" {
+      instance.player_binding = serde_json::from_str::<Option<PlayerBinding>>(&value)?;
     } else if key_path.starts_with("spec_game_config.") {
       let key = key_path.split_at("spec_game_config.".len()).1;
       let game_config = instance.spec_game_config.as_mut().unwrap();
@@ -413,8 +439,8 @@ pub async fn retrieve_game_server_list(
     };
 
   let nbt_path = game_root_dir.join("servers.dat");
-  let servers = match load_servers_info_from_path(&nbt_path).await {
-    Ok(servers) => servers,
+  let servers = match ServersDat::load(&nbt_path).await {
+    Ok(servers_dat) => servers_dat.entries(),
     Err(_) => return Err(InstanceError::ServerNbtReadError.into()),
   };
   for server in servers {
@@ -423,10 +449,12 @@ pub async fn retrieve_game_server_list(
       name: server.name,
       description: String::new(),
       icon_src: server.icon.unwrap_or_default(),
+      accept_textures: server.accept_textures,
       is_queried: false,
       players_max: 0,
       players_online: 0,
       online: false,
+      latency: None,
     });
   }
 
@@ -441,8 +469,11 @@ pub async fn retrieve_game_server_list(
               server.players_online = query_result.players.online as usize;
               server.players_max = query_result.players.max as usize;
               server.online = query_result.online;
-              server.description = query_result.description.text.unwrap_or_default();
-              server.icon_src = query_result.favicon.unwrap_or_default();
+              server.description = query_result.description;
+              server.latency = query_result.latency;
+              if let Some(favicon) = query_result.favicon {
+                server.icon_src = favicon;
+              }
             }
             Err(_) => {
               server.is_queried = false;
@@ -465,6 +496,98 @@ pub async fn retrieve_game_server_list(
   Ok(game_servers)
 }
 
+fn get_servers_dat_path(app: &AppHandle, instance_id: &String) -> LXMCLResult<PathBuf> {
+  get_instance_subdir_path_by_id(app, instance_id, &InstanceSubdirType::Root)
+    .map(|root_dir| root_dir.join("servers.dat"))
+    .ok_or(InstanceError::InstanceNotFoundByID.into())
+}
+
+// load servers.dat of the instance, apply the edit and save it back.
+async fn edit_servers_dat(
+  app: &AppHandle,
+  instance_id: &String,
+  edit: impl FnOnce(&mut ServersDat) -> LXMCLResult<()>,
+) -> LXMCLResult<()> {
+  let nbt_path = get_servers_dat_path(app, instance_id)?;
+  let mut servers_dat = ServersDat::load(&nbt_path).await?;
+  edit(&mut servers_dat)?;
+  servers_dat
+    .save(&nbt_path)
+    .await
+    .map_err(|_| InstanceError::ServerNbtWriteError.into())
+}
+
+#[tauri::command]
+pub async fn add_game_server(
+  app: AppHandle,
+  instance_id: String,
+  server: GameServerEntry,
+  index: Option<usize>,
+) -> LXMCLResult<()> {
+  edit_servers_dat(&app, &instance_id, |servers_dat| {
+    servers_dat.insert(index, &server)
+  })
+  .await
+}
+
+#[tauri::command]
+pub async fn update_game_server(
+  app: AppHandle,
+  instance_id: String,
+  index: usize,
+  server: GameServerEntry,
+) -> LXMCLResult<()> {
+  edit_servers_dat(&app, &instance_id, |servers_dat| {
+    servers_dat.update(index, &server)
+  })
+  .await
+}
+
+#[tauri::command]
+pub async fn delete_game_server(
+  app: AppHandle,
+  instance_id: String,
+  index: usize,
+) -> LXMCLResult<()> {
+  edit_servers_dat(&app, &instance_id, |servers_dat| servers_dat.remove(index)).await
+}
+
+#[tauri::command]
+pub async fn reorder_game_servers(
+  app: AppHandle,
+  instance_id: String,
+  order: Vec<usize>,
+) -> LXMCLResult<()> {
+  edit_servers_dat(&app, &instance_id, |servers_dat| {
+    servers_dat.reorder(&order)
+  })
+  .await
+}
+
+#[tauri::command]
+pub async fn import_game_servers_to_instances(
+  app: AppHandle,
+  src_file_path: String,
+  tgt_inst_ids: Vec<String>,
+) -> LXMCLResult<()> {
+  let imported_servers = parse_server_list_file(Path::new(&src_file_path)).await?;
+  // edit all the lists first, so that nothing is written if any of them fails
+  let mut edited = Vec::new();
+  for tgt_inst_id in &tgt_inst_ids {
+    let nbt_path = get_servers_dat_path(&app, tgt_inst_id)?;
+    let mut servers_dat = ServersDat::load(&nbt_path).await?;
+    servers_dat.import(&imported_servers)?;
+    edited.push((nbt_path, servers_dat));
+  }
+  for (nbt_path, servers_dat) in edited {
+    servers_dat
+      .save(&nbt_path)
+      .await
+      .map_err(|_| InstanceError::ServerNbtWriteError)?;
+  }
+  Ok(())
+}
+
 #[tauri::command]
 pub async fn retrieve_local_mod_list(
   app: AppHandle,
@@ -549,6 +672,86 @@ pub async fn retrieve_local_mod_list(
   Ok(mod_infos)
 }
 
+#[tauri::command]
+pub async fn check_mod_dependencies(
+  app: AppHandle,
+  instance_id: String,
+) -> LXMCLResult<Vec<ModDependencyIssue>> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let mods_dir = match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Mods)
+  {
+    Some(path) => path,
+    None => return Ok(Vec::new()),
+  };
+
+  // only enabled mods are loaded by the game
+  let valid_extensions = RegexBuilder::new(r"\.(jar|zip)$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+  let mod_paths = get_files_with_regex(&mods_dir, &valid_extensions).unwrap_or_default();
+  let mut tasks = Vec::new();
+  for path in mod_paths {
+    let task = tokio::spawn(async move {
+      let file_name = path.file_name()?.to_string_lossy().to_string();
+      let file = std::io::Cursor::new(tokio::fs::read(&path).await.ok()?);
+      let mut jar = ZipArchive::new(file).ok()?;
+      Some(read_dependency_nodes(&mut jar, &file_name, 0))
+    });
+    tasks.push(task);
+  }
+  let mut nodes = Vec::new();
+  for task in tasks {
+    if let Ok(Some(mod_nodes)) = task.await {
+      nodes.extend(mod_nodes);
+    }
+  }
+
+  let builtins = get_builtin_mod_versions(&instance.version, &instance.mod_loader);
+  let mut issues = check_mod_dependency_graph(&nodes, &builtins);
+
+  // look up missing dependencies remotely, so they can be downloaded directly
+  let mut missing_ids = issues
+    .iter()
+    .filter(|issue| issue.issue_type == ModDependencyIssueType::MissingDependency)
+    .filter_map(|issue| issue.target_mod_id.clone())
+    .collect::<Vec<_>>();
+  missing_ids.sort();
+  missing_ids.dedup();
+  let mut lookup_tasks = Vec::new();
+  for mod_id in missing_ids {
+    let app = app.clone();
+    let game_version = instance.version.clone();
+    lookup_tasks.push(tokio::spawn(async move {
+      let resource = fetch_remote_mod_by_mod_id(&app, &mod_id, &game_version).await;
+      (mod_id, resource)
+    }));
+  }
+  let mut remote_resources = HashMap::new();
+  for task in lookup_tasks {
+    if let Ok((mod_id, Some(resource))) = task.await {
+      remote_resources.insert(mod_id, resource);
+    }
+  }
+  for issue in issues.iter_mut() {
+    if issue.issue_type == ModDependencyIssueType::MissingDependency {
+      issue.remote_resource = issue
+        .target_mod_id
+        .as_ref()
+        .and_then(|mod_id| remote_resources.get(mod_id).cloned());
+    }
+  }
+
+  Ok(issues)
+}
+
 #[tauri::command]
 pub async fn retrieve_resource_pack_list(
   app: AppHandle,
@@ -821,6 +1024,134 @@ pub async fn retrieve_world_details(
   }
 }
 
+// apply the patch to the world's level.dat, returns the updated level data.
+#[tauri::command]
+pub async fn update_world_level_data(
+  app: AppHandle,
+  instance_id: String,
+  world_name: String,
+  patch: LevelDataPatch,
+) -> LXMCLResult<LevelData> {
+  let worlds_dir =
+    match get_instance_subdir_path_by_id(&app, &instance_id, &InstanceSubdirType::Saves) {
+      Some(path) => path,
+      None => return Err(InstanceError::WorldNotExistError.into()),
+    };
+  if !sanitize_filename::is_sanitized(&world_name) {
+    return Err(InstanceError::WorldNotExistError.into());
+  }
+  let world_dir = worlds_dir.join(world_name);
+  let level_path = world_dir.join("level.dat");
+  if tokio::fs::metadata(&level_path).await.is_err() {
+    return Err(InstanceError::LevelNotExistError.into());
+  }
+  // the game keeps the level data in memory, and would overwrite the edit on save
+  if is_instance_running(&app, &instance_id) || is_world_in_use(&world_dir) {
+    return Err(InstanceError::WorldInUseError.into());
+  }
+  save_level_data_patch(&level_path, &patch).await?;
+  load_level_data_from_path(&level_path)
+    .await
+    .map_err(|_| InstanceError::LevelParseError.into())
+}
+
+fn get_world_backup_dirs(
+  app: &AppHandle,
+  instance_id: &String,
+  world_name: &str,
+) -> LXMCLResult<(PathBuf, PathBuf)> {
+  if !sanitize_filename::is_sanitized(world_name) {
+    return Err(InstanceError::InvalidNameError.into());
+  }
+  let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+  let state = binding.lock()?;
+  let instance = state
+    .get(instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let dirs = get_instance_subdir_paths(
+    app,
+    instance,
+    &[&InstanceSubdirType::Saves, &InstanceSubdirType::Backups],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [saves_dir, backups_dir] = dirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
+  Ok((saves_dir.clone(), backups_dir.clone()))
+}
+
+#[tauri::command]
+pub fn create_world_backup(
+  app: AppHandle,
+  instance_id: String,
+  world_name: String,
+) -> LXMCLResult<WorldBackupInfo> {
+  let (saves_dir, backups_dir) = get_world_backup_dirs(&app, &instance_id, &world_name)?;
+  let world_dir = saves_dir.join(&world_name);
+  if !world_dir.is_dir() {
+    return Err(InstanceError::WorldNotExistError.into());
+  }
+  write_world_backup(&world_dir, &backups_dir)
+}
+
+#[tauri::command]
+pub fn retrieve_world_backup_list(
+  app: AppHandle,
+  instance_id: String,
+  world_name: String,
+) -> LXMCLResult<Vec<WorldBackupInfo>> {
+  let (_, backups_dir) = get_world_backup_dirs(&app, &instance_id, &world_name)?;
+  list_world_backups(&backups_dir, &world_name)
+}
+
+// restore a snapshot to `target_world_name` (the original world if None).
+// the world to be overwritten is backed up first.
+#[tauri::command]
+pub fn restore_world_backup(
+  app: AppHandle,
+  instance_id: String,
+  world_name: String,
+  backup_file_name: String,
+  target_world_name: Option<String>,
+) -> LXMCLResult<()> {
+  let (saves_dir, backups_dir) = get_world_backup_dirs(&app, &instance_id, &world_name)?;
+  if !sanitize_filename::is_sanitized(&backup_file_name) {
+    return Err(InstanceError::InvalidNameError.into());
+  }
+  let backup_path = backups_dir.join(&world_name).join(&backup_file_name);
+  if !backup_path.is_file() {
+    return Err(InstanceError::FileNotFoundError.into());
+  }
+
+  let target_world_name = target_world_name.unwrap_or(world_name.clone());
+  // checked before anything is written, the same directory is backed up then replaced
+  if !sanitize_filename::is_sanitized(&target_world_name) {
+    return Err(InstanceError::InvalidNameError.into());
+  }
+  let target_dir = saves_dir.join(&target_world_name);
+  // the game would overwrite the restored files, or keep the replaced ones open
+  if is_instance_running(&app, &instance_id) || is_world_in_use(&target_dir) {
+    return Err(InstanceError::WorldInUseError.into());
+  }
+  if target_dir.join("level.dat").is_file() {
+    write_world_backup(&target_dir, &backups_dir)?;
+  }
+  extract_world_backup(&backup_path, &world_name, &target_dir)
+}
+
+// remove the snapshots of a world beyond `max_count` or older than `max_age_days` (0 to ignore).
+#[tauri::command]
+pub fn prune_world_backups(
+  app: AppHandle,
+  instance_id: String,
+  world_name: String,
+  max_count: usize,
+  max_age_days: u32,
+) -> LXMCLResult<Vec<WorldBackupInfo>> {
+  let (_, backups_dir) = get_world_backup_dirs(&app, &instance_id, &world_name)?;
+  remove_expired_world_backups(&backups_dir, &world_name, max_count, max_age_days)
+}
+
 #[tauri::command]
 pub fn create_launch_desktop_shortcut(app: AppHandle, instance_id: String) -> LXMCLResult<()> {
   let binding = app.state::<Mutex<HashMap<String, Instance>>>();
@@ -833,10 +1164,10 @@ pub fn create_launch_desktop_shortcut(app: AppHandle, instance_id: String) -> LX
 
   let name = instance.name.clone();
   let encoded_id = url::form_urlencoded::Serializer::new(String::new())
-    .append_pair("id", &instance.id)
+    .append_pair("instance", &instance.id)
     .finish()
     .replace("+", "%20");
-  let url = format!("sjmcl://launch?{}", encoded_id);
+  let url = format!("lxmcl://launch?{}", encoded_id);
 
   create_url_shortcut(&app, name, url, None).map_err(|_| InstanceError::ShortcutCreationFailed)?;
 
@@ -854,6 +1185,7 @@ pub async fn create_instance(
   mod_loader: ModLoaderResourceInfo,
   modpack_path: Option<String>,
   is_install_fabric_api: Option<bool>,
+  options_template_name: Option<String>,
 ) -> LXMCLResult<()> {
   let client = app.state::<reqwest::Client>();
   let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
@@ -868,6 +1200,17 @@ pub async fn create_instance(
   if version_path.exists() {
     return Err(InstanceError::ConflictNameError.into());
   }
+  // Ensure the settings template exists before anything is created
+  if let Some(template_name) = &options_template_name {
+    let template_list = OptionsTemplateList::load().unwrap_or_default();
+    if !template_list
+      .templates
+      .iter()
+      .any(|t| &t.name == template_name)
+    {
+      return Err(InstanceError::FileNotFoundError.into());
+    }
+  }
 
   // Create instance config
   let instance = Instance {
@@ -879,7 +1222,10 @@ pub async fn create_instance(
       loader_type: mod_loader.loader_type.clone(),
       status: if matches!(
         mod_loader.loader_type,
-        ModLoaderType::Unknown | ModLoaderType::Fabric
+        ModLoaderType::Unknown
+          | ModLoaderType::Fabric
+          | ModLoaderType::Quilt
+          | ModLoaderType::LiteLoader
       ) {
         ModLoaderStatus::Installed
       } else {
@@ -888,12 +1234,14 @@ pub async fn create_instance(
       version: mod_loader.version.clone(),
       branch: mod_loader.branch.clone(),
     },
+    optifine: None,
     description,
     icon_src,
     starred: false,
     play_time: 0,
     use_spec_game_config: false,
     spec_game_config: None,
+    player_binding: None,
   };
 
   // Download version info
@@ -930,6 +1278,7 @@ pub async fn create_instance(
     dest: instance.version_path.join(format!("{}.jar", name)),
     filename: None,
     sha1: Some(client_download_info.sha1.clone()),
+    fallback_srcs: vec![],
   }));
   let subdirs = get_instance_subdir_paths(
     &app,
@@ -949,14 +1298,33 @@ pub async fn create_instance(
     .await
     .map_err(|_| InstanceError::ClientJsonParseError)?;
 
+  // files already in the shared store (if enabled) are linked instead of downloaded
+  let shared_store_dir = get_shared_store_dir(&app);
+
   // We only download libraries if they are invalid (not already downloaded)
   task_params.extend(
-    get_invalid_library_files(priority_list[0], libraries_dir, &version_info, false).await?,
+    get_invalid_library_files(
+      priority_list[0],
+      libraries_dir,
+      &version_info,
+      false,
+      shared_store_dir.as_deref(),
+    )
+    .await?,
   );
 
   // We only download assets if they are invalid (not already downloaded)
-  task_params
-    .extend(get_invalid_assets(&app, &version_info, priority_list[0], assets_dir, false).await?);
+  task_params.extend(
+    get_invalid_assets(
+      &app,
+      &version_info,
+      priority_list[0],
+      assets_dir,
+      false,
+      shared_store_dir.as_deref(),
+    )
+    .await?,
+  );
 
   if instance.mod_loader.loader_type != ModLoaderType::Unknown {
     install_mod_loader(
@@ -1030,6 +1398,22 @@ pub async fn create_instance(
     .await
     .map_err(|_| InstanceError::FileCreationFailed)?;
 
+  // Apply the chosen settings template (key bindings and video settings) to the new instance,
+  // the instance is usable without it, so a failure is not fatal
+  if let Some(template_name) = options_template_name {
+    if let Some(root_dir) = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Root])
+      .and_then(|dirs| dirs.into_iter().next())
+    {
+      let options_path = root_dir.join("options.txt");
+      if let Err(e) =
+        apply_options_template_to_instance(&app, &options_path, &instance.version, &template_name)
+          .await
+      {
+        log::warn!("Failed to apply the settings template {template_name}: {e:?}");
+      }
+    }
+  }
+
   Ok(())
 }
 
@@ -1052,17 +1436,40 @@ pub async fn finish_mod_loader_install(app: AppHandle, instance_id: String) -> L
     ModLoaderStatus::Installing => {
       return Err(InstanceError::InstallationDuplicated.into());
     }
-    ModLoaderStatus::Installed => {
-      return Ok(());
-    }
-    _ => {}
+    ModLoaderStatus::Installed => {}
+    _ => finish_processors_install(&app, &instance_id, &instance).await?,
   }
 
+  // OptiFine is patched on top of the installed mod loader
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  if instance
+    .optifine
+    .as_ref()
+    .is_some_and(|optifine| optifine.status != ModLoaderStatus::Installed)
+  {
+    finish_optifine_install_to_instance(&app, &instance_id, instance).await?;
+  }
+
+  Ok(())
+}
+
+async fn finish_processors_install(
+  app: &AppHandle,
+  instance_id: &str,
+  instance: &Instance,
+) -> LXMCLResult<()> {
   {
     let binding = app.state::<Mutex<HashMap<String, Instance>>>();
     let mut state = binding.lock()?;
     let instance = state
-      .get_mut(&instance_id)
+      .get_mut(instance_id)
       .ok_or(InstanceError::InstanceNotFoundByID)?;
     instance.mod_loader.status = ModLoaderStatus::Installing;
   };
@@ -1075,14 +1482,14 @@ pub async fn finish_mod_loader_install(app: AppHandle, instance_id: String) -> L
   let install_profile_dir = instance.version_path.join("install_profile.json");
   if install_profile_dir.exists() {
     let install_profile = load_json_async::<InstallProfile>(&install_profile_dir).await?;
-    execute_processors(&app, &instance, &client_info, &install_profile).await?;
+    execute_processors(app, instance, &client_info, &install_profile).await?;
   }
 
   let instance = {
     let binding = app.state::<Mutex<HashMap<String, Instance>>>();
     let mut state = binding.lock()?;
     let instance = state
-      .get_mut(&instance_id)
+      .get_mut(instance_id)
       .ok_or(InstanceError::InstanceNotFoundByID)?;
     instance.mod_loader.status = ModLoaderStatus::Installed;
     instance.clone()
@@ -1092,6 +1499,229 @@ pub async fn finish_mod_loader_install(app: AppHandle, instance_id: String) -> L
   Ok(())
 }
 
+// Called when the OptiFine downloads are done, the status is set to installed only on success.
+async fn finish_optifine_install_to_instance(
+  app: &AppHandle,
+  instance_id: &str,
+  instance: Instance,
+) -> LXMCLResult<()> {
+  let optifine = instance.optifine.clone().unwrap_or_default();
+  match optifine.status {
+    ModLoaderStatus::Installing => return Err(InstanceError::InstallationDuplicated.into()),
+    ModLoaderStatus::Installed => return Ok(()),
+    _ => {}
+  }
+
+  let set_status = |status: ModLoaderStatus| -> LXMCLResult<Instance> {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    if let Some(optifine) = instance.optifine.as_mut() {
+      optifine.status = status;
+    }
+    Ok(instance.clone())
+  };
+  set_status(ModLoaderStatus::Installing)?;
+
+  let subdirs = get_instance_subdir_paths(
+    app,
+    &instance,
+    &[&InstanceSubdirType::Libraries, &InstanceSubdirType::Mods],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [lib_dir, mods_dir] = subdirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
+  let client_info_dir = instance
+    .version_path
+    .join(format!("{}.json", instance.name));
+
+  let result: LXMCLResult<()> = async {
+    match instance.mod_loader.loader_type {
+      // works as a regular mod, nothing to patch
+      ModLoaderType::Forge | ModLoaderType::LegacyForge => {
+        if !mods_dir.join(&optifine.filename).is_file() {
+          return Err(InstanceError::FileNotFoundError.into());
+        }
+      }
+      _ => {
+        let mut client_info = load_json_async::<McClientInfo>(&client_info_dir).await?;
+        finish_optifine_install(app, &instance, &optifine.version, lib_dir, &mut client_info)
+          .await?;
+        save_json_async(&client_info, &client_info_dir).await?;
+      }
+    }
+    Ok(())
+  }
+  .await;
+  if let Err(e) = result {
+    set_status(ModLoaderStatus::DownloadFailed)?
+      .save_json_cfg()
+      .await?;
+    return Err(e);
+  }
+
+  set_status(ModLoaderStatus::Installed)?
+    .save_json_cfg()
+    .await?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn install_optifine_to_instance(
+  app: AppHandle,
+  instance_id: String,
+  optifine: OptiFineResourceInfo,
+) -> LXMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  // a failed installation can be retried, it is replaced by the new one. wait for the mod loader
+  // first, OptiFine is patched on top of it
+  if instance.mod_loader.status != ModLoaderStatus::Installed
+    || instance.optifine.as_ref().is_some_and(|optifine| {
+      matches!(
+        optifine.status,
+        ModLoaderStatus::Installed | ModLoaderStatus::Installing
+      )
+    })
+  {
+    return Err(InstanceError::InstallationDuplicated.into());
+  }
+
+  let priority_list = {
+    let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
+    let launcher_config = launcher_config_state.lock()?;
+    get_source_priority_list(&launcher_config)
+  };
+  let subdirs = get_instance_subdir_paths(
+    &app,
+    &instance,
+    &[&InstanceSubdirType::Libraries, &InstanceSubdirType::Mods],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [libraries_dir, mods_dir] = subdirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
+
+  let mut task_params = Vec::<PTaskParam>::new();
+  install_optifine(
+    &priority_list,
+    &optifine,
+    &instance.mod_loader.loader_type,
+    libraries_dir.to_path_buf(),
+    mods_dir.to_path_buf(),
+    &mut task_params,
+  )
+  .await?;
+
+  // installed in `finish_mod_loader_install` after downloading (and patching for standalone)
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let mut state = binding.lock()?;
+    let instance = state
+      .get_mut(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?;
+    instance.optifine = Some(OptiFine {
+      version: optifine.version(),
+      filename: optifine.filename.clone(),
+      status: ModLoaderStatus::NotDownloaded,
+    });
+    instance.clone()
+  };
+  instance.save_json_cfg().await?;
+
+  schedule_progressive_task_group(
+    app.clone(),
+    format!("optifine-libraries?{}", instance_id),
+    task_params,
+    true,
+  )
+  .await?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn install_liteloader_to_instance(
+  app: AppHandle,
+  instance_id: String,
+  liteloader: ModLoaderResourceInfo,
+) -> LXMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  // LiteLoader can only be stacked on an installed (Legacy)Forge, as forge rewrites the client json
+  if !matches!(
+    instance.mod_loader.loader_type,
+    ModLoaderType::Forge | ModLoaderType::LegacyForge
+  ) || instance.mod_loader.status != ModLoaderStatus::Installed
+    || liteloader.loader_type != ModLoaderType::LiteLoader
+  {
+    return Err(InstanceError::UnsupportedModLoader.into());
+  }
+
+  let priority_list = {
+    let launcher_config_state = app.state::<Mutex<LauncherConfig>>();
+    let launcher_config = launcher_config_state.lock()?;
+    get_source_priority_list(&launcher_config)
+  };
+  let libraries_dir = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Libraries])
+    .ok_or(InstanceError::InstanceNotFoundByID)?
+    .remove(0);
+  let client_info_dir = instance
+    .version_path
+    .join(format!("{}.json", instance.name));
+  let mut client_info = load_json_async::<McClientInfo>(&client_info_dir).await?;
+  if client_info
+    .patches
+    .iter()
+    .any(|patch| patch.id == "liteloader")
+  {
+    return Err(InstanceError::InstallationDuplicated.into());
+  }
+
+  let mut task_params = Vec::<PTaskParam>::new();
+  install_liteloader(
+    &app,
+    &priority_list,
+    &instance.version,
+    &ModLoader {
+      loader_type: ModLoaderType::LiteLoader,
+      version: liteloader.version.clone(),
+      ..Default::default()
+    },
+    libraries_dir,
+    &mut client_info,
+    &mut task_params,
+  )
+  .await?;
+
+  schedule_progressive_task_group(
+    app.clone(),
+    format!("liteloader?{}", instance_id),
+    task_params,
+    true,
+  )
+  .await?;
+  save_json_async(&client_info, &client_info_dir).await?;
+
+  Ok(())
+}
+
 #[tauri::command]
 pub async fn check_change_mod_loader_availablity(
   app: AppHandle,
@@ -1163,7 +1793,10 @@ pub async fn change_mod_loader(
     version: new_mod_loader.version.clone(),
     status: if matches!(
       new_mod_loader.loader_type,
-      ModLoaderType::Unknown | ModLoaderType::Fabric
+      ModLoaderType::Unknown
+        | ModLoaderType::Fabric
+        | ModLoaderType::Quilt
+        | ModLoaderType::LiteLoader
     ) {
       ModLoaderStatus::Installed
     } else {
@@ -1181,12 +1814,18 @@ pub async fn change_mod_loader(
   let [libraries_dir, mods_dir] = subdirs.as_slice() else {
     return Err(InstanceError::InstanceNotFoundByID.into());
   };
-  // Remove Fabric API mods if switching from Fabric modloader
-  if instance.mod_loader.loader_type == ModLoaderType::Fabric && version_isolation {
+  // Remove Fabric API (or QFAPI) mods if switching from Fabric or Quilt modloader
+  if matches!(
+    instance.mod_loader.loader_type,
+    ModLoaderType::Fabric | ModLoaderType::Quilt
+  ) && version_isolation
+  {
     remove_fabric_api_mods(mods_dir).await?;
   }
   // construct new version info
   instance.mod_loader = mod_loader.clone();
+  // the client json is rebuilt from vanilla below, which drops a standalone OptiFine patch
+  instance.optifine = None;
   let mut version_info: McClientInfo = vanilla_info.clone().into();
   version_info.id = current_info.id.clone();
   version_info.jar = Some(instance.name.clone());
@@ -1235,3 +1874,198 @@ pub async fn retrieve_modpack_meta_info(path: String) -> LXMCLResult<ModpackMeta
   let file = fs::File::open(&path).map_err(|_| InstanceError::FileNotFoundError)?;
   ModpackMetaInfo::from_archive(&file).await
 }
+
+#[tauri::command]
+pub async fn export_modpack(
+  app: AppHandle,
+  instance_id: String,
+  modpack_source: OtherResourceSource,
+  save_path: String,
+  name: String,
+  version: String,
+  author: String,
+  description: Option<String>,
+  override_paths: Vec<String>,
+) -> LXMCLResult<()> {
+  let instance = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state
+      .get(&instance_id)
+      .ok_or(InstanceError::InstanceNotFoundByID)?
+      .clone()
+  };
+  let subdirs = get_instance_subdir_paths(
+    &app,
+    &instance,
+    &[&InstanceSubdirType::Root, &InstanceSubdirType::Mods],
+  )
+  .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let [game_root, mods_dir] = subdirs.as_slice() else {
+    return Err(InstanceError::InstanceNotFoundByID.into());
+  };
+
+  // only enabled jars can be resolved remotely, other files in mods/ are always put into overrides
+  let jar_regex = RegexBuilder::new(r"\.jar$")
+    .case_insensitive(true)
+    .build()
+    .unwrap();
+  let (mod_jars, mut other_files): (Vec<PathBuf>, Vec<PathBuf>) =
+    get_files_with_regex(mods_dir, &Regex::new(".*").unwrap())
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|path| path.is_file())
+      .partition(|path| {
+        path
+          .file_name()
+          .is_some_and(|name| jar_regex.is_match(&name.to_string_lossy()))
+      });
+
+  let save_path = PathBuf::from(save_path);
+  match modpack_source {
+    OtherResourceSource::Modrinth => {
+      let (manifest, unresolved) =
+        ModrinthManifest::from_instance(&app, &instance, &name, &version, description, &mod_jars)
+          .await?;
+      other_files.extend(unresolved);
+      write_modpack_archive(
+        &save_path,
+        "modrinth.index.json",
+        &manifest,
+        game_root,
+        &override_paths,
+        &other_files,
+      )
+    }
+    OtherResourceSource::CurseForge => {
+      let (manifest, unresolved) =
+        CurseForgeManifest::from_instance(&app, &instance, &name, &version, &author, &mod_jars)
+          .await?;
+      other_files.extend(unresolved);
+      write_modpack_archive(
+        &save_path,
+        "manifest.json",
+        &manifest,
+        game_root,
+        &override_paths,
+        &other_files,
+      )
+    }
+    OtherResourceSource::Unknown => Err(ResourceError::NoDownloadApi.into()),
+  }
+}
+
+// returns the path of options.txt and the game version of the instance.
+fn get_instance_options_path(
+  app: &AppHandle,
+  instance_id: &String,
+) -> LXMCLResult<(PathBuf, String)> {
+  let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+  let state = binding.lock()?;
+  let instance = state
+    .get(instance_id)
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+  let root_dir = get_instance_subdir_paths(app, instance, &[&InstanceSubdirType::Root])
+    .ok_or(InstanceError::InstanceNotFoundByID)?
+    .remove(0);
+  Ok((root_dir.join("options.txt"), instance.version.clone()))
+}
+
+#[tauri::command]
+pub async fn retrieve_game_options(
+  app: AppHandle,
+  instance_id: String,
+) -> LXMCLResult<GameOptions> {
+  let (options_path, _) = get_instance_options_path(&app, &instance_id)?;
+  Ok(OptionsTxt::load(&options_path).await?.to_game_options())
+}
+
+#[tauri::command]
+pub async fn update_game_options(
+  app: AppHandle,
+  instance_id: String,
+  options: GameOptions,
+) -> LXMCLResult<()> {
+  let (options_path, version) = get_instance_options_path(&app, &instance_id)?;
+  let mut options_txt = OptionsTxt::load(&options_path).await?;
+  let use_legacy_key_codes = uses_legacy_key_codes(&app, &options_txt, &version).await;
+  options_txt.apply_game_options(&options, use_legacy_key_codes);
+  options_txt
+    .save(&options_path)
+    .await
+    .map_err(|_| InstanceError::FileCreationFailed.into())
+}
+
+#[tauri::command]
+pub fn retrieve_options_template_list() -> LXMCLResult<Vec<OptionsTemplate>> {
+  Ok(OptionsTemplateList::load().unwrap_or_default().templates)
+}
+
+#[tauri::command]
+pub async fn create_options_template(
+  app: AppHandle,
+  instance_id: String,
+  name: String,
+) -> LXMCLResult<OptionsTemplate> {
+  let name = name.trim().to_string();
+  if name.is_empty() {
+    return Err(InstanceError::InvalidNameError.into());
+  }
+  let (options_path, version) = get_instance_options_path(&app, &instance_id)?;
+  if !options_path.is_file() {
+    return Err(InstanceError::FileNotFoundError.into());
+  }
+  let template = OptionsTxt::load(&options_path)
+    .await?
+    .to_template(&name, &version);
+
+  // a template with the same name is replaced
+  let mut template_list = OptionsTemplateList::load().unwrap_or_default();
+  template_list.templates.retain(|t| t.name != name);
+  template_list.templates.push(template.clone());
+  template_list.save()?;
+  Ok(template)
+}
+
+#[tauri::command]
+pub fn delete_options_template(template_name: String) -> LXMCLResult<()> {
+  let mut template_list = OptionsTemplateList::load().unwrap_or_default();
+  let count = template_list.templates.len();
+  template_list.templates.retain(|t| t.name != template_name);
+  if template_list.templates.len() == count {
+    return Err(InstanceError::FileNotFoundError.into());
+  }
+  template_list.save()?;
+  Ok(())
+}
+
+async fn apply_options_template_to_instance(
+  app: &AppHandle,
+  options_path: &Path,
+  version: &str,
+  template_name: &str,
+) -> LXMCLResult<()> {
+  let template = OptionsTemplateList::load()
+    .unwrap_or_default()
+    .templates
+    .into_iter()
+    .find(|t| t.name == template_name)
+    .ok_or(InstanceError::FileNotFoundError)?;
+  let mut options_txt = OptionsTxt::load(options_path).await?;
+  let use_legacy_key_codes = uses_legacy_key_codes(app, &options_txt, version).await;
+  options_txt.apply_template(&template, use_legacy_key_codes);
+  options_txt
+    .save(options_path)
+    .await
+    .map_err(|_| InstanceError::FileCreationFailed.into())
+}
+
+#[tauri::command]
+pub async fn apply_options_template(
+  app: AppHandle,
+  instance_id: String,
+  template_name: String,
+) -> LXMCLResult<()> {
+  let (options_path, version) = get_instance_options_path(&app, &instance_id)?;
+  apply_options_template_to_instance(&app, &options_path, &version, &template_name).await
+}
@@ -1 +1,2 @@
 pub const INSTANCE_CFG_FILE_NAME: &str = "sjmclcfg.json";
+pub const OPTIONS_TEMPLATES_FILE_NAME: &str = "sjmcl.options-templates.json";
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 use tauri_plugin_http::reqwest;
+use tokio::fs;
 
 use crate::error::LXMCLResult;
 use crate::instance::models::misc::InstanceError;
@@ -13,6 +14,12 @@ use crate::storage::{load_json_async, save_json_async};
 #[serde(default)]
 pub struct AssetIndex {
   pub objects: HashMap<String, AssetIndexItem>,
+  // `legacy` index (1.6.x): assets are read by name from `assets/virtual/<id>`
+  #[serde(rename = "virtual", skip_serializing_if = "std::ops::Not::not")]
+  pub is_virtual: bool,
+  // `pre-1.6` index: assets are read by name from `<game_dir>/resources`
+  #[serde(skip_serializing_if = "std::ops::Not::not")]
+  pub map_to_resources: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
@@ -50,3 +57,58 @@ pub async fn load_asset_index(
     Ok(asset_index)
   }
 }
+
+// The directory passed as `${game_assets}`, where legacy versions read assets by their names.
+pub fn get_game_assets_dir(
+  asset_index: &AssetIndex,
+  asset_index_id: &str,
+  assets_dir: &Path,
+  game_dir: &Path,
+) -> PathBuf {
+  if asset_index.map_to_resources {
+    game_dir.join("resources")
+  } else if asset_index.is_virtual {
+    assets_dir.join("virtual").join(asset_index_id)
+  } else {
+    assets_dir.to_path_buf()
+  }
+}
+
+// Copy the hashed objects to their names for the `virtual` and `map_to_resources` indexes.
+// Objects that are missing or already in place (with the same size) are skipped.
+pub async fn materialize_legacy_assets(
+  asset_index: &AssetIndex,
+  asset_index_id: &str,
+  assets_dir: &Path,
+  game_dir: &Path,
+) -> LXMCLResult<()> {
+  if !asset_index.is_virtual && !asset_index.map_to_resources {
+    return Ok(());
+  }
+  let target_dir = get_game_assets_dir(asset_index, asset_index_id, assets_dir, game_dir);
+
+  for (name, item) in &asset_index.objects {
+    let is_normal_path = Path::new(name)
+      .components()
+      .all(|c| matches!(c, Component::Normal(_)));
+    if !is_normal_path || item.hash.len() < 2 {
+      continue;
+    }
+    let dest = target_dir.join(name);
+    if fs::metadata(&dest)
+      .await
+      .is_ok_and(|metadata| metadata.len() as i64 == item.size)
+    {
+      continue;
+    }
+    let src = assets_dir.join(format!("objects/{}/{}", &item.hash[..2], item.hash));
+    if !fs::try_exists(&src).await? {
+      continue;
+    }
+    if let Some(parent) = dest.parent() {
+      fs::create_dir_all(parent).await?;
+    }
+    fs::copy(&src, &dest).await?;
+  }
+  Ok(())
+}
@@ -90,6 +90,7 @@ pub async fn install_fabric_loader(
       dest: lib_dir.join(&rel),
       filename: None,
       sha1: None,
+      fallback_srcs: vec![],
     }));
     Ok(())
   };
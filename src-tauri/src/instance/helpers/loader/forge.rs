@@ -88,6 +88,7 @@ pub async fn install_forge_loader(
     dest: installer_path.clone(),
     filename: None,
     sha1: None,
+    fallback_srcs: vec![],
   }));
 
   Ok(())
@@ -224,6 +225,7 @@ pub async fn download_forge_libraries(
               dest: lib_dir.join(mojmaps),
               filename: None,
               sha1: Some(client_mappings.sha1.clone()),
+              fallback_srcs: vec![],
             }));
           }
         }
@@ -305,6 +307,7 @@ pub async fn download_forge_libraries(
         dest: lib_dir.join(&convert_library_name_to_path(name, None)?),
         filename: None,
         sha1: None,
+        fallback_srcs: vec![],
       }));
     }
 
@@ -361,6 +364,7 @@ pub async fn download_forge_libraries(
         dest: lib_dir.join(&rel),
         filename: None,
         sha1: None,
+        fallback_srcs: vec![],
       }));
     }
   } else {
@@ -433,6 +437,7 @@ pub async fn download_forge_libraries(
         dest: lib_dir.join(&rel),
         filename: None,
         sha1: None,
+        fallback_srcs: vec![],
       }));
     }
     client_info.patches.push(new_patch);
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+use tauri::AppHandle;
+use url::Url;
+
+use crate::error::LXMCLResult;
+use crate::instance::helpers::client_json::{McClientInfo, PatchesInfo};
+use crate::instance::helpers::loader::common::add_library_entry;
+use crate::instance::models::misc::{InstanceError, ModLoader};
+use crate::launch::helpers::file_validator::convert_library_name_to_path;
+use crate::resource::helpers::loader_meta::liteloader::fetch_liteloader_manifest;
+use crate::resource::helpers::misc::get_download_api;
+use crate::resource::models::{ResourceType, SourceType};
+use crate::tasks::download::DownloadParam;
+use crate::tasks::PTaskParam;
+
+const LAUNCHWRAPPER_MAIN_CLASS: &str = "net.minecraft.launchwrapper.Launch";
+
+// LiteLoader only supports legacy versions (<= 1.12.2), which always use `minecraftArguments`.
+// On a vanilla client it is launched by launchwrapper directly,
+// on top of (Legacy)Forge its tweaker is placed in front of FML's, and cascades it.
+pub async fn install_liteloader(
+  app: &AppHandle,
+  priority: &[SourceType],
+  game_version: &str,
+  loader: &ModLoader,
+  lib_dir: PathBuf,
+  client_info: &mut McClientInfo,
+  task_params: &mut Vec<PTaskParam>,
+) -> LXMCLResult<()> {
+  let manifest = fetch_liteloader_manifest(app, priority).await?;
+  let game_version_info = manifest
+    .versions
+    .get(game_version)
+    .ok_or(InstanceError::ModLoaderVersionParseError)?;
+  let version_info = game_version_info
+    .all_versions()
+    .find(|info| info.version == loader.version)
+    .ok_or(InstanceError::ModLoaderVersionParseError)?;
+
+  let minecraft_arguments = client_info
+    .minecraft_arguments
+    .as_mut()
+    .ok_or(InstanceError::UnsupportedModLoader)?;
+  let tweak_arg = format!("--tweakClass {}", version_info.tweak_class);
+  if !minecraft_arguments.contains(&tweak_arg) {
+    *minecraft_arguments = match minecraft_arguments.find("--tweakClass") {
+      Some(pos) => format!(
+        "{}{} {}",
+        &minecraft_arguments[..pos],
+        tweak_arg,
+        &minecraft_arguments[pos..]
+      ),
+      None => format!("{minecraft_arguments} {tweak_arg}"),
+    };
+  }
+  client_info.main_class = LAUNCHWRAPPER_MAIN_CLASS.to_string();
+
+  let liteloader_name = format!("com.mumfrey:liteloader:{}", version_info.version);
+  let mut new_patch = PatchesInfo {
+    id: "liteloader".to_string(),
+    version: version_info.version.clone(),
+    priority: 30001, // applied over forge, if present
+    main_class: LAUNCHWRAPPER_MAIN_CLASS.to_string(),
+    minecraft_arguments: client_info.minecraft_arguments.clone(),
+    ..Default::default()
+  };
+
+  let libraries_root = get_download_api(priority[0], ResourceType::Libraries)?;
+  let liteloader_root = match priority[0] {
+    SourceType::Official => Url::parse(&game_version_info.repo.url)?,
+    SourceType::BMCLAPIMirror => libraries_root.clone(),
+  };
+
+  let mut libraries = vec![(liteloader_name, liteloader_root)];
+  for library in &version_info.libraries {
+    let url_root = match &library.url {
+      Some(url) => Url::parse(url)?,
+      None => libraries_root.clone(),
+    };
+    libraries.push((library.name.clone(), url_root));
+  }
+
+  for (name, url_root) in libraries {
+    add_library_entry(&mut client_info.libraries, &name, None)?;
+    add_library_entry(&mut new_patch.libraries, &name, None)?;
+
+    let rel = convert_library_name_to_path(&name, None)?;
+    task_params.push(PTaskParam::Download(DownloadParam {
+      src: url_root.join(&rel)?,
+      dest: lib_dir.join(&rel),
+      filename: None,
+      sha1: None,
+      fallback_srcs: vec![],
+    }));
+  }
+
+  client_info.patches.push(new_patch);
+
+  Ok(())
+}
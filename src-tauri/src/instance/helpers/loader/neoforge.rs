@@ -63,6 +63,7 @@ pub async fn install_neoforge_loader(
     dest: installer_path.clone(),
     filename: None,
     sha1: None,
+    fallback_srcs: vec![],
   }));
 
   Ok(())
@@ -202,6 +203,7 @@ pub async fn download_neoforge_libraries(
             dest: lib_dir.join(mojmaps),
             filename: None,
             sha1: Some(client_mappings.sha1.clone()),
+            fallback_srcs: vec![],
           }));
         }
       }
@@ -279,6 +281,7 @@ pub async fn download_neoforge_libraries(
       dest: lib_dir.join(&convert_library_name_to_path(name, None)?),
       filename: None,
       sha1: None,
+      fallback_srcs: vec![],
     }));
   }
 
@@ -327,6 +330,7 @@ pub async fn download_neoforge_libraries(
       dest: lib_dir.join(&rel),
       filename: None,
       sha1: None,
+      fallback_srcs: vec![],
     }));
   }
 
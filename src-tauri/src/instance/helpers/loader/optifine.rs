@@ -0,0 +1,201 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+use zip::ZipArchive;
+
+use crate::error::LXMCLResult;
+use crate::instance::helpers::client_json::{ArgumentsItem, McClientInfo, PatchesInfo};
+use crate::instance::helpers::loader::common::add_library_entry;
+use crate::instance::helpers::misc::get_instance_game_config;
+use crate::instance::models::misc::{Instance, InstanceError, ModLoaderType};
+use crate::launch::helpers::file_validator::convert_library_name_to_path;
+use crate::launch::helpers::jre_selector::select_java_runtime;
+use crate::launcher_config::models::JavaInfo;
+use crate::resource::helpers::misc::get_download_api;
+use crate::resource::models::{OptiFineResourceInfo, ResourceError, ResourceType, SourceType};
+use crate::tasks::download::DownloadParam;
+use crate::tasks::PTaskParam;
+
+const OPTIFINE_TWEAK_CLASS: &str = "optifine.OptiFineTweaker";
+const LAUNCHWRAPPER_MAIN_CLASS: &str = "net.minecraft.launchwrapper.Launch";
+// used when the OptiFine jar does not bundle its own launchwrapper (older versions)
+const LEGACY_LAUNCHWRAPPER: &str = "net.minecraft:launchwrapper:1.12";
+
+fn get_optifine_installer_coord(game_version: &str, optifine_version: &str) -> String {
+  format!("optifine:OptiFine:{game_version}_{optifine_version}:installer")
+}
+
+pub async fn install_optifine(
+  priority: &[SourceType],
+  optifine: &OptiFineResourceInfo,
+  loader_type: &ModLoaderType,
+  lib_dir: PathBuf,
+  mods_dir: PathBuf,
+  task_params: &mut Vec<PTaskParam>,
+) -> LXMCLResult<()> {
+  let src = priority
+    .iter()
+    .find_map(|source| get_download_api(*source, ResourceType::Optifine).ok())
+    .ok_or(ResourceError::NoDownloadApi)?
+    .join(&format!(
+      "{}/{}/{}",
+      optifine.game_version, optifine.type_, optifine.patch
+    ))?;
+
+  match loader_type {
+    // OptiFine works as a regular mod in Forge instances
+    ModLoaderType::Forge | ModLoaderType::LegacyForge => {
+      task_params.push(PTaskParam::Download(DownloadParam {
+        src,
+        dest: mods_dir.join(&optifine.filename),
+        filename: Some(optifine.filename.clone()),
+        sha1: None,
+        fallback_srcs: vec![],
+      }));
+    }
+    // standalone, the downloaded jar is used as the patcher in `finish_optifine_install`
+    ModLoaderType::Unknown => {
+      let installer_coord =
+        get_optifine_installer_coord(&optifine.game_version, &optifine.version());
+      task_params.push(PTaskParam::Download(DownloadParam {
+        src,
+        dest: lib_dir.join(convert_library_name_to_path(&installer_coord, None)?),
+        filename: Some(optifine.filename.clone()),
+        sha1: None,
+        fallback_srcs: vec![],
+      }));
+
+      let launchwrapper_rel = convert_library_name_to_path(LEGACY_LAUNCHWRAPPER, None)?;
+      task_params.push(PTaskParam::Download(DownloadParam {
+        src: get_download_api(priority[0], ResourceType::Libraries)?.join(&launchwrapper_rel)?,
+        dest: lib_dir.join(&launchwrapper_rel),
+        filename: None,
+        sha1: None,
+        fallback_srcs: vec![],
+      }));
+    }
+    _ => return Err(InstanceError::UnsupportedModLoader.into()),
+  }
+
+  Ok(())
+}
+
+// extract the launchwrapper bundled in the OptiFine jar, returns its library name if present.
+fn extract_bundled_launchwrapper(
+  installer_path: &Path,
+  lib_dir: &Path,
+) -> LXMCLResult<Option<String>> {
+  let mut archive = ZipArchive::new(File::open(installer_path)?)?;
+
+  let launchwrapper_version = match archive.by_name("launchwrapper-of.txt") {
+    Ok(mut file) => {
+      let mut content = String::new();
+      file.read_to_string(&mut content)?;
+      content.trim().to_string()
+    }
+    Err(_) => return Ok(None),
+  };
+
+  let name = format!("optifine:launchwrapper-of:{launchwrapper_version}");
+  let dest = lib_dir.join(convert_library_name_to_path(&name, None)?);
+  let mut file = archive.by_name(&format!("launchwrapper-of-{launchwrapper_version}.jar"))?;
+  if let Some(parent) = dest.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  let mut output = File::create(&dest)?;
+  std::io::copy(&mut file, &mut output)?;
+
+  Ok(Some(name))
+}
+
+pub async fn finish_optifine_install(
+  app: &AppHandle,
+  instance: &Instance,
+  optifine_version: &str,
+  lib_dir: &Path,
+  client_info: &mut McClientInfo,
+) -> LXMCLResult<()> {
+  let installer_path = lib_dir.join(convert_library_name_to_path(
+    &get_optifine_installer_coord(&instance.version, optifine_version),
+    None,
+  )?);
+  if !installer_path.exists() {
+    return Err(InstanceError::FileNotFoundError.into());
+  }
+
+  let optifine_name = format!(
+    "optifine:OptiFine:{}_{}",
+    instance.version, optifine_version
+  );
+  let optifine_path = lib_dir.join(convert_library_name_to_path(&optifine_name, None)?);
+  if let Some(parent) = optifine_path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  let javas_state = app.state::<Mutex<Vec<JavaInfo>>>();
+  let javas = javas_state.lock()?.clone();
+  let game_config = get_instance_game_config(app, instance);
+  let selected_java = select_java_runtime(
+    app,
+    &game_config.game_java,
+    &javas,
+    instance,
+    client_info.java_version.major_version,
+  )
+  .await?;
+
+  // run the OptiFine patcher to diff the vanilla client against the OptiFine jar
+  let mut cmd_base = Command::new(selected_java.exec_path.clone());
+  #[cfg(target_os = "windows")]
+  cmd_base.creation_flags(0x08000000);
+  let output = cmd_base
+    .arg("-cp")
+    .arg(&installer_path)
+    .arg("optifine.Patcher")
+    .arg(instance.version_path.join(format!("{}.jar", instance.name)))
+    .arg(&installer_path)
+    .arg(&optifine_path)
+    .output()
+    .await?;
+  if !output.status.success() {
+    eprintln!(
+      "[{}] OptiFine patcher failed with exit code: {:?}",
+      instance.name,
+      output.status.code()
+    );
+    return Err(InstanceError::ProcessorExecutionFailed.into());
+  }
+
+  let launchwrapper_name = extract_bundled_launchwrapper(&installer_path, lib_dir)?
+    .unwrap_or(LEGACY_LAUNCHWRAPPER.to_string());
+
+  let mut new_patch = PatchesInfo {
+    id: "optifine".to_string(),
+    version: optifine_version.to_string(),
+    priority: 40000,
+    main_class: LAUNCHWRAPPER_MAIN_CLASS.to_string(),
+    ..Default::default()
+  };
+  for name in [&optifine_name, &launchwrapper_name] {
+    add_library_entry(&mut client_info.libraries, name, None)?;
+    add_library_entry(&mut new_patch.libraries, name, None)?;
+  }
+
+  client_info.main_class = LAUNCHWRAPPER_MAIN_CLASS.to_string();
+  if let Some(args) = client_info.arguments.as_mut() {
+    args.game.push(ArgumentsItem {
+      value: vec!["--tweakClass".to_string(), OPTIFINE_TWEAK_CLASS.to_string()],
+      ..Default::default()
+    });
+  } else if let Some(args) = client_info.minecraft_arguments.as_mut() {
+    args.push_str(&format!(" --tweakClass {OPTIFINE_TWEAK_CLASS}"));
+  }
+  client_info.patches.push(new_patch);
+
+  fs::remove_file(&installer_path)?;
+
+  Ok(())
+}
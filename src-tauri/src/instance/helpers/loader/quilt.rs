@@ -0,0 +1,123 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use url::Url;
+
+use crate::error::LXMCLResult;
+use crate::instance::helpers::client_json::{LaunchArgumentTemplate, McClientInfo, PatchesInfo};
+use crate::instance::helpers::loader::common::add_library_entry;
+use crate::instance::models::misc::{InstanceError, ModLoader};
+use crate::launch::helpers::file_validator::convert_library_name_to_path;
+use crate::resource::helpers::misc::{convert_url_to_target_source, get_download_api};
+use crate::resource::helpers::modrinth::get_latest_quilted_fabric_api_mod_download;
+use crate::resource::models::{ResourceType, SourceType};
+use crate::tasks::download::DownloadParam;
+use crate::tasks::PTaskParam;
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct QuiltProfile {
+  pub main_class: String,
+  pub arguments: Option<LaunchArgumentTemplate>,
+  pub libraries: Vec<QuiltProfileLibrary>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+struct QuiltProfileLibrary {
+  pub name: String,
+  pub url: Option<String>,
+}
+
+pub async fn install_quilt_loader(
+  app: AppHandle,
+  priority: &[SourceType],
+  game_version: &str,
+  loader: &ModLoader,
+  lib_dir: PathBuf,
+  mods_dir: PathBuf,
+  client_info: &mut McClientInfo,
+  task_params: &mut Vec<PTaskParam>,
+  is_install_quilt_api: Option<bool>,
+) -> LXMCLResult<()> {
+  let client = app.state::<reqwest::Client>();
+  let loader_ver = &loader.version;
+
+  // the profile json is a complete (inherited) client json, containing main class, arguments and libraries
+  let mut profile = None;
+  for source in priority {
+    let profile_url = get_download_api(*source, ResourceType::QuiltMeta)?.join(&format!(
+      "v3/versions/loader/{game_version}/{loader_ver}/profile/json"
+    ))?;
+    if let Ok(response) = client.get(profile_url).send().await {
+      if response.status().is_success() {
+        profile = response.json::<QuiltProfile>().await.ok();
+        break;
+      }
+    }
+  }
+  let profile = profile.ok_or(InstanceError::NetworkError)?;
+  if profile.main_class.is_empty() {
+    return Err(InstanceError::ClientJsonParseError.into());
+  }
+
+  client_info.main_class = profile.main_class.clone();
+
+  let mut new_patch = PatchesInfo {
+    id: "quilt".to_string(),
+    version: loader_ver.to_string(),
+    priority: 30000,
+    main_class: profile.main_class.clone(),
+    arguments: profile.arguments.clone(),
+    ..Default::default()
+  };
+
+  if let Some(profile_args) = &profile.arguments {
+    if let Some(args) = client_info.arguments.as_mut() {
+      args.game.extend(profile_args.game.iter().cloned());
+      args.jvm.extend(profile_args.jvm.iter().cloned());
+    }
+  }
+
+  let maven_root = get_download_api(priority[0], ResourceType::QuiltMaven)?;
+  for library in &profile.libraries {
+    add_library_entry(&mut client_info.libraries, &library.name, None)?;
+    add_library_entry(&mut new_patch.libraries, &library.name, None)?;
+
+    let rel: String = convert_library_name_to_path(&library.name, None)?;
+    let url_root = match &library.url {
+      Some(url) => Url::parse(url)?,
+      None => maven_root.clone(),
+    };
+    let src = convert_url_to_target_source(
+      &url_root.join(&rel)?,
+      &[
+        ResourceType::QuiltMaven,
+        ResourceType::FabricMaven,
+        ResourceType::Libraries,
+      ],
+      &priority[0],
+    )?;
+    task_params.push(PTaskParam::Download(DownloadParam {
+      src,
+      dest: lib_dir.join(&rel),
+      filename: None,
+      sha1: None,
+      fallback_srcs: vec![],
+    }));
+  }
+
+  client_info.patches.push(new_patch);
+
+  // Download Quilted Fabric API (QFAPI), which bundles the Quilt Standard Libraries (QSL)
+  if is_install_quilt_api.unwrap_or(true) {
+    if let Ok(Some(quilt_api_download)) =
+      get_latest_quilted_fabric_api_mod_download(&app, game_version, mods_dir).await
+    {
+      task_params.push(PTaskParam::Download(quilt_api_download));
+    }
+  }
+
+  Ok(())
+}
@@ -10,3 +10,4 @@ pub mod options_txt;
 pub mod resourcepack;
 pub mod server;
 pub mod world;
+pub mod world_backup;
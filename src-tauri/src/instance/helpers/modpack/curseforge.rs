@@ -1,6 +1,6 @@
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
@@ -9,7 +9,9 @@ use tauri_plugin_http::reqwest;
 use zip::ZipArchive;
 
 use crate::error::{LXMCLError, LXMCLResult};
-use crate::instance::models::misc::{InstanceError, ModLoaderType};
+use crate::instance::helpers::modpack::misc::get_loader_version_without_game;
+use crate::instance::models::misc::{Instance, InstanceError, ModLoaderType};
+use crate::resource::helpers::curseforge::fetch_curseforge_file_by_local;
 use crate::resource::helpers::curseforge::misc::CurseForgeProject;
 use crate::tasks::download::DownloadParam;
 use crate::tasks::PTaskParam;
@@ -35,6 +37,10 @@ structstruck::strike! {
 #[strikethrough[derive(Deserialize, Serialize, Debug, Clone)]]
 #[strikethrough[serde(rename_all = "camelCase")]]
   pub struct CurseForgeManifest {
+    #[serde(default)]
+    pub manifest_type: String,
+    #[serde(default)]
+    pub manifest_version: u32,
     pub name: String,
     pub version: String,
     pub author: String,
@@ -173,6 +179,7 @@ impl CurseForgeManifest {
             })
             .join(&file_manifest.data.file_name),
           filename: Some(file_manifest.data.file_name.clone()),
+          fallback_srcs: vec![],
         });
 
         Ok::<PTaskParam, LXMCLError>(task_param)
@@ -188,3 +195,71 @@ impl CurseForgeManifest {
     Ok(task_params)
   }
 }
+
+impl CurseForgeManifest {
+  /// Build a CurseForge `manifest.json` for the instance, resolving each file in `mods/` by its fingerprint.
+  /// Returns the manifest and the mod files that cannot be resolved (to be put into `overrides/`).
+  pub async fn from_instance(
+    app: &AppHandle,
+    instance: &Instance,
+    name: &str,
+    version: &str,
+    author: &str,
+    mod_paths: &[PathBuf],
+  ) -> LXMCLResult<(Self, Vec<PathBuf>)> {
+    let loader_prefix = match instance.mod_loader.loader_type {
+      ModLoaderType::Unknown => None,
+      ModLoaderType::Forge | ModLoaderType::LegacyForge => Some("forge"),
+      ModLoaderType::NeoForge => Some("neoforge"),
+      ModLoaderType::Fabric => Some("fabric"),
+      ModLoaderType::Quilt => Some("quilt"),
+      ModLoaderType::LiteLoader => return Err(InstanceError::UnsupportedModLoader.into()),
+    };
+    let mod_loaders = loader_prefix
+      .map(|prefix| {
+        vec![CurseForgeModLoader {
+          id: format!(
+            "{}-{}",
+            prefix,
+            get_loader_version_without_game(&instance.mod_loader.version, &instance.version)
+          ),
+          primary: true,
+        }]
+      })
+      .unwrap_or_default();
+
+    let tasks = mod_paths
+      .iter()
+      .map(|path| async move { (path, fetch_curseforge_file_by_local(app, path).await) });
+
+    let mut files = Vec::new();
+    let mut unresolved = Vec::new();
+    for (path, remote) in futures::future::join_all(tasks).await {
+      match remote {
+        Ok(cf_file) => files.push(CurseForgeFiles {
+          project_id: cf_file.mod_id as u64,
+          file_id: cf_file.id as u64,
+          required: true,
+        }),
+        Err(_) => unresolved.push(path.clone()),
+      }
+    }
+
+    Ok((
+      CurseForgeManifest {
+        manifest_type: "minecraftModpack".to_string(),
+        manifest_version: 1,
+        name: name.to_string(),
+        version: version.to_string(),
+        author: author.to_string(),
+        overrides: "overrides".to_string(),
+        minecraft: Minecraft {
+          version: instance.version.clone(),
+          mod_loaders,
+        },
+        files,
+      },
+      unresolved,
+    ))
+  }
+}
@@ -4,11 +4,14 @@ use crate::instance::helpers::modpack::modrinth::ModrinthManifest;
 use crate::instance::helpers::modpack::multimc::MultiMcManifest;
 use crate::instance::models::misc::{InstanceError, ModLoader};
 use crate::resource::models::OtherResourceSource;
+use crate::utils::fs::add_path_to_zip;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::fs::File;
-use std::path::Path;
-use zip::ZipArchive;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use zip::write::{ExtendedFileOptions, FileOptions};
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -110,3 +113,52 @@ pub fn extract_overrides(
   }
   Ok(())
 }
+
+/// Strip the "<game version>-" prefix some loader versions carry, e.g. `1.20.1-47.2.0` -> `47.2.0`.
+pub fn get_loader_version_without_game(loader_version: &str, game_version: &str) -> String {
+  loader_version
+    .strip_prefix(&format!("{}-", game_version))
+    .unwrap_or(loader_version)
+    .to_string()
+}
+
+/// Write an exported modpack archive: the manifest at the archive root, then the chosen paths
+/// (relative to the game root) and the unresolved mod files under `overrides/`.
+pub fn write_modpack_archive<T: Serialize>(
+  save_path: &Path,
+  manifest_name: &str,
+  manifest: &T,
+  game_root: &Path,
+  override_paths: &[String],
+  unresolved_mods: &[PathBuf],
+) -> LXMCLResult<()> {
+  let zip_file = File::create(save_path).map_err(|_| InstanceError::FileCreationFailed)?;
+  let mut zip = ZipWriter::new(zip_file);
+  let options =
+    FileOptions::<ExtendedFileOptions>::default().compression_method(CompressionMethod::Deflated);
+
+  zip.start_file(manifest_name, options.clone())?;
+  zip.write_all(serde_json::to_string_pretty(manifest)?.as_bytes())?;
+
+  for rel_path in override_paths {
+    let rel = Path::new(rel_path);
+    // only plain relative paths inside the game root are accepted, `mods/` is handled separately
+    if rel.components().any(|c| !matches!(c, Component::Normal(_))) || rel.starts_with("mods") {
+      return Err(InstanceError::InvalidSourcePath.into());
+    }
+    let src = game_root.join(rel);
+    if src.exists() {
+      add_path_to_zip(&mut zip, &src, &Path::new("overrides").join(rel), &options)?;
+    }
+  }
+
+  for mod_path in unresolved_mods {
+    if let Some(file_name) = mod_path.file_name() {
+      let entry = Path::new("overrides").join("mods").join(file_name);
+      add_path_to_zip(&mut zip, mod_path, &entry, &options)?;
+    }
+  }
+
+  zip.finish()?;
+  Ok(())
+}
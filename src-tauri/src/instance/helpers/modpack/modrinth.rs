@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::Sha512;
+use tauri::AppHandle;
 use zip::ZipArchive;
 
 use crate::error::LXMCLResult;
-use crate::instance::models::misc::{InstanceError, ModLoaderType};
+use crate::instance::helpers::modpack::misc::get_loader_version_without_game;
+use crate::instance::models::misc::{Instance, InstanceError, ModLoaderType};
+use crate::resource::helpers::modrinth::fetch_remote_resource_by_local_modrinth;
 use crate::tasks::download::DownloadParam;
 use crate::tasks::PTaskParam;
 
@@ -32,6 +37,10 @@ pub struct ModrinthFile {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ModrinthManifest {
+  #[serde(default)]
+  pub format_version: u32,
+  #[serde(default)]
+  pub game: String,
   pub version_id: String,
   pub name: String,
   pub summary: Option<String>,
@@ -67,6 +76,7 @@ impl ModrinthManifest {
         "minecraft" => continue,
         "forge" => return Ok((ModLoaderType::Forge, val.to_string())),
         "fabric-loader" => return Ok((ModLoaderType::Fabric, val.to_string())),
+        "quilt-loader" => return Ok((ModLoaderType::Quilt, val.to_string())),
         "neoforge" => return Ok((ModLoaderType::NeoForge, val.to_string())),
         _ => return Err(InstanceError::UnsupportedModLoader.into()),
       }
@@ -88,8 +98,83 @@ impl ModrinthManifest {
           sha1: Some(file.hashes.sha1.clone()),
           dest: instance_path.join(&file.path),
           filename: None,
+          fallback_srcs: vec![],
         }))
       })
       .collect::<LXMCLResult<Vec<_>>>()
   }
 }
+
+impl ModrinthManifest {
+  /// Build a `modrinth.index.json` for the instance, resolving each file in `mods/` on Modrinth by its SHA-1.
+  /// Returns the manifest and the mod files that cannot be resolved (to be put into `overrides/`).
+  pub async fn from_instance(
+    app: &AppHandle,
+    instance: &Instance,
+    name: &str,
+    version: &str,
+    summary: Option<String>,
+    mod_paths: &[PathBuf],
+  ) -> LXMCLResult<(Self, Vec<PathBuf>)> {
+    let mut dependencies = HashMap::new();
+    dependencies.insert("minecraft".to_string(), instance.version.clone());
+    let loader_key = match instance.mod_loader.loader_type {
+      ModLoaderType::Unknown => None,
+      ModLoaderType::Forge | ModLoaderType::LegacyForge => Some("forge"),
+      ModLoaderType::NeoForge => Some("neoforge"),
+      ModLoaderType::Fabric => Some("fabric-loader"),
+      ModLoaderType::Quilt => Some("quilt-loader"),
+      ModLoaderType::LiteLoader => return Err(InstanceError::UnsupportedModLoader.into()),
+    };
+    if let Some(key) = loader_key {
+      dependencies.insert(
+        key.to_string(),
+        get_loader_version_without_game(&instance.mod_loader.version, &instance.version),
+      );
+    }
+
+    let tasks = mod_paths.iter().map(|path| async move {
+      let remote = fetch_remote_resource_by_local_modrinth(app, &path.to_string_lossy()).await;
+      (path, remote)
+    });
+
+    let mut files = Vec::new();
+    let mut unresolved = Vec::new();
+    for (path, remote) in futures::future::join_all(tasks).await {
+      let Ok(remote) = remote else {
+        unresolved.push(path.clone());
+        continue;
+      };
+      let content = tokio::fs::read(path).await?;
+      let file_name = path
+        .file_name()
+        .ok_or(InstanceError::InvalidSourcePath)?
+        .to_string_lossy()
+        .to_string();
+      files.push(ModrinthFile {
+        path: format!("mods/{}", file_name),
+        hashes: Hashes {
+          sha1: hex::encode(Sha1::digest(&content)),
+          sha512: hex::encode(Sha512::digest(&content)),
+        },
+        env: None,
+        downloads: vec![remote.download_url],
+        file_size: content.len() as u64,
+      });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok((
+      ModrinthManifest {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: version.to_string(),
+        name: name.to_string(),
+        summary,
+        files,
+        dependencies,
+      },
+      unresolved,
+    ))
+  }
+}
@@ -111,6 +111,7 @@ impl MultiMcManifest {
         "net.fabricmc.fabric-loader" => {
           return Ok((ModLoaderType::Fabric, get_version(component)?))
         }
+        "org.quiltmc.quilt-loader" => return Ok((ModLoaderType::Quilt, get_version(component)?)),
         "net.neoforged" => return Ok((ModLoaderType::NeoForge, get_version(component)?)),
         _ => continue,
       }
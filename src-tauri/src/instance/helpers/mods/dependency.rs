@@ -0,0 +1,749 @@
+// Builds a dependency graph from the declared metadata of each mod, and checks it for
+// missing dependencies, version range violations, duplicate mod ids and declared conflicts.
+// https://wiki.fabricmc.net/documentation:fabric_mod_json_spec#versionrange
+// https://maven.apache.org/enforcer/enforcer-rules/versionRanges.html (used by mods.toml)
+use crate::instance::helpers::modpack::misc::get_loader_version_without_game;
+use crate::instance::helpers::mods::forge::ForgeModDependency;
+use crate::instance::helpers::mods::{fabric, forge, legacy_forge, quilt};
+use crate::instance::models::misc::{
+  ModDependencyIssue, ModDependencyIssueType, ModLoader, ModLoaderType,
+};
+use crate::resource::helpers::curseforge::fetch_resource_list_by_name_curseforge;
+use crate::resource::helpers::modrinth::fetch_remote_resource_by_id_modrinth;
+use crate::resource::models::{OtherResourceInfo, OtherResourceSearchQuery};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read, Seek};
+use tauri::AppHandle;
+use zip::ZipArchive;
+
+// nested jars (jar-in-jar) are only resolved this deep
+const MAX_NESTED_DEPTH: u8 = 2;
+
+// ids which are provided by the game or the mod loader rather than by a mod file
+const IGNORED_MOD_IDS: &[&str] = &["java", "fml", "javafml", "lowcodefml", "mcp"];
+
+#[derive(Debug, Clone)]
+pub enum ModVersionRange {
+  Any,
+  Fabric(Vec<String>), // alternatives, each one a space separated list of predicates
+  Maven(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ModDependency {
+  pub mod_id: String,
+  pub range: ModVersionRange,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModDependencyNode {
+  pub mod_id: String,
+  pub name: String,
+  pub version: String,
+  pub file_name: String,
+  pub provides: Vec<String>,
+  pub depends: Vec<ModDependency>,
+  pub breaks: Vec<ModDependency>,
+  pub nested: bool,
+}
+
+impl ModVersionRange {
+  fn from_fabric_value(value: &Value) -> Self {
+    let predicates = match value {
+      Value::String(s) => vec![s.clone()],
+      Value::Array(arr) => arr
+        .iter()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect(),
+      _ => vec![],
+    };
+    if predicates.is_empty() || predicates.iter().any(|p| p.trim() == "*") {
+      ModVersionRange::Any
+    } else {
+      ModVersionRange::Fabric(predicates)
+    }
+  }
+
+  fn from_maven_range(range: &str) -> Self {
+    let range = range.trim();
+    // a bare version is only a recommendation in maven, placeholders are not expanded in dev jars
+    if range.is_empty() || range == "*" || range.contains("${") || !range.starts_with(['[', '(']) {
+      ModVersionRange::Any
+    } else {
+      ModVersionRange::Maven(range.to_string())
+    }
+  }
+
+  pub fn describe(&self) -> Option<String> {
+    match self {
+      ModVersionRange::Any => None,
+      ModVersionRange::Fabric(predicates) => Some(predicates.join(" || ")),
+      ModVersionRange::Maven(range) => Some(range.clone()),
+    }
+  }
+
+  // unparsable versions are considered as matched, to avoid false positives
+  pub fn matches(&self, version: &str) -> bool {
+    match self {
+      ModVersionRange::Any => true,
+      ModVersionRange::Fabric(predicates) => predicates.iter().any(|predicate| {
+        predicate
+          .split_whitespace()
+          .all(|term| matches_fabric_predicate(term, version))
+      }),
+      ModVersionRange::Maven(range) => matches_maven_range(range, version),
+    }
+  }
+}
+
+fn split_version(version: &str) -> (Vec<&str>, Option<&str>) {
+  // ignore build metadata
+  let mut rest = version.split('+').next().unwrap_or_default();
+  let mut release = Vec::new();
+  loop {
+    let (head, tail) = match rest.split_once('-') {
+      Some((head, tail)) => (head, Some(tail)),
+      None => (rest, None),
+    };
+    release.extend(head.split('.'));
+    match tail {
+      // only a suffix starting with a letter is a pre-release (e.g. -beta.1), a numeric one is part
+      // of the version, e.g. legacy forge 10.13.4.1614-1.7.10 or mod versions like 1.12.2-4.15.0
+      Some(tail) if tail.starts_with(|c: char| c.is_ascii_digit()) => rest = tail,
+      pre => return (release, pre),
+    }
+  }
+}
+
+fn compare_segment(a: &str, b: &str) -> Ordering {
+  match (a.parse::<u64>(), b.parse::<u64>()) {
+    (Ok(a), Ok(b)) => a.cmp(&b),
+    _ => a.cmp(b),
+  }
+}
+
+// lenient semver-like comparison, mod versions are often not valid semver
+pub fn compare_mod_versions(a: &str, b: &str) -> Ordering {
+  let (a_release, a_pre) = split_version(a.trim());
+  let (b_release, b_pre) = split_version(b.trim());
+  for i in 0..a_release.len().max(b_release.len()) {
+    let a_seg = a_release.get(i).copied().unwrap_or("0");
+    let b_seg = b_release.get(i).copied().unwrap_or("0");
+    match compare_segment(a_seg, b_seg) {
+      Ordering::Equal => continue,
+      order => return order,
+    }
+  }
+  match (a_pre, b_pre) {
+    (None, None) => Ordering::Equal,
+    (None, Some(_)) => Ordering::Greater,
+    (Some(_), None) => Ordering::Less,
+    (Some(a_pre), Some(b_pre)) => {
+      let a_segs = a_pre.split('.').collect::<Vec<_>>();
+      let b_segs = b_pre.split('.').collect::<Vec<_>>();
+      for (a_seg, b_seg) in a_segs.iter().zip(b_segs.iter()) {
+        match compare_segment(a_seg, b_seg) {
+          Ordering::Equal => continue,
+          order => return order,
+        }
+      }
+      a_segs.len().cmp(&b_segs.len())
+    }
+  }
+}
+
+fn matches_fabric_predicate(term: &str, version: &str) -> bool {
+  let term = term.trim();
+  if term.is_empty() || term == "*" {
+    return true;
+  }
+  let (op, target) = ["<=", ">=", "<", ">", "=", "^", "~"]
+    .iter()
+    .find_map(|op| term.strip_prefix(op).map(|target| (*op, target.trim())))
+    .unwrap_or(("", term));
+
+  // wildcard, e.g. 1.20.x
+  if target.ends_with(".x") || target.ends_with(".X") || target.ends_with(".*") {
+    let prefix = &target[..target.len() - 2];
+    let (prefix_release, _) = split_version(prefix);
+    let (release, _) = split_version(version);
+    return prefix_release.iter().enumerate().all(|(i, seg)| {
+      release
+        .get(i)
+        .is_some_and(|v| compare_segment(v, seg).is_eq())
+    });
+  }
+
+  let order = compare_mod_versions(version, target);
+  let (target_release, _) = split_version(target);
+  let bump = |index: usize| -> String {
+    let mut segs = target_release
+      .iter()
+      .map(|seg| seg.parse::<u64>().unwrap_or_default())
+      .collect::<Vec<_>>();
+    segs.resize(segs.len().max(index + 1), 0);
+    segs[index] += 1;
+    segs.truncate(index + 1);
+    segs
+      .iter()
+      .map(|seg| seg.to_string())
+      .collect::<Vec<_>>()
+      .join(".")
+  };
+  match op {
+    "<=" => order.is_le(),
+    ">=" => order.is_ge(),
+    "<" => order.is_lt(),
+    ">" => order.is_gt(),
+    // same major version
+    "^" => order.is_ge() && compare_mod_versions(version, &bump(0)).is_lt(),
+    // same major and minor version
+    "~" => order.is_ge() && compare_mod_versions(version, &bump(1)).is_lt(),
+    _ => order.is_eq(),
+  }
+}
+
+fn matches_maven_range(range: &str, version: &str) -> bool {
+  // a range may be a union of several intervals, e.g. "[1.0,1.2),(1.2,)"
+  let mut intervals = Vec::new();
+  let mut current = String::new();
+  for c in range.chars() {
+    current.push(c);
+    if c == ']' || c == ')' {
+      intervals.push(current.trim().trim_start_matches(',').trim().to_string());
+      current.clear();
+    }
+  }
+  if intervals.is_empty() {
+    return true;
+  }
+
+  intervals.iter().any(|interval| {
+    if interval.len() < 2 {
+      return true;
+    }
+    let lower_inclusive = interval.starts_with('[');
+    let upper_inclusive = interval.ends_with(']');
+    let inner = &interval[1..interval.len() - 1];
+    match inner.split_once(',') {
+      None => compare_mod_versions(version, inner).is_eq(),
+      Some((lower, upper)) => {
+        let (lower, upper) = (lower.trim(), upper.trim());
+        let lower_ok = lower.is_empty() || {
+          let order = compare_mod_versions(version, lower);
+          order.is_gt() || (lower_inclusive && order.is_eq())
+        };
+        let upper_ok = upper.is_empty() || {
+          let order = compare_mod_versions(version, upper);
+          order.is_lt() || (upper_inclusive && order.is_eq())
+        };
+        lower_ok && upper_ok
+      }
+    }
+  })
+}
+
+fn parse_quilt_dependency(value: &Value) -> Option<(ModDependency, bool)> {
+  // strip the optional maven group, e.g. "org.quiltmc:quilt_loader"
+  let normalize_id = |id: &str| id.rsplit(':').next().unwrap_or(id).to_string();
+  match value {
+    Value::String(id) => Some((
+      ModDependency {
+        mod_id: normalize_id(id),
+        range: ModVersionRange::Any,
+      },
+      false,
+    )),
+    Value::Object(obj) => {
+      let id = obj.get("id")?.as_str()?;
+      let range = match obj.get("versions") {
+        Some(versions @ (Value::String(_) | Value::Array(_))) => {
+          ModVersionRange::from_fabric_value(versions)
+        }
+        _ => ModVersionRange::Any,
+      };
+      let optional = obj
+        .get("optional")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+      Some((
+        ModDependency {
+          mod_id: normalize_id(id),
+          range,
+        },
+        optional,
+      ))
+    }
+    _ => None,
+  }
+}
+
+fn read_nested_jar<R: Read + Seek>(
+  jar: &mut ZipArchive<R>,
+  path: &str,
+  file_name: &str,
+  depth: u8,
+) -> Vec<ModDependencyNode> {
+  let mut buf = Vec::new();
+  match jar.by_name(path) {
+    Ok(mut file) => {
+      if file.read_to_end(&mut buf).is_err() {
+        return vec![];
+      }
+    }
+    Err(_) => return vec![],
+  }
+  match ZipArchive::new(Cursor::new(buf)) {
+    Ok(mut nested) => read_dependency_nodes(&mut nested, file_name, depth + 1)
+      .into_iter()
+      .map(|node| ModDependencyNode {
+        nested: true,
+        ..node
+      })
+      .collect(),
+    Err(_) => vec![],
+  }
+}
+
+fn read_forge_jarjar_paths<R: Read + Seek>(jar: &mut ZipArchive<R>) -> Vec<String> {
+  let Ok(file) = jar.by_name("META-INF/jarjar/metadata.json") else {
+    return vec![];
+  };
+  let Ok(metadata) = serde_json::from_reader::<_, Value>(file) else {
+    return vec![];
+  };
+  metadata["jars"]
+    .as_array()
+    .map(|jars| {
+      jars
+        .iter()
+        .filter_map(|jar| jar["path"].as_str().map(String::from))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+// read the mod(s) declared in a jar, the first nodes are the jar's own mods, followed by nested ones.
+pub fn read_dependency_nodes<R: Read + Seek>(
+  jar: &mut ZipArchive<R>,
+  file_name: &str,
+  depth: u8,
+) -> Vec<ModDependencyNode> {
+  let mut nodes = Vec::new();
+  let mut nested_paths = Vec::new();
+
+  if let Ok(meta) = fabric::get_mod_metadata_from_jar(jar) {
+    let to_dependencies = |map: &HashMap<String, Value>| {
+      map
+        .iter()
+        .map(|(id, value)| ModDependency {
+          mod_id: id.clone(),
+          range: ModVersionRange::from_fabric_value(value),
+        })
+        .collect::<Vec<_>>()
+    };
+    nodes.push(ModDependencyNode {
+      mod_id: meta.id.clone(),
+      name: meta.name.clone().unwrap_or(meta.id.clone()),
+      version: meta.version.clone(),
+      file_name: file_name.to_string(),
+      provides: meta.provides.clone(),
+      depends: to_dependencies(&meta.depends),
+      breaks: to_dependencies(&meta.breaks),
+      nested: false,
+    });
+    nested_paths.extend(meta.jars.iter().map(|jar| jar.file.clone()));
+  } else if let Ok(meta) = quilt::get_mod_metadata_from_jar(jar) {
+    let depends = meta
+      .depends
+      .iter()
+      .filter_map(parse_quilt_dependency)
+      .filter(|(_, optional)| !optional)
+      .map(|(dependency, _)| dependency)
+      .collect();
+    let breaks = meta
+      .breaks
+      .iter()
+      .filter_map(parse_quilt_dependency)
+      .map(|(dependency, _)| dependency)
+      .collect();
+    let provides = meta
+      .provides
+      .iter()
+      .filter_map(|value| match value {
+        Value::String(id) => Some(id.clone()),
+        Value::Object(obj) => obj.get("id").and_then(|v| v.as_str()).map(String::from),
+        _ => None,
+      })
+      .collect();
+    nodes.push(ModDependencyNode {
+      mod_id: meta.id.clone(),
+      name: meta.metadata.name.clone().unwrap_or(meta.id.clone()),
+      version: meta.version.clone(),
+      file_name: file_name.to_string(),
+      provides,
+      depends,
+      breaks,
+      nested: false,
+    });
+    nested_paths.extend(meta.jars.iter().cloned());
+  } else if let Ok(meta) = forge::get_mod_metadata_from_jar(jar) {
+    for sub_mod in meta.mods.iter().filter(|m| !m.mod_id.is_empty()) {
+      let mut depends = Vec::new();
+      let mut breaks = Vec::new();
+      let dependencies = meta
+        .dependencies
+        .get(&sub_mod.mod_id)
+        .and_then(|value| serde_json::from_value::<Vec<ForgeModDependency>>(value.clone()).ok())
+        .unwrap_or_default();
+      for dependency in dependencies {
+        if dependency
+          .side
+          .as_ref()
+          .is_some_and(|side| side.eq_ignore_ascii_case("server"))
+        {
+          continue;
+        }
+        let entry = ModDependency {
+          mod_id: dependency.mod_id.clone(),
+          range: ModVersionRange::from_maven_range(&dependency.version_range),
+        };
+        match (dependency.type_.as_deref(), dependency.mandatory) {
+          (Some("required"), _) | (None, Some(true)) => depends.push(entry),
+          (Some("incompatible"), _) => breaks.push(entry),
+          _ => {}
+        }
+      }
+      nodes.push(ModDependencyNode {
+        mod_id: sub_mod.mod_id.clone(),
+        name: sub_mod
+          .display_name
+          .clone()
+          .unwrap_or(sub_mod.mod_id.clone()),
+        version: sub_mod.version.clone().unwrap_or_default(),
+        file_name: file_name.to_string(),
+        provides: vec![],
+        depends,
+        breaks,
+        nested: false,
+      });
+    }
+    nested_paths.extend(read_forge_jarjar_paths(jar));
+  } else if let Ok(meta) = legacy_forge::get_mod_metadata_from_jar(jar) {
+    if !meta.modid.is_empty() {
+      // entries may carry a version, e.g. "Forge@[10.13,)"
+      let depends = meta
+        .required_mods
+        .clone()
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| match entry.split_once('@') {
+          Some((id, range)) => ModDependency {
+            mod_id: id.to_string(),
+            range: ModVersionRange::from_maven_range(range),
+          },
+          None => ModDependency {
+            mod_id: entry.clone(),
+            range: ModVersionRange::Any,
+          },
+        })
+        .collect();
+      nodes.push(ModDependencyNode {
+        mod_id: meta.modid.clone(),
+        name: meta.name.clone().unwrap_or(meta.modid.clone()),
+        version: meta.version.clone().unwrap_or_default(),
+        file_name: file_name.to_string(),
+        provides: vec![],
+        depends,
+        breaks: vec![],
+        nested: false,
+      });
+    }
+  }
+
+  if depth < MAX_NESTED_DEPTH {
+    for path in nested_paths {
+      nodes.extend(read_nested_jar(jar, &path, file_name, depth));
+    }
+  }
+  nodes
+}
+
+// `builtins` are the ids provided by the game and the mod loader, with their versions
+pub fn check_mod_dependency_graph(
+  nodes: &[ModDependencyNode],
+  builtins: &HashMap<String, String>,
+) -> Vec<ModDependencyIssue> {
+  let mut issues = Vec::new();
+
+  // mod id (or provided alias) -> versions and files, BTreeMap for a stable report order
+  let mut provided: BTreeMap<String, Vec<&ModDependencyNode>> = BTreeMap::new();
+  for node in nodes {
+    provided
+      .entry(node.mod_id.to_lowercase())
+      .or_default()
+      .push(node);
+    for alias in &node.provides {
+      provided.entry(alias.to_lowercase()).or_default().push(node);
+    }
+  }
+
+  // duplicate mod ids, nested jars are deduplicated by the loader itself
+  for (mod_id, providers) in &provided {
+    let mut top_level = providers
+      .iter()
+      .filter(|node| !node.nested && node.mod_id.eq_ignore_ascii_case(mod_id))
+      .collect::<Vec<_>>();
+    top_level.dedup_by(|a, b| a.file_name == b.file_name);
+    if top_level.len() > 1 {
+      issues.push(ModDependencyIssue {
+        issue_type: ModDependencyIssueType::DuplicateMod,
+        mod_id: top_level[0].mod_id.clone(),
+        mod_name: top_level[0].name.clone(),
+        file_names: top_level
+          .iter()
+          .map(|node| node.file_name.clone())
+          .collect(),
+        ..Default::default()
+      });
+    }
+  }
+
+  let find_versions = |mod_id: &str| -> Option<Vec<String>> {
+    let mod_id = mod_id.to_lowercase();
+    if let Some(version) = builtins.get(&mod_id) {
+      return Some(vec![version.clone()]);
+    }
+    provided
+      .get(&mod_id)
+      .map(|providers| providers.iter().map(|node| node.version.clone()).collect())
+  };
+
+  for node in nodes.iter().filter(|node| !node.nested) {
+    for dependency in &node.depends {
+      if IGNORED_MOD_IDS.contains(&dependency.mod_id.to_lowercase().as_str()) {
+        continue;
+      }
+      match find_versions(&dependency.mod_id) {
+        None => issues.push(ModDependencyIssue {
+          issue_type: ModDependencyIssueType::MissingDependency,
+          mod_id: node.mod_id.clone(),
+          mod_name: node.name.clone(),
+          file_names: vec![node.file_name.clone()],
+          target_mod_id: Some(dependency.mod_id.clone()),
+          required_version: dependency.range.describe(),
+          ..Default::default()
+        }),
+        Some(versions) => {
+          // empty versions are unknown (e.g. loader version not recorded), skip checking
+          if versions
+            .iter()
+            .any(|version| version.is_empty() || dependency.range.matches(version))
+          {
+            continue;
+          }
+          issues.push(ModDependencyIssue {
+            issue_type: ModDependencyIssueType::VersionMismatch,
+            mod_id: node.mod_id.clone(),
+            mod_name: node.name.clone(),
+            file_names: vec![node.file_name.clone()],
+            target_mod_id: Some(dependency.mod_id.clone()),
+            required_version: dependency.range.describe(),
+            found_version: versions.first().cloned(),
+            ..Default::default()
+          });
+        }
+      }
+    }
+
+    for conflict in &node.breaks {
+      let Some(providers) = provided.get(&conflict.mod_id.to_lowercase()) else {
+        continue;
+      };
+      let conflicting = providers
+        .iter()
+        .filter(|other| other.file_name != node.file_name && conflict.range.matches(&other.version))
+        .collect::<Vec<_>>();
+      if let Some(other) = conflicting.first() {
+        issues.push(ModDependencyIssue {
+          issue_type: ModDependencyIssueType::Conflict,
+          mod_id: node.mod_id.clone(),
+          mod_name: node.name.clone(),
+          file_names: vec![node.file_name.clone(), other.file_name.clone()],
+          target_mod_id: Some(conflict.mod_id.clone()),
+          required_version: conflict.range.describe(),
+          found_version: Some(other.version.clone()),
+          ..Default::default()
+        });
+      }
+    }
+  }
+
+  issues
+}
+
+pub fn get_builtin_mod_versions(game_version: &str, loader: &ModLoader) -> HashMap<String, String> {
+  let loader_version = get_loader_version_without_game(&loader.version, game_version);
+  let loader_version = loader_version
+    .strip_prefix("neoforge-")
+    .or(loader_version.strip_prefix("forge-"))
+    .unwrap_or(&loader_version)
+    .to_string();
+  let loader_ids: &[&str] = match loader.loader_type {
+    ModLoaderType::Fabric => &["fabricloader"],
+    ModLoaderType::Quilt => &["quilt_loader", "fabricloader"],
+    ModLoaderType::Forge | ModLoaderType::LegacyForge => &["forge"],
+    ModLoaderType::NeoForge => &["neoforge", "forge"],
+    ModLoaderType::LiteLoader => &["liteloader"],
+    ModLoaderType::Unknown => &[],
+  };
+
+  let mut builtins = HashMap::new();
+  builtins.insert("minecraft".to_string(), game_version.to_string());
+  for id in loader_ids {
+    builtins.insert(id.to_string(), loader_version.clone());
+  }
+  builtins
+}
+
+// mod ids usually match the project slug on Modrinth or CurseForge (with '_' written as '-')
+pub async fn fetch_remote_mod_by_mod_id(
+  app: &AppHandle,
+  mod_id: &str,
+  game_version: &str,
+) -> Option<OtherResourceInfo> {
+  let mut slugs = vec![mod_id.to_lowercase()];
+  if mod_id.contains('_') {
+    slugs.push(mod_id.to_lowercase().replace('_', "-"));
+  }
+
+  for slug in &slugs {
+    if let Ok(resource) = fetch_remote_resource_by_id_modrinth(app, slug).await {
+      return Some(resource);
+    }
+  }
+
+  let query = OtherResourceSearchQuery {
+    resource_type: "mod".to_string(),
+    search_query: mod_id.to_string(),
+    game_version: game_version.to_string(),
+    selected_tag: "All".to_string(),
+    sort_by: "Popularity".to_string(),
+    page: 0,
+    page_size: 10,
+  };
+  fetch_resource_list_by_name_curseforge(app, &query)
+    .await
+    .ok()?
+    .list
+    .into_iter()
+    .find(|resource| slugs.contains(&resource.slug.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn compare_versions() {
+    let cases = [
+      ("1.0.0", "1.0", Ordering::Equal),
+      ("1.0.10", "1.0.9", Ordering::Greater),
+      ("1.0.0+build.5", "1.0.0", Ordering::Equal),
+      ("1.0.0-beta", "1.0.0", Ordering::Less),
+      ("1.0.0-alpha.2", "1.0.0-alpha.10", Ordering::Less),
+      ("1.0.0-rc.1", "1.0.0-beta.2", Ordering::Greater),
+      // numeric suffixes are not pre-releases
+      ("10.13.4.1614-1.7.10", "10.13.4.1614", Ordering::Greater),
+      ("1.12.2-4.15.0", "1.12.2-4.16.0", Ordering::Less),
+      ("1.20.1-0.5.3-beta", "1.20.1-0.5.3", Ordering::Less),
+    ];
+    for (a, b, expected) in cases {
+      assert_eq!(compare_mod_versions(a, b), expected, "{a} vs {b}");
+      assert_eq!(compare_mod_versions(b, a), expected.reverse(), "{b} vs {a}");
+    }
+  }
+
+  #[test]
+  fn parse_ranges() {
+    let fabric_cases = [
+      (json!("*"), None),
+      (json!([]), None),
+      (json!(5), None),
+      (json!([">=1.0", "*"]), None),
+      (json!(">=0.14.0"), Some(">=0.14.0")),
+      (json!([">=1.0 <2.0", "3.x"]), Some(">=1.0 <2.0 || 3.x")),
+    ];
+    for (value, expected) in fabric_cases {
+      let range = ModVersionRange::from_fabric_value(&value);
+      assert_eq!(range.describe().as_deref(), expected, "{value}");
+    }
+
+    let maven_cases = [
+      ("", None),
+      ("*", None),
+      ("${version}", None),
+      // a bare version is only a recommendation
+      ("1.0", None),
+      (" [1.0,) ", Some("[1.0,)")),
+      ("(,2.0]", Some("(,2.0]")),
+    ];
+    for (range, expected) in maven_cases {
+      let parsed = ModVersionRange::from_maven_range(range);
+      assert_eq!(parsed.describe().as_deref(), expected, "{range}");
+    }
+  }
+
+  #[test]
+  fn match_fabric_ranges() {
+    let cases = [
+      (">=0.14.0", "0.15.7", true),
+      (">=0.14.0", "0.13.0", false),
+      ("<1.0.0-beta", "1.0.0-alpha", true),
+      ("^1.2.0", "1.9.0", true),
+      ("^1.2.0", "2.0.0", false),
+      ("~1.2.0", "1.2.5", true),
+      ("~1.2.0", "1.3.0", false),
+      ("1.20.x", "1.20.4", true),
+      ("1.20.x", "1.21", false),
+      (">=1.0 <2.0", "1.5", true),
+      (">=1.0 <2.0", "2.0", false),
+      ("1.0.0", "1.0.0", true),
+      ("=1.0.0", "1.0.1", false),
+    ];
+    for (range, version, expected) in cases {
+      let parsed = ModVersionRange::from_fabric_value(&json!(range));
+      assert_eq!(parsed.matches(version), expected, "{version} in {range}");
+    }
+
+    let alternatives = ModVersionRange::from_fabric_value(&json!(["1.19.x", "1.20.x"]));
+    assert!(alternatives.matches("1.20.1"));
+    assert!(!alternatives.matches("1.18.2"));
+  }
+
+  #[test]
+  fn match_maven_ranges() {
+    let cases = [
+      ("[1.0,2.0)", "1.5", true),
+      ("[1.0,2.0)", "2.0", false),
+      ("[1.0,2.0]", "2.0", true),
+      ("(1.0,)", "1.0", false),
+      ("(,1.0]", "0.9", true),
+      ("[1.0]", "1.0", true),
+      ("[1.0]", "1.1", false),
+      ("[1.0,1.2),(1.2,)", "1.2", false),
+      ("[1.0,1.2),(1.2,)", "1.3", true),
+      ("[1.0-beta,)", "1.0-alpha", false),
+      // legacy forge versions carry the game version after the build number
+      ("[10.13.4,)", "10.13.4.1614-1.7.10", true),
+      ("[1.12.2-4.0.0,)", "1.12.2-4.15.0", true),
+    ];
+    for (range, version, expected) in cases {
+      let parsed = ModVersionRange::from_maven_range(range);
+      assert_eq!(parsed.matches(version), expected, "{version} in {range}");
+    }
+  }
+}
@@ -18,6 +18,20 @@ pub struct FabricModMetadata {
   pub icon: Option<String>,
   pub authors: Option<Value>,
   pub contact: Option<HashMap<String, String>>,
+  // mod id -> version predicate(s), a single string or an array of alternatives
+  #[serde(default)]
+  pub depends: HashMap<String, Value>,
+  #[serde(default)]
+  pub breaks: HashMap<String, Value>,
+  #[serde(default)]
+  pub provides: Vec<String>,
+  #[serde(default)]
+  pub jars: Vec<FabricNestedJar>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FabricNestedJar {
+  pub file: String,
 }
 
 pub fn get_mod_metadata_from_jar<R: Read + Seek>(
@@ -6,6 +6,7 @@ use crate::instance::models::misc::ModLoaderType;
 use crate::utils::image::{load_image_from_dir_async, load_image_from_jar, ImageWrapper};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::io::{Cursor, Read, Seek};
 use std::path::Path;
 use zip::ZipArchive;
@@ -21,6 +22,8 @@ pub struct ForgeModMetadata {
   pub mods: Vec<ForgeModSubItem>,
   // some non-standard mods write logo_file field in toml meta section.
   pub logo_file: Option<String>,
+  // mod id -> [[dependencies.<mod id>]] entries, see `ForgeModDependency`
+  pub dependencies: HashMap<String, Value>,
   // not in file, added by sjmcl
   pub valid_logo_file: Option<ImageWrapper>,
 }
@@ -39,6 +42,17 @@ pub struct ForgeModSubItem {
   pub logo_file: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ForgeModDependency {
+  pub mod_id: String,
+  pub mandatory: Option<bool>, // before Forge 1.20.6
+  #[serde(rename = "type")]
+  pub type_: Option<String>, // required, optional, incompatible or discouraged
+  pub version_range: String,
+  pub side: Option<String>,
+}
+
 pub fn get_mod_metadata_from_jar<R: Read + Seek>(
   jar: &mut ZipArchive<R>,
 ) -> LXMCLResult<ForgeModMetadata> {
@@ -70,6 +84,7 @@ pub fn get_mod_metadata_from_jar<R: Read + Seek>(
           license: String::new(),
           mods: vec![ForgeModSubItem::default()],
           logo_file: None,
+          dependencies: HashMap::new(),
           valid_logo_file: None,
         })
       } else {
@@ -139,6 +154,7 @@ pub async fn get_mod_metadata_from_dir(dir_path: &Path) -> LXMCLResult<ForgeModM
           license: String::new(),
           mods: vec![ForgeModSubItem::default()],
           logo_file: None,
+          dependencies: HashMap::new(),
           valid_logo_file: None,
         })
       } else {
@@ -20,6 +20,7 @@ pub struct LegacyForgeModMetadata {
   pub update_url: Option<String>,
   pub credits: Option<String>,
   pub author_list: Option<Vec<Value>>,
+  pub required_mods: Option<Vec<String>>,
 }
 
 pub fn get_mod_metadata_from_jar<R: Read + Seek>(
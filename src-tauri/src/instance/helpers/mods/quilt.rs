@@ -21,6 +21,11 @@ pub struct QuiltLoader {
   pub id: String,
   pub version: String,
   pub metadata: QuiltLoaderMetadata,
+  // entries are either a mod id string or an object with `id`, `versions` and `optional`
+  pub depends: Vec<Value>,
+  pub breaks: Vec<Value>,
+  pub provides: Vec<Value>,
+  pub jars: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -36,19 +41,19 @@ pub struct QuiltLoaderMetadata {
 pub fn get_mod_metadata_from_jar<R: Read + Seek>(
   jar: &mut ZipArchive<R>,
 ) -> LXMCLResult<QuiltLoader> {
-  let meta: QuiltLoader = match jar.by_name("quilt.mod.json") {
+  let meta: QuiltModMetadata = match jar.by_name("quilt.mod.json") {
     Ok(val) => match serde_json::from_reader(val) {
       Ok(val) => val,
       Err(e) => return Err(LXMCLError::from(e)),
     },
     Err(e) => return Err(LXMCLError::from(e)),
   };
-  Ok(meta)
+  Ok(meta.quilt_loader)
 }
 
 pub async fn get_mod_metadata_from_dir(dir_path: &Path) -> LXMCLResult<QuiltLoader> {
   let quilt_file_path = dir_path.join("quilt.mod.json");
   let content = tokio::fs::read_to_string(quilt_file_path).await?;
-  let meta: QuiltLoader = serde_json::from_str(&content)?;
-  Ok(meta)
+  let meta: QuiltModMetadata = serde_json::from_str(&content)?;
+  Ok(meta.quilt_loader)
 }
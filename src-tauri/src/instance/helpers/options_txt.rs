@@ -1,4 +1,9 @@
+use crate::error::LXMCLResult;
 use crate::instance::helpers::game_version::compare_game_versions;
+use crate::instance::models::misc::{GameOptions, OptionsTemplate};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
 use tauri::AppHandle;
 
 pub async fn get_zh_hans_lang_tag(game_version: &str, app: &AppHandle) -> Option<&'static str> {
@@ -18,4 +23,572 @@ pub async fn get_zh_hans_lang_tag(game_version: &str, app: &AppHandle) -> Option
   }
 }
 
-// TBD: struct of options.txt and more helpers?
+const KEY_BINDING_PREFIX: &str = "key_";
+const UNKNOWN_KEY_NAME: &str = "key.keyboard.unknown";
+
+// options captured in a settings template, besides key bindings.
+// keys not supported by the target game version are simply ignored by the game.
+const VIDEO_OPTION_KEYS: &[&str] = &[
+  "renderDistance",
+  "simulationDistance",
+  "guiScale",
+  "maxFps",
+  "enableVsync",
+  "fullscreen",
+  "fullscreenResolution",
+  "graphicsMode",
+  "fancyGraphics",
+  "ao",
+  "renderClouds",
+  "particles",
+  "mipmapLevels",
+  "biomeBlendRadius",
+  "entityShadows",
+  "entityDistanceScaling",
+  "prioritizeChunkUpdates",
+  "gamma",
+  "bobView",
+  "fov",
+  "fovEffectScale",
+  "screenEffectScale",
+  "darknessEffectScale",
+  "glintSpeed",
+  "glintStrength",
+  "attackIndicator",
+  "useVbo",
+  "anaglyph3d",
+];
+
+// key bindings renamed between game versions, (newer name, older name)
+const KEY_BINDING_ALIASES: &[(&str, &str)] = &[("key_key.swapOffhand", "key_key.swapHands")];
+
+// LWJGL 2 key codes used before 17w43a (1.13), and the key names used since.
+// mouse buttons were saved as `button - 100`.
+const LEGACY_KEY_CODES: &[(i32, &str)] = &[
+  (0, UNKNOWN_KEY_NAME),
+  (1, "key.keyboard.escape"),
+  (2, "key.keyboard.1"),
+  (3, "key.keyboard.2"),
+  (4, "key.keyboard.3"),
+  (5, "key.keyboard.4"),
+  (6, "key.keyboard.5"),
+  (7, "key.keyboard.6"),
+  (8, "key.keyboard.7"),
+  (9, "key.keyboard.8"),
+  (10, "key.keyboard.9"),
+  (11, "key.keyboard.0"),
+  (12, "key.keyboard.minus"),
+  (13, "key.keyboard.equal"),
+  (14, "key.keyboard.backspace"),
+  (15, "key.keyboard.tab"),
+  (16, "key.keyboard.q"),
+  (17, "key.keyboard.w"),
+  (18, "key.keyboard.e"),
+  (19, "key.keyboard.r"),
+  (20, "key.keyboard.t"),
+  (21, "key.keyboard.y"),
+  (22, "key.keyboard.u"),
+  (23, "key.keyboard.i"),
+  (24, "key.keyboard.o"),
+  (25, "key.keyboard.p"),
+  (26, "key.keyboard.left.bracket"),
+  (27, "key.keyboard.right.bracket"),
+  (28, "key.keyboard.enter"),
+  (29, "key.keyboard.left.control"),
+  (30, "key.keyboard.a"),
+  (31, "key.keyboard.s"),
+  (32, "key.keyboard.d"),
+  (33, "key.keyboard.f"),
+  (34, "key.keyboard.g"),
+  (35, "key.keyboard.h"),
+  (36, "key.keyboard.j"),
+  (37, "key.keyboard.k"),
+  (38, "key.keyboard.l"),
+  (39, "key.keyboard.semicolon"),
+  (40, "key.keyboard.apostrophe"),
+  (41, "key.keyboard.grave.accent"),
+  (42, "key.keyboard.left.shift"),
+  (43, "key.keyboard.backslash"),
+  (44, "key.keyboard.z"),
+  (45, "key.keyboard.x"),
+  (46, "key.keyboard.c"),
+  (47, "key.keyboard.v"),
+  (48, "key.keyboard.b"),
+  (49, "key.keyboard.n"),
+  (50, "key.keyboard.m"),
+  (51, "key.keyboard.comma"),
+  (52, "key.keyboard.period"),
+  (53, "key.keyboard.slash"),
+  (54, "key.keyboard.right.shift"),
+  (55, "key.keyboard.keypad.multiply"),
+  (56, "key.keyboard.left.alt"),
+  (57, "key.keyboard.space"),
+  (58, "key.keyboard.caps.lock"),
+  (59, "key.keyboard.f1"),
+  (60, "key.keyboard.f2"),
+  (61, "key.keyboard.f3"),
+  (62, "key.keyboard.f4"),
+  (63, "key.keyboard.f5"),
+  (64, "key.keyboard.f6"),
+  (65, "key.keyboard.f7"),
+  (66, "key.keyboard.f8"),
+  (67, "key.keyboard.f9"),
+  (68, "key.keyboard.f10"),
+  (69, "key.keyboard.num.lock"),
+  (70, "key.keyboard.scroll.lock"),
+  (71, "key.keyboard.keypad.7"),
+  (72, "key.keyboard.keypad.8"),
+  (73, "key.keyboard.keypad.9"),
+  (74, "key.keyboard.keypad.subtract"),
+  (75, "key.keyboard.keypad.4"),
+  (76, "key.keyboard.keypad.5"),
+  (77, "key.keyboard.keypad.6"),
+  (78, "key.keyboard.keypad.add"),
+  (79, "key.keyboard.keypad.1"),
+  (80, "key.keyboard.keypad.2"),
+  (81, "key.keyboard.keypad.3"),
+  (82, "key.keyboard.keypad.0"),
+  (83, "key.keyboard.keypad.decimal"),
+  (87, "key.keyboard.f11"),
+  (88, "key.keyboard.f12"),
+  (100, "key.keyboard.f13"),
+  (101, "key.keyboard.f14"),
+  (102, "key.keyboard.f15"),
+  (103, "key.keyboard.f16"),
+  (104, "key.keyboard.f17"),
+  (105, "key.keyboard.f18"),
+  (113, "key.keyboard.f19"),
+  (141, "key.keyboard.keypad.equal"),
+  (156, "key.keyboard.keypad.enter"),
+  (157, "key.keyboard.right.control"),
+  (181, "key.keyboard.keypad.divide"),
+  (183, "key.keyboard.print.screen"),
+  (184, "key.keyboard.right.alt"),
+  (197, "key.keyboard.pause"),
+  (199, "key.keyboard.home"),
+  (200, "key.keyboard.up"),
+  (201, "key.keyboard.page.up"),
+  (203, "key.keyboard.left"),
+  (205, "key.keyboard.right"),
+  (207, "key.keyboard.end"),
+  (208, "key.keyboard.down"),
+  (209, "key.keyboard.page.down"),
+  (210, "key.keyboard.insert"),
+  (211, "key.keyboard.delete"),
+  (219, "key.keyboard.left.win"),
+  (220, "key.keyboard.right.win"),
+  (221, "key.keyboard.menu"),
+];
+
+fn legacy_key_code_to_name(code: i32) -> String {
+  match code {
+    -100 => "key.mouse.left".to_string(),
+    -99 => "key.mouse.right".to_string(),
+    -98 => "key.mouse.middle".to_string(),
+    // mouse button 3 (0-based) is named "key.mouse.4"
+    -97..=-1 => format!("key.mouse.{}", code + 101),
+    _ => LEGACY_KEY_CODES
+      .iter()
+      .find(|(c, _)| *c == code)
+      .map(|(_, name)| name.to_string())
+      .unwrap_or(UNKNOWN_KEY_NAME.to_string()),
+  }
+}
+
+fn key_name_to_legacy_code(name: &str) -> i32 {
+  match name {
+    "key.mouse.left" => -100,
+    "key.mouse.right" => -99,
+    "key.mouse.middle" => -98,
+    _ => match name
+      .strip_prefix("key.mouse.")
+      .and_then(|n| n.parse::<i32>().ok())
+    {
+      Some(button) if (4..=100).contains(&button) => button - 101,
+      _ => LEGACY_KEY_CODES
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(code, _)| *code)
+        .unwrap_or(0),
+    },
+  }
+}
+
+// convert a key binding value to the key name format (e.g. "key.keyboard.w").
+pub fn normalize_key_binding(value: &str) -> String {
+  match value.parse::<i32>() {
+    Ok(code) => legacy_key_code_to_name(code),
+    Err(_) => value.to_string(),
+  }
+}
+
+// convert a key binding value to the format of the target game version.
+pub fn format_key_binding(value: &str, use_legacy_key_codes: bool) -> String {
+  let name = normalize_key_binding(value);
+  if use_legacy_key_codes {
+    key_name_to_legacy_code(&name).to_string()
+  } else {
+    name
+  }
+}
+
+// key bindings are saved as legacy key codes before 17w43a (1.13)
+pub async fn uses_legacy_key_codes(
+  app: &AppHandle,
+  options: &OptionsTxt,
+  game_version: &str,
+) -> bool {
+  match options.has_legacy_key_codes() {
+    Some(legacy) => legacy,
+    None => compare_game_versions(app, game_version, "17w43a", false)
+      .await
+      .is_lt(),
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OptionsLine {
+  Entry { key: String, value: String },
+  // empty or malformed lines, kept as is
+  Raw(String),
+}
+
+// Lossless model of options.txt: the order of lines, unknown keys and malformed lines are kept.
+#[derive(Debug, Clone, Default)]
+pub struct OptionsTxt {
+  lines: Vec<OptionsLine>,
+  crlf: bool,
+  trailing_newline: bool,
+}
+
+impl OptionsTxt {
+  pub fn parse(content: &str) -> Self {
+    let lines = content
+      .lines()
+      .map(|line| match line.split_once(':') {
+        Some((key, value)) if !key.is_empty() => OptionsLine::Entry {
+          key: key.to_string(),
+          value: value.to_string(),
+        },
+        _ => OptionsLine::Raw(line.to_string()),
+      })
+      .collect();
+    OptionsTxt {
+      lines,
+      crlf: content.contains("\r\n"),
+      trailing_newline: content.is_empty() || content.ends_with('\n'),
+    }
+  }
+
+  // returns an empty model if the file does not exist.
+  pub async fn load(path: &Path) -> LXMCLResult<Self> {
+    if !path.exists() {
+      return Ok(Self::parse(""));
+    }
+    let bytes = tokio::fs::read(path).await?;
+    Ok(Self::parse(&String::from_utf8_lossy(&bytes)))
+  }
+
+  pub async fn save(&self, path: &Path) -> LXMCLResult<()> {
+    tokio::fs::write(path, self.to_string()).await?;
+    Ok(())
+  }
+
+  pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.lines.iter().filter_map(|line| match line {
+      OptionsLine::Entry { key, value } => Some((key.as_str(), value.as_str())),
+      OptionsLine::Raw(_) => None,
+    })
+  }
+
+  pub fn get(&self, key: &str) -> Option<&str> {
+    self.entries().find(|(k, _)| *k == key).map(|(_, v)| v)
+  }
+
+  pub fn contains_key(&self, key: &str) -> bool {
+    self.get(key).is_some()
+  }
+
+  // update the value in place, or append the key if absent.
+  pub fn set(&mut self, key: &str, value: &str) {
+    for line in self.lines.iter_mut() {
+      if let OptionsLine::Entry { key: k, value: v } = line {
+        if k == key {
+          *v = value.to_string();
+          return;
+        }
+      }
+    }
+    self.lines.push(OptionsLine::Entry {
+      key: key.to_string(),
+      value: value.to_string(),
+    });
+  }
+
+  pub fn language(&self) -> Option<&str> {
+    self.get("lang")
+  }
+
+  pub fn set_language(&mut self, language: &str) {
+    self.set("lang", language);
+  }
+
+  pub fn render_distance(&self) -> Option<u32> {
+    self.get("renderDistance")?.trim().parse().ok()
+  }
+
+  pub fn set_render_distance(&mut self, render_distance: u32) {
+    self.set("renderDistance", &render_distance.to_string());
+  }
+
+  // 0 for "auto"
+  pub fn gui_scale(&self) -> Option<u32> {
+    self.get("guiScale")?.trim().parse().ok()
+  }
+
+  pub fn set_gui_scale(&mut self, gui_scale: u32) {
+    self.set("guiScale", &gui_scale.to_string());
+  }
+
+  // key bindings as (option key, raw value), e.g. ("key_key.jump", "key.keyboard.space")
+  pub fn key_bindings(&self) -> Vec<(&str, &str)> {
+    self
+      .entries()
+      .filter(|(key, _)| key.starts_with(KEY_BINDING_PREFIX))
+      .collect()
+  }
+
+  // whether key bindings are saved as legacy key codes, None if no key binding exists.
+  pub fn has_legacy_key_codes(&self) -> Option<bool> {
+    let (_, value) = self.key_bindings().into_iter().next()?;
+    Some(value.parse::<i32>().is_ok())
+  }
+
+  // set a key binding, using the name of the binding known by this file (if renamed).
+  pub fn set_key_binding(&mut self, key: &str, value: &str, use_legacy_key_codes: bool) {
+    let key = if self.contains_key(key) {
+      key
+    } else {
+      KEY_BINDING_ALIASES
+        .iter()
+        .find_map(|(newer, older)| {
+          if key == *newer && self.contains_key(older) {
+            Some(*older)
+          } else if key == *older && self.contains_key(newer) {
+            Some(*newer)
+          } else {
+            None
+          }
+        })
+        .unwrap_or(key)
+    };
+    let value = format_key_binding(value, use_legacy_key_codes);
+    self.set(key, &value);
+  }
+
+  // enabled resource packs, from the bottom to the top, e.g. ["vanilla", "file/pack.zip"]
+  pub fn resource_packs(&self) -> Vec<String> {
+    self
+      .get("resourcePacks")
+      .and_then(|value| serde_json::from_str(value).ok())
+      .unwrap_or_default()
+  }
+
+  pub fn set_resource_packs(&mut self, resource_packs: &[String]) {
+    let value = serde_json::to_string(resource_packs).unwrap_or("[]".to_string());
+    self.set("resourcePacks", &value);
+  }
+
+  pub fn to_game_options(&self) -> GameOptions {
+    GameOptions {
+      language: self.language().map(str::to_string),
+      render_distance: self.render_distance(),
+      gui_scale: self.gui_scale(),
+      key_bindings: self
+        .key_bindings()
+        .into_iter()
+        .map(|(key, value)| {
+          (
+            key.trim_start_matches(KEY_BINDING_PREFIX).to_string(),
+            normalize_key_binding(value),
+          )
+        })
+        .collect::<HashMap<_, _>>(),
+      resource_packs: self
+        .contains_key("resourcePacks")
+        .then(|| self.resource_packs()),
+    }
+  }
+
+  // apply the given fields (`None` for unchanged).
+  pub fn apply_game_options(&mut self, options: &GameOptions, use_legacy_key_codes: bool) {
+    if let Some(language) = &options.language {
+      self.set_language(language);
+    }
+    if let Some(render_distance) = options.render_distance {
+      self.set_render_distance(render_distance);
+    }
+    if let Some(gui_scale) = options.gui_scale {
+      self.set_gui_scale(gui_scale);
+    }
+    for (key, value) in &options.key_bindings {
+      self.set_key_binding(
+        &format!("{KEY_BINDING_PREFIX}{key}"),
+        value,
+        use_legacy_key_codes,
+      );
+    }
+    if let Some(resource_packs) = &options.resource_packs {
+      self.set_resource_packs(resource_packs);
+    }
+  }
+
+  // capture key bindings (in key name format) and video settings into a template.
+  pub fn to_template(&self, name: &str, source_version: &str) -> OptionsTemplate {
+    OptionsTemplate {
+      name: name.to_string(),
+      source_version: source_version.to_string(),
+      created_at: chrono::Utc::now().timestamp(),
+      key_bindings: self
+        .key_bindings()
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), normalize_key_binding(value)))
+        .collect::<BTreeMap<_, _>>(),
+      video_settings: self
+        .entries()
+        .filter(|(key, _)| VIDEO_OPTION_KEYS.contains(key))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<BTreeMap<_, _>>(),
+    }
+  }
+
+  pub fn apply_template(&mut self, template: &OptionsTemplate, use_legacy_key_codes: bool) {
+    for (key, value) in &template.key_bindings {
+      self.set_key_binding(key, value, use_legacy_key_codes);
+    }
+    for (key, value) in &template.video_settings {
+      self.set(key, value);
+    }
+    // "fancyGraphics" was replaced by "graphicsMode" (0: fast, 1: fancy, 2: fabulous) in 1.16
+    if let Some(mode) = template.video_settings.get("graphicsMode") {
+      if self.contains_key("fancyGraphics")
+        && !template.video_settings.contains_key("fancyGraphics")
+      {
+        self.set("fancyGraphics", if mode == "0" { "false" } else { "true" });
+      }
+    }
+  }
+}
+
+impl fmt::Display for OptionsTxt {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let line_ending = if self.crlf { "\r\n" } else { "\n" };
+    for (index, line) in self.lines.iter().enumerate() {
+      match line {
+        OptionsLine::Entry { key, value } => write!(f, "{key}:{value}")?,
+        OptionsLine::Raw(raw) => write!(f, "{raw}")?,
+      }
+      if index + 1 < self.lines.len() || self.trailing_newline {
+        write!(f, "{line_ending}")?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_and_format_losslessly() {
+    let contents = [
+      "",
+      "version:3465\nlang:en_us\n",
+      "version:3465\r\nlang:en_us\r\n",
+      "lang:en_us",
+      "resourcePacks:[\"vanilla\",\"file/pack.zip\"]\n\nnot an option\n:empty key\nmodded_option:a:b\n",
+    ];
+    for content in contents {
+      assert_eq!(OptionsTxt::parse(content).to_string(), content);
+    }
+  }
+
+  #[test]
+  fn set_updates_in_place_and_appends() {
+    let mut options = OptionsTxt::parse("version:3465\r\nrenderDistance:12\r\nunknownKey:1\r\n");
+    options.set_render_distance(8);
+    options.set_language("zh_cn");
+    assert_eq!(
+      options.to_string(),
+      "version:3465\r\nrenderDistance:8\r\nunknownKey:1\r\nlang:zh_cn\r\n"
+    );
+    assert_eq!(options.render_distance(), Some(8));
+  }
+
+  #[test]
+  fn read_resource_packs() {
+    let options = OptionsTxt::parse("resourcePacks:[\"vanilla\",\"file/pack.zip\"]\n");
+    assert_eq!(options.resource_packs(), ["vanilla", "file/pack.zip"]);
+    assert!(OptionsTxt::parse("resourcePacks:broken\n")
+      .resource_packs()
+      .is_empty());
+  }
+
+  #[test]
+  fn translate_legacy_key_codes() {
+    let cases = [
+      ("17", "key.keyboard.w"),
+      ("57", "key.keyboard.space"),
+      ("0", UNKNOWN_KEY_NAME),
+      ("9999", UNKNOWN_KEY_NAME),
+      ("-100", "key.mouse.left"),
+      ("-99", "key.mouse.right"),
+      ("-98", "key.mouse.middle"),
+      ("-97", "key.mouse.4"),
+      ("key.keyboard.q", "key.keyboard.q"),
+    ];
+    for (value, name) in cases {
+      assert_eq!(normalize_key_binding(value), name, "{value}");
+    }
+  }
+
+  #[test]
+  fn format_key_bindings_for_target_version() {
+    let cases = [
+      ("key.keyboard.w", true, "17"),
+      ("key.keyboard.w", false, "key.keyboard.w"),
+      ("17", false, "key.keyboard.w"),
+      ("17", true, "17"),
+      ("key.mouse.left", true, "-100"),
+      ("key.mouse.5", true, "-96"),
+      ("key.keyboard.world.1", true, "0"),
+    ];
+    for (value, legacy, formatted) in cases {
+      assert_eq!(format_key_binding(value, legacy), formatted, "{value}");
+    }
+    // every legacy code converts back to itself
+    for (code, _) in LEGACY_KEY_CODES {
+      assert_eq!(
+        format_key_binding(&code.to_string(), true),
+        code.to_string()
+      );
+    }
+  }
+
+  #[test]
+  fn set_key_binding_by_known_alias() {
+    let mut options = OptionsTxt::parse("key_key.swapHands:key.keyboard.f\n");
+    assert_eq!(options.has_legacy_key_codes(), Some(false));
+    options.set_key_binding("key_key.swapOffhand", "key.keyboard.g", false);
+    assert_eq!(options.to_string(), "key_key.swapHands:key.keyboard.g\n");
+    assert_eq!(
+      OptionsTxt::parse("key_key.jump:57\n").has_legacy_key_codes(),
+      Some(true)
+    );
+    assert_eq!(
+      OptionsTxt::parse("lang:en_us\n").has_legacy_key_codes(),
+      None
+    );
+  }
+}
@@ -1,58 +1,715 @@
 use crate::error::{LXMCLError, LXMCLResult};
-use quartz_nbt::io::Flavor;
+use crate::instance::models::misc::{GameServerEntry, InstanceError};
+use hickory_resolver::TokioAsyncResolver;
+use quartz_nbt::io::{read_nbt, write_nbt, Flavor};
+use quartz_nbt::{NbtCompound, NbtList, NbtTag};
 use serde::{self, Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{Cursor, Read};
+use std::net::IpAddr;
 use std::path::Path;
-use tauri_plugin_http::reqwest;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-pub struct NbtServerInfo {
-  pub ip: String,
-  pub icon: Option<String>,
-  pub name: String,
+const DEFAULT_SERVER_PORT: u16 = 25565;
+const SERVER_QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+// -1 means the client does not know which version to use, servers reply with their own version.
+const STATUS_PROTOCOL_VERSION: i32 = -1;
+// protocol version sent in the 1.6 legacy ping, 74 is 1.6.2.
+const LEGACY_PROTOCOL_VERSION: u8 = 74;
+const MAX_STATUS_PACKET_LEN: usize = 1 << 21;
+
+const SERVERS_TAG: &str = "servers";
+// the name given by the game to a server added without one.
+const DEFAULT_SERVER_NAME: &str = "Minecraft Server";
+const ICON_DATA_URL_PREFIX: &str = "data:image/png;base64,";
+
+/// Editable model of `servers.dat` (uncompressed NBT).
+/// Unknown tags, of the root and of each server entry, are kept when saving.
+pub struct ServersDat {
+  root: NbtCompound,
+  root_name: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct NbtServersInfo {
-  pub servers: Vec<NbtServerInfo>,
+impl ServersDat {
+  /// Load from the given path, returns an empty list if the file does not exist.
+  pub async fn load(path: &Path) -> LXMCLResult<Self> {
+    if !path.exists() {
+      return Ok(ServersDat {
+        root: NbtCompound::new(),
+        root_name: String::new(),
+      });
+    }
+    let bytes = tokio::fs::read(path).await?;
+    let (root, root_name) = read_nbt(&mut Cursor::new(bytes), Flavor::Uncompressed)
+      .map_err(|_| InstanceError::ServerNbtReadError)?;
+    Ok(ServersDat { root, root_name })
+  }
+
+  /// Save like the game does: write `servers.dat_tmp`, keep the previous file as `servers.dat_old`.
+  pub async fn save(&self, path: &Path) -> LXMCLResult<()> {
+    let mut bytes = Vec::new();
+    write_nbt(
+      &mut bytes,
+      Some(&self.root_name),
+      &self.root,
+      Flavor::Uncompressed,
+    )
+    .map_err(|_| InstanceError::ServerNbtWriteError)?;
+    let tmp_path = path.with_file_name("servers.dat_tmp");
+    tokio::fs::write(&tmp_path, bytes).await?;
+    if path.exists() {
+      tokio::fs::copy(path, path.with_file_name("servers.dat_old")).await?;
+    }
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+  }
+
+  fn servers_mut(&mut self) -> &mut Vec<NbtTag> {
+    if self.root.get::<_, &NbtList>(SERVERS_TAG).is_err() {
+      self.root.insert(SERVERS_TAG, NbtList::new());
+    }
+    self
+      .root
+      .get_mut::<_, &mut NbtList>(SERVERS_TAG)
+      .unwrap()
+      .inner_mut()
+  }
+
+  pub fn entries(&self) -> Vec<GameServerEntry> {
+    let Ok(servers) = self.root.get::<_, &NbtList>(SERVERS_TAG) else {
+      return Vec::new();
+    };
+    // keep one entry per tag, so indices match the ones in the file
+    servers
+      .iter()
+      .map(|tag| match tag {
+        NbtTag::Compound(compound) => read_server_entry(compound),
+        _ => GameServerEntry::default(),
+      })
+      .collect()
+  }
+
+  /// Insert a server at `index` (appended if None).
+  pub fn insert(&mut self, index: Option<usize>, entry: &GameServerEntry) -> LXMCLResult<()> {
+    let mut compound = NbtCompound::new();
+    write_server_entry(&mut compound, entry)?;
+    let servers = self.servers_mut();
+    let index = index.unwrap_or(servers.len());
+    if index > servers.len() {
+      return Err(InstanceError::ServerNotFoundError.into());
+    }
+    servers.insert(index, NbtTag::Compound(compound));
+    Ok(())
+  }
+
+  pub fn update(&mut self, index: usize, entry: &GameServerEntry) -> LXMCLResult<()> {
+    match self.servers_mut().get_mut(index) {
+      Some(NbtTag::Compound(compound)) => write_server_entry(compound, entry),
+      _ => Err(InstanceError::ServerNotFoundError.into()),
+    }
+  }
+
+  pub fn remove(&mut self, index: usize) -> LXMCLResult<()> {
+    let servers = self.servers_mut();
+    if index >= servers.len() {
+      return Err(InstanceError::ServerNotFoundError.into());
+    }
+    servers.remove(index);
+    Ok(())
+  }
+
+  /// Import servers from a list, those already in the list (by address, also within the imported
+  /// list) are updated in place, keeping their icon if the imported entry has none.
+  pub fn import(&mut self, entries: &[GameServerEntry]) -> LXMCLResult<()> {
+    let mut current = self.entries();
+    for entry in entries {
+      let mut entry = GameServerEntry {
+        ip: entry.ip.trim().to_string(),
+        ..entry.clone()
+      };
+      match current.iter().position(|server| server.ip == entry.ip) {
+        Some(index) => {
+          if !entry
+            .icon
+            .as_deref()
+            .is_some_and(|icon| !icon.trim().is_empty())
+          {
+            entry.icon = current[index].icon.clone();
+          }
+          self.update(index, &entry)?;
+          current[index] = entry;
+        }
+        None => {
+          self.insert(None, &entry)?;
+          current.push(entry);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Reorder the servers, `order` lists the current indices in the new order.
+  pub fn reorder(&mut self, order: &[usize]) -> LXMCLResult<()> {
+    let servers = self.servers_mut();
+    let mut sorted_order = order.to_vec();
+    sorted_order.sort_unstable();
+    if !sorted_order.iter().copied().eq(0..servers.len()) {
+      return Err(InstanceError::ServerNotFoundError.into());
+    }
+    let mut tags = std::mem::take(servers)
+      .into_iter()
+      .map(Some)
+      .collect::<Vec<_>>();
+    *servers = order.iter().filter_map(|&i| tags[i].take()).collect();
+    Ok(())
+  }
+}
+
+fn read_server_entry(compound: &NbtCompound) -> GameServerEntry {
+  let get_string = |key: &str| compound.get::<_, &str>(key).ok().map(str::to_string);
+  GameServerEntry {
+    name: get_string("name").unwrap_or_default(),
+    ip: get_string("ip").unwrap_or_default(),
+    icon: get_string("icon"),
+    accept_textures: compound.get::<_, bool>("acceptTextures").ok(),
+  }
+}
+
+// `acceptTextures` is absent for "prompt", the icon is a base64 PNG without the data URL prefix.
+fn write_server_entry(compound: &mut NbtCompound, entry: &GameServerEntry) -> LXMCLResult<()> {
+  let ip = entry.ip.trim();
+  parse_server_address(ip).map_err(|_| InstanceError::InvalidServerAddress)?;
+  let name = match entry.name.trim() {
+    "" => DEFAULT_SERVER_NAME,
+    name => name,
+  };
+  compound.insert("name", name);
+  compound.insert("ip", ip);
+  match entry.icon.as_deref().map(str::trim) {
+    Some(icon) if !icon.is_empty() => {
+      compound.insert("icon", icon.trim_start_matches(ICON_DATA_URL_PREFIX));
+    }
+    _ => {
+      compound.inner_mut().remove("icon");
+    }
+  }
+  match entry.accept_textures {
+    Some(accept_textures) => compound.insert("acceptTextures", accept_textures),
+    None => {
+      compound.inner_mut().remove("acceptTextures");
+    }
+  }
+  Ok(())
 }
 
-pub async fn load_servers_info_from_path(path: &Path) -> LXMCLResult<Vec<NbtServerInfo>> {
-  if !path.exists() {
-    return Ok(Vec::new());
+/// Parse a server list to import, as a JSON array or a CSV file with a header row
+/// (columns `name`, `ip`, and optional `icon`, `acceptTextures`).
+pub async fn parse_server_list_file(path: &Path) -> LXMCLResult<Vec<GameServerEntry>> {
+  let content = tokio::fs::read_to_string(path)
+    .await
+    .map_err(|_| InstanceError::InvalidSourcePath)?;
+  let extension = path
+    .extension()
+    .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+  let entries = match extension.as_deref() {
+    Some("json") => serde_json::from_str::<Vec<GameServerEntry>>(&content)
+      .map_err(|_| InstanceError::ServerListParseError)?,
+    Some("csv") => csv::ReaderBuilder::new()
+      .trim(csv::Trim::All)
+      .from_reader(content.as_bytes())
+      .deserialize::<GameServerEntry>()
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(|_| InstanceError::ServerListParseError)?,
+    _ => return Err(InstanceError::InvalidSourcePath.into()),
+  };
+  if entries
+    .iter()
+    .any(|entry| parse_server_address(&entry.ip).is_err())
+  {
+    return Err(InstanceError::InvalidServerAddress.into());
   }
-  let bytes = tokio::fs::read(path).await?;
-  let (servers_info, _snbt) =
-    quartz_nbt::serde::deserialize::<NbtServersInfo>(&bytes, Flavor::Uncompressed)?;
-  Ok(servers_info.servers)
+  Ok(entries)
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SjmcServerQueryResult {
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ServerQueryResult {
   pub online: bool,
   pub players: Players,
-  pub description: Description,
+  pub description: String, // plain text MOTD, formatting codes stripped
   pub favicon: Option<String>,
+  pub version: Option<String>,
+  pub protocol: Option<i32>,
+  pub latency: Option<u64>, // in milliseconds, None if the server did not answer the ping
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Players {
   pub online: u64,
   pub max: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Description {
-  pub html: Option<String>,
-  pub text: Option<String>,
+// ref: https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping#Status_Response
+#[derive(Debug, Deserialize)]
+struct StatusResponse {
+  version: Option<StatusVersion>,
+  players: Option<Players>,
+  description: Option<Value>,
+  favicon: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusVersion {
+  name: String,
+  protocol: i32,
+}
+
+/// Query a Minecraft server's status via the Server List Ping protocol.
+/// Tries the modern (1.7+) handshake first, then falls back to the 1.6 and pre-1.4 legacy pings.
+///
+/// # Arguments
+///
+/// * `server` - The server address as saved in `servers.dat`, e.g. `mc.example.com`, `1.2.3.4:25566`, `[::1]:25565`.
+pub async fn query_server_status(server: &String) -> LXMCLResult<ServerQueryResult> {
+  let (host, port) = parse_server_address(server)?;
+  let (conn_host, conn_port) = match port {
+    Some(port) => (host.clone(), port),
+    None => resolve_srv_record(&host)
+      .await
+      .unwrap_or((host.clone(), DEFAULT_SERVER_PORT)),
+  };
+
+  if let Ok(Ok(result)) = timeout(
+    SERVER_QUERY_TIMEOUT,
+    modern_ping(&host, &conn_host, conn_port),
+  )
+  .await
+  {
+    return Ok(result);
+  }
+  if let Ok(Ok(result)) = timeout(
+    SERVER_QUERY_TIMEOUT,
+    legacy_ping_v16(&host, &conn_host, conn_port),
+  )
+  .await
+  {
+    return Ok(result);
+  }
+  Ok(timeout(SERVER_QUERY_TIMEOUT, legacy_ping_v14(&conn_host, conn_port)).await??)
+}
+
+/// Split a server address into host and optional port, IPv6 literals may be wrapped in brackets.
+fn parse_server_address(server: &str) -> LXMCLResult<(String, Option<u16>)> {
+  let server = server.trim();
+  if server.is_empty() {
+    return Err(LXMCLError("empty server address".to_string()));
+  }
+  let parse_port = |port: &str| {
+    port
+      .parse::<u16>()
+      .map_err(|_| LXMCLError(format!("invalid port: {}", port)))
+  };
+
+  if let Some(rest) = server.strip_prefix('[') {
+    let (host, tail) = rest
+      .split_once(']')
+      .ok_or_else(|| LXMCLError(format!("invalid server address: {}", server)))?;
+    let port = match tail.strip_prefix(':') {
+      Some(port) => Some(parse_port(port)?),
+      None => None,
+    };
+    return Ok((host.to_string(), port));
+  }
+  // bare IPv6 literal without port
+  if server.parse::<IpAddr>().is_ok() {
+    return Ok((server.to_string(), None));
+  }
+  match server.rsplit_once(':') {
+    Some((host, port)) => Ok((host.to_string(), Some(parse_port(port)?))),
+    None => Ok((server.to_string(), None)),
+  }
+}
+
+/// Look up the `_minecraft._tcp` SRV record, returns the target host and port if exists.
+async fn resolve_srv_record(host: &str) -> Option<(String, u16)> {
+  if host.parse::<IpAddr>().is_ok() || host.eq_ignore_ascii_case("localhost") {
+    return None;
+  }
+  let resolver = TokioAsyncResolver::tokio_from_system_conf().ok()?;
+  let lookup = timeout(
+    SERVER_QUERY_TIMEOUT,
+    resolver.srv_lookup(format!("_minecraft._tcp.{}.", host.trim_end_matches('.'))),
+  )
+  .await
+  .ok()?
+  .ok()?;
+  let record = lookup
+    .iter()
+    .min_by_key(|r| (r.priority(), std::cmp::Reverse(r.weight())))?;
+  let target = record.target().to_utf8();
+  Some((target.trim_end_matches('.').to_string(), record.port()))
+}
+
+async fn modern_ping(host: &str, conn_host: &str, port: u16) -> std::io::Result<ServerQueryResult> {
+  let mut stream = TcpStream::connect((conn_host, port)).await?;
+  stream.set_nodelay(true)?;
+
+  // handshake (next state = 1, status)
+  let mut handshake = Vec::new();
+  write_varint(&mut handshake, 0x00);
+  write_varint(&mut handshake, STATUS_PROTOCOL_VERSION);
+  write_string(&mut handshake, host);
+  handshake.extend_from_slice(&port.to_be_bytes());
+  write_varint(&mut handshake, 1);
+  send_packet(&mut stream, &handshake).await?;
+
+  // status request
+  send_packet(&mut stream, &[0x00]).await?;
+  let mut packet = read_packet(&mut stream).await?;
+  if read_varint(&mut packet)? != 0x00 {
+    return Err(invalid_data("unexpected status response packet id"));
+  }
+  let json = read_string(&mut packet)?;
+  let status: StatusResponse = serde_json::from_str(&json)?;
+
+  // ping with a timestamp payload, the server must echo it back
+  let payload = chrono::Utc::now().timestamp_millis();
+  let mut ping = Vec::new();
+  write_varint(&mut ping, 0x01);
+  ping.extend_from_slice(&payload.to_be_bytes());
+  let start = Instant::now();
+  send_packet(&mut stream, &ping).await?;
+  // some servers close the connection instead of answering the ping, the status is still valid
+  let latency = match read_packet(&mut stream).await {
+    Ok(mut pong) => match read_varint(&mut pong) {
+      Ok(0x01) => Some(start.elapsed().as_millis() as u64),
+      _ => None,
+    },
+    Err(_) => None,
+  };
+
+  let (version, protocol) = match status.version {
+    Some(v) => (Some(v.name), Some(v.protocol)),
+    None => (None, None),
+  };
+  Ok(ServerQueryResult {
+    online: true,
+    players: status.players.unwrap_or_default(),
+    description: status
+      .description
+      .as_ref()
+      .map(chat_component_to_plain_text)
+      .unwrap_or_default(),
+    favicon: status.favicon,
+    version,
+    protocol,
+    latency,
+  })
 }
 
-pub async fn query_server_status(server: &String) -> LXMCLResult<SjmcServerQueryResult> {
-  // construct request url
-  let url = format!("https://mc.sjtu.cn/custom/serverlist/?query={}", server);
-  let response = reqwest::get(&url).await?;
-  if !response.status().is_success() {
-    return Err(LXMCLError(format!("http error: {}", response.status())));
+// ref: https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping#1.6
+async fn legacy_ping_v16(
+  host: &str,
+  conn_host: &str,
+  port: u16,
+) -> std::io::Result<ServerQueryResult> {
+  let mut stream = TcpStream::connect((conn_host, port)).await?;
+
+  let mut request = vec![0xFE, 0x01, 0xFA];
+  write_utf16_string(&mut request, "MC|PingHost");
+  let host_utf16: Vec<u16> = host.encode_utf16().collect();
+  request.extend_from_slice(&(7 + 2 * host_utf16.len() as u16).to_be_bytes());
+  request.push(LEGACY_PROTOCOL_VERSION);
+  write_utf16_string(&mut request, host);
+  request.extend_from_slice(&(port as i32).to_be_bytes());
+
+  let start = Instant::now();
+  stream.write_all(&request).await?;
+  let response = read_legacy_kick(&mut stream).await?;
+  let latency = Some(start.elapsed().as_millis() as u64);
+
+  // 1.4+ servers answer with "§1\0<protocol>\0<version>\0<motd>\0<online>\0<max>"
+  if let Some(rest) = response.strip_prefix("\u{a7}1\0") {
+    let fields: Vec<&str> = rest.split('\0').collect();
+    if fields.len() < 5 {
+      return Err(invalid_data("malformed legacy ping response"));
+    }
+    return Ok(ServerQueryResult {
+      online: true,
+      players: Players {
+        online: fields[3].parse().unwrap_or(0),
+        max: fields[4].parse().unwrap_or(0),
+      },
+      description: strip_formatting_codes(fields[2]),
+      favicon: None,
+      version: Some(fields[1].to_string()),
+      protocol: fields[0].parse().ok(),
+      latency,
+    });
+  }
+  parse_beta_response(&response, latency)
+}
+
+// ref: https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping#Beta_1.8_to_1.3
+async fn legacy_ping_v14(conn_host: &str, port: u16) -> std::io::Result<ServerQueryResult> {
+  let mut stream = TcpStream::connect((conn_host, port)).await?;
+  let start = Instant::now();
+  stream.write_all(&[0xFE]).await?;
+  let response = read_legacy_kick(&mut stream).await?;
+  parse_beta_response(&response, Some(start.elapsed().as_millis() as u64))
+}
+
+// "<motd>§<online>§<max>", the MOTD itself may not contain '§'
+fn parse_beta_response(response: &str, latency: Option<u64>) -> std::io::Result<ServerQueryResult> {
+  let mut fields = response.rsplitn(3, '\u{a7}');
+  let max = fields.next().and_then(|s| s.parse().ok());
+  let online = fields.next().and_then(|s| s.parse().ok());
+  let motd = fields.next();
+  match (motd, online, max) {
+    (Some(motd), Some(online), Some(max)) => Ok(ServerQueryResult {
+      online: true,
+      players: Players { online, max },
+      description: motd.to_string(),
+      latency,
+      ..Default::default()
+    }),
+    _ => Err(invalid_data("malformed legacy ping response")),
+  }
+}
+
+async fn read_legacy_kick<R: AsyncRead + Unpin>(stream: &mut R) -> std::io::Result<String> {
+  if stream.read_u8().await? != 0xFF {
+    return Err(invalid_data("unexpected legacy ping response packet id"));
+  }
+  let len = stream.read_u16().await? as usize;
+  let mut buf = vec![0u8; len * 2];
+  stream.read_exact(&mut buf).await?;
+  let units: Vec<u16> = buf
+    .chunks_exact(2)
+    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+    .collect();
+  Ok(String::from_utf16_lossy(&units))
+}
+
+/// Flatten a chat component (string, object with `text`/`translate`/`extra`, or array) to plain text.
+fn chat_component_to_plain_text(component: &Value) -> String {
+  fn collect(component: &Value, out: &mut String) {
+    match component {
+      Value::String(s) => out.push_str(s),
+      Value::Array(items) => items.iter().for_each(|item| collect(item, out)),
+      Value::Object(map) => {
+        if let Some(Value::String(text)) = map.get("text") {
+          out.push_str(text);
+        } else if let Some(Value::String(key)) = map.get("translate") {
+          out.push_str(key);
+        }
+        if let Some(extra) = map.get("extra") {
+          collect(extra, out);
+        }
+      }
+      Value::Number(n) => out.push_str(&n.to_string()),
+      Value::Bool(b) => out.push_str(&b.to_string()),
+      Value::Null => {}
+    }
+  }
+  let mut text = String::new();
+  collect(component, &mut text);
+  strip_formatting_codes(&text)
+}
+
+/// Remove legacy `§x` formatting codes which are still widely used in MOTDs.
+fn strip_formatting_codes(text: &str) -> String {
+  let mut result = String::with_capacity(text.len());
+  let mut chars = text.chars();
+  while let Some(c) = chars.next() {
+    if c == '\u{a7}' {
+      chars.next();
+    } else {
+      result.push(c);
+    }
+  }
+  result
+}
+
+async fn send_packet(stream: &mut TcpStream, data: &[u8]) -> std::io::Result<()> {
+  let mut packet = Vec::with_capacity(data.len() + 5);
+  write_varint(&mut packet, data.len() as i32);
+  packet.extend_from_slice(data);
+  stream.write_all(&packet).await
+}
+
+async fn read_packet<R: AsyncRead + Unpin>(stream: &mut R) -> std::io::Result<Cursor<Vec<u8>>> {
+  let mut len: u32 = 0;
+  for i in 0..5 {
+    let byte = stream.read_u8().await?;
+    len |= ((byte & 0x7F) as u32) << (7 * i);
+    if byte & 0x80 == 0 {
+      if len as usize > MAX_STATUS_PACKET_LEN {
+        return Err(invalid_data("status packet too large"));
+      }
+      let mut buf = vec![0u8; len as usize];
+      stream.read_exact(&mut buf).await?;
+      return Ok(Cursor::new(buf));
+    }
+  }
+  Err(invalid_data("varint too long"))
+}
+
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+  let mut value = value as u32;
+  loop {
+    if value & !0x7F == 0 {
+      buf.push(value as u8);
+      return;
+    }
+    buf.push((value & 0x7F | 0x80) as u8);
+    value >>= 7;
+  }
+}
+
+fn read_varint(reader: &mut impl Read) -> std::io::Result<i32> {
+  let mut result: u32 = 0;
+  for i in 0..5 {
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte)?;
+    result |= ((byte[0] & 0x7F) as u32) << (7 * i);
+    if byte[0] & 0x80 == 0 {
+      return Ok(result as i32);
+    }
+  }
+  Err(invalid_data("varint too long"))
+}
+
+fn write_string(buf: &mut Vec<u8>, s: &str) {
+  write_varint(buf, s.len() as i32);
+  buf.extend_from_slice(s.as_bytes());
+}
+
+fn read_string(reader: &mut impl Read) -> std::io::Result<String> {
+  let len = read_varint(reader)?;
+  if len < 0 || len as usize > MAX_STATUS_PACKET_LEN {
+    return Err(invalid_data("invalid string length"));
+  }
+  let mut buf = vec![0u8; len as usize];
+  reader.read_exact(&mut buf)?;
+  String::from_utf8(buf).map_err(|_| invalid_data("invalid utf-8 string"))
+}
+
+fn write_utf16_string(buf: &mut Vec<u8>, s: &str) {
+  let units: Vec<u16> = s.encode_utf16().collect();
+  buf.extend_from_slice(&(units.len() as u16).to_be_bytes());
+  for unit in units {
+    buf.extend_from_slice(&unit.to_be_bytes());
+  }
+}
+
+fn invalid_data(msg: &str) -> std::io::Error {
+  std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::net::TcpListener;
+
+  fn server(name: &str, ip: &str, icon: Option<&str>) -> GameServerEntry {
+    GameServerEntry {
+      name: name.to_string(),
+      ip: ip.to_string(),
+      icon: icon.map(str::to_string),
+      accept_textures: None,
+    }
+  }
+
+  #[test]
+  fn import_servers() {
+    let mut servers_dat = ServersDat {
+      root: NbtCompound::new(),
+      root_name: String::new(),
+    };
+    servers_dat
+      .insert(None, &server("Old", "mc.example.com", Some("aWNvbg==")))
+      .unwrap();
+
+    servers_dat
+      .import(&[
+        server("Renamed", " mc.example.com ", None),
+        server("New", "play.example.net:25566", None),
+        server("New Again", "play.example.net:25566", Some("bmV3")),
+      ])
+      .unwrap();
+
+    assert_eq!(
+      servers_dat.entries(),
+      [
+        server("Renamed", "mc.example.com", Some("aWNvbg==")),
+        server("New Again", "play.example.net:25566", Some("bmV3")),
+      ]
+    );
+  }
+
+  const STATUS_JSON: &str = r#"{
+    "version": {"name": "1.21.4", "protocol": 769},
+    "players": {"max": 20, "online": 3},
+    "description": {"text": "\u00a7aHello", "extra": [{"text": " world"}]}
+  }"#;
+
+  // Stand-in server answering a modern status request, the pong is sent only if `answer_ping`.
+  async fn serve_status(answer_ping: bool) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+      let (mut stream, _) = listener.accept().await.unwrap();
+      let mut handshake = read_packet(&mut stream).await.unwrap();
+      assert_eq!(read_varint(&mut handshake).unwrap(), 0x00);
+      assert_eq!(
+        read_varint(&mut handshake).unwrap(),
+        STATUS_PROTOCOL_VERSION
+      );
+      assert_eq!(read_string(&mut handshake).unwrap(), "127.0.0.1");
+
+      let mut request = read_packet(&mut stream).await.unwrap();
+      assert_eq!(read_varint(&mut request).unwrap(), 0x00);
+      let mut response = Vec::new();
+      write_varint(&mut response, 0x00);
+      write_string(&mut response, STATUS_JSON);
+      send_packet(&mut stream, &response).await.unwrap();
+
+      let ping = read_packet(&mut stream).await.unwrap().into_inner();
+      assert_eq!(ping[0], 0x01);
+      if answer_ping {
+        send_packet(&mut stream, &ping).await.unwrap();
+      }
+    });
+    port
+  }
+
+  #[tokio::test]
+  async fn query_status_from_local_server() {
+    let port = serve_status(true).await;
+    let result = query_server_status(&format!("127.0.0.1:{}", port))
+      .await
+      .unwrap();
+
+    assert!(result.online);
+    assert_eq!(result.players.online, 3);
+    assert_eq!(result.players.max, 20);
+    assert_eq!(result.description, "Hello world");
+    assert_eq!(result.version.as_deref(), Some("1.21.4"));
+    assert_eq!(result.protocol, Some(769));
+    assert!(result.latency.is_some());
+  }
+
+  #[tokio::test]
+  async fn query_status_without_pong() {
+    let port = serve_status(false).await;
+    let result = query_server_status(&format!("127.0.0.1:{}", port))
+      .await
+      .unwrap();
+
+    assert!(result.online);
+    assert_eq!(result.latency, None);
   }
-  let query_result: SjmcServerQueryResult = response.json().await?;
-  Ok(query_result)
 }
@@ -1,8 +1,19 @@
 use crate::error::{LXMCLError, LXMCLResult};
-use crate::instance::models::world::level::{Level, LevelData};
-use quartz_nbt::io::Flavor;
+use crate::instance::models::misc::InstanceError;
+use crate::instance::models::world::level::{Level, LevelData, LevelDataPatch};
+use quartz_nbt::io::{read_nbt, write_nbt, Flavor};
 use quartz_nbt::serde::deserialize;
-use std::path::PathBuf;
+use quartz_nbt::{NbtCompound, NbtTag};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+// world coordinates are limited to +-30,000,000 blocks, and the world border to +-29,999,984
+const MAX_WORLD_COORDINATE: i32 = 30_000_000;
+const MAX_BORDER_CENTER: f64 = 29_999_984.0;
+const MAX_BORDER_SIZE: f64 = 59_999_968.0;
+// build height limits of data packs (1.18+)
+const MIN_SPAWN_Y: i32 = -2032;
+const MAX_SPAWN_Y: i32 = 2031;
 
 pub async fn load_level_data_from_path(path: &PathBuf) -> LXMCLResult<LevelData> {
   let nbt_bytes = tokio::fs::read(path).await?;
@@ -43,3 +54,252 @@ pub fn level_data_to_world_info(data: &LevelData) -> LXMCLResult<(i64, String, S
     GAMEMODE_STR[gametype as usize].to_string(),
   ))
 }
+
+fn is_valid_game_rule(name: &str, value: &str, current: Option<&str>) -> bool {
+  let is_bool = |v: &str| v == "true" || v == "false";
+  let is_int = |v: &str| v.parse::<i32>().is_ok();
+  if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+    return false;
+  }
+  match current {
+    // keep the type of an existing rule
+    Some(current) if is_bool(current) => is_bool(value),
+    Some(current) if is_int(current) => is_int(value),
+    _ => is_bool(value) || is_int(value),
+  }
+}
+
+// returns the name (as in the patch) of the first invalid field, if any.
+fn find_invalid_level_data_field(data: &NbtCompound, patch: &LevelDataPatch) -> Option<String> {
+  let game_rules = data.get::<_, &NbtCompound>("GameRules").ok();
+  if let Some((name, _)) = patch.game_rules.iter().find(|(name, value)| {
+    let current = game_rules.and_then(|rules| rules.get::<_, &str>(name.as_str()).ok());
+    !is_valid_game_rule(name, value, current)
+  }) {
+    return Some(format!("gameRules.{name}"));
+  }
+
+  if let Some(border) = &patch.world_border {
+    let border_fields = [
+      (
+        "centerX",
+        border.center_x,
+        -MAX_BORDER_CENTER,
+        MAX_BORDER_CENTER,
+      ),
+      (
+        "centerZ",
+        border.center_z,
+        -MAX_BORDER_CENTER,
+        MAX_BORDER_CENTER,
+      ),
+      ("size", border.size, 1.0, MAX_BORDER_SIZE),
+      ("damagePerBlock", border.damage_per_block, 0.0, f64::MAX),
+      ("safeZone", border.safe_zone, 0.0, f64::MAX),
+      ("warningBlocks", border.warning_blocks, 0.0, f64::MAX),
+      ("warningTime", border.warning_time, 0.0, f64::MAX),
+    ];
+    if let Some((name, ..)) = border_fields.iter().find(|(_, value, min, max)| {
+      value.is_some_and(|v| !(v.is_finite() && v >= *min && v <= *max))
+    }) {
+      return Some(format!("worldBorder.{name}"));
+    }
+  }
+
+  if let Some(spawn) = &patch.spawn_point {
+    let spawn_fields = [
+      ("x", spawn.x.abs() <= MAX_WORLD_COORDINATE),
+      ("y", (MIN_SPAWN_Y..=MAX_SPAWN_Y).contains(&spawn.y)),
+      ("z", spawn.z.abs() <= MAX_WORLD_COORDINATE),
+    ];
+    if let Some((name, _)) = spawn_fields.iter().find(|(_, valid)| !valid) {
+      return Some(format!("spawnPoint.{name}"));
+    }
+  }
+
+  let fields = [
+    (
+      "difficulty",
+      patch.difficulty.map_or(true, |difficulty| difficulty <= 3),
+    ),
+    ("dayTime", patch.day_time.map_or(true, |time| time >= 0)),
+    (
+      "clearWeatherTime",
+      patch.clear_weather_time.map_or(true, |time| time >= 0),
+    ),
+    ("rainTime", patch.rain_time.map_or(true, |time| time >= 0)),
+    (
+      "thunderTime",
+      patch.thunder_time.map_or(true, |time| time >= 0),
+    ),
+  ];
+  fields
+    .iter()
+    .find(|(_, valid)| !valid)
+    .map(|(name, _)| name.to_string())
+}
+
+// only the tags in the patch are touched, all other (including unknown) tags are kept as is.
+fn apply_level_data_patch(data: &mut NbtCompound, patch: &LevelDataPatch) {
+  fn set<T: Into<NbtTag>>(data: &mut NbtCompound, name: &str, value: Option<T>) {
+    if let Some(value) = value {
+      data.insert(name, value);
+    }
+  }
+
+  if !patch.game_rules.is_empty() {
+    if !data.contains_key("GameRules") {
+      data.insert("GameRules", NbtCompound::new());
+    }
+    if let Ok(game_rules) = data.get_mut::<_, &mut NbtCompound>("GameRules") {
+      for (name, value) in &patch.game_rules {
+        game_rules.insert(name.as_str(), value.as_str());
+      }
+    }
+  }
+  set(data, "Difficulty", patch.difficulty.map(|v| v as i8));
+  set(data, "DifficultyLocked", patch.difficulty_locked);
+  set(data, "hardcore", patch.hardcore);
+  set(data, "allowCommands", patch.allow_commands);
+  set(data, "DayTime", patch.day_time);
+  set(data, "clearWeatherTime", patch.clear_weather_time);
+  set(data, "raining", patch.raining);
+  set(data, "rainTime", patch.rain_time);
+  set(data, "thundering", patch.thundering);
+  set(data, "thunderTime", patch.thunder_time);
+
+  if let Some(border) = &patch.world_border {
+    set(data, "BorderCenterX", border.center_x);
+    set(data, "BorderCenterZ", border.center_z);
+    if let Some(size) = border.size {
+      // stop any ongoing border resizing
+      data.insert("BorderSize", size);
+      data.insert("BorderSizeLerpTarget", size);
+      data.insert("BorderSizeLerpTime", 0i64);
+    }
+    set(data, "BorderDamagePerBlock", border.damage_per_block);
+    set(data, "BorderSafeZone", border.safe_zone);
+    set(data, "BorderWarningBlocks", border.warning_blocks);
+    set(data, "BorderWarningTime", border.warning_time);
+  }
+
+  if let Some(spawn) = &patch.spawn_point {
+    data.insert("SpawnX", spawn.x);
+    data.insert("SpawnY", spawn.y);
+    data.insert("SpawnZ", spawn.z);
+  }
+}
+
+// apply the patch to the gzipped NBT of level.dat, returns the new file content.
+fn patch_level_data_bytes(nbt_bytes: Vec<u8>, patch: &LevelDataPatch) -> LXMCLResult<Vec<u8>> {
+  let (mut root, root_name) = read_nbt(&mut Cursor::new(nbt_bytes), Flavor::GzCompressed)
+    .map_err(|_| InstanceError::LevelParseError)?;
+  let data = root
+    .get_mut::<_, &mut NbtCompound>("Data")
+    .map_err(|_| InstanceError::LevelParseError)?;
+  if let Some(field) = find_invalid_level_data_field(data, patch) {
+    return Err(LXMCLError(format!(
+      "{} ({})",
+      InstanceError::LevelPatchInvalidError,
+      field
+    )));
+  }
+  apply_level_data_patch(data, patch);
+
+  let mut new_bytes = Vec::new();
+  write_nbt(
+    &mut new_bytes,
+    Some(&root_name),
+    &root,
+    Flavor::GzCompressed,
+  )?;
+  Ok(new_bytes)
+}
+
+pub async fn save_level_data_patch(path: &Path, patch: &LevelDataPatch) -> LXMCLResult<()> {
+  let nbt_bytes = tokio::fs::read(path).await?;
+  let new_bytes = patch_level_data_bytes(nbt_bytes, patch)?;
+  // same as the game: keep the previous file as level.dat_old, and replace level.dat at once
+  tokio::fs::copy(path, path.with_file_name("level.dat_old")).await?;
+  let new_path = path.with_file_name("level.dat_new");
+  tokio::fs::write(&new_path, new_bytes).await?;
+  tokio::fs::rename(&new_path, path).await?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::instance::models::world::level::SpawnPointPatch;
+  use std::collections::HashMap;
+
+  fn level_data_bytes(data: NbtCompound) -> Vec<u8> {
+    let mut root = NbtCompound::new();
+    root.insert("Data", data);
+    let mut bytes = Vec::new();
+    write_nbt(&mut bytes, Some(""), &root, Flavor::GzCompressed).unwrap();
+    bytes
+  }
+
+  fn read_data(bytes: Vec<u8>) -> NbtCompound {
+    let (root, _) = read_nbt(&mut Cursor::new(bytes), Flavor::GzCompressed).unwrap();
+    root.get::<_, &NbtCompound>("Data").unwrap().clone()
+  }
+
+  #[test]
+  fn patch_keeps_unknown_tags() {
+    let mut forge_data = NbtCompound::new();
+    forge_data.insert("modCount", 42i32);
+    let mut data = NbtCompound::new();
+    data.insert("Difficulty", 1i8);
+    data.insert("DayTime", 6000i64);
+    data.insert("FML", forge_data);
+    data.insert("CustomTag", "kept");
+
+    let patch = LevelDataPatch {
+      difficulty: Some(3),
+      game_rules: HashMap::from([("doDaylightCycle".to_string(), "false".to_string())]),
+      ..Default::default()
+    };
+    let data = read_data(patch_level_data_bytes(level_data_bytes(data), &patch).unwrap());
+
+    assert_eq!(data.get::<_, i8>("Difficulty").unwrap(), 3);
+    assert_eq!(data.get::<_, i64>("DayTime").unwrap(), 6000);
+    assert_eq!(data.get::<_, &str>("CustomTag").unwrap(), "kept");
+    let forge_data = data.get::<_, &NbtCompound>("FML").unwrap();
+    assert_eq!(forge_data.get::<_, i32>("modCount").unwrap(), 42);
+    let game_rules = data.get::<_, &NbtCompound>("GameRules").unwrap();
+    assert_eq!(
+      game_rules.get::<_, &str>("doDaylightCycle").unwrap(),
+      "false"
+    );
+  }
+
+  #[test]
+  fn invalid_patch_reports_the_field() {
+    let patch = LevelDataPatch {
+      spawn_point: Some(SpawnPointPatch {
+        x: 0,
+        y: 5000,
+        z: 0,
+      }),
+      ..Default::default()
+    };
+    let error = patch_level_data_bytes(level_data_bytes(NbtCompound::new()), &patch).unwrap_err();
+    assert_eq!(error.0, "LEVEL_PATCH_INVALID_ERROR (spawnPoint.y)");
+
+    let mut game_rules = NbtCompound::new();
+    game_rules.insert("randomTickSpeed", "3");
+    let mut data = NbtCompound::new();
+    data.insert("GameRules", game_rules);
+    let patch = LevelDataPatch {
+      game_rules: HashMap::from([("randomTickSpeed".to_string(), "true".to_string())]),
+      ..Default::default()
+    };
+    let error = patch_level_data_bytes(level_data_bytes(data), &patch).unwrap_err();
+    assert_eq!(
+      error.0,
+      "LEVEL_PATCH_INVALID_ERROR (gameRules.randomTickSpeed)"
+    );
+  }
+}
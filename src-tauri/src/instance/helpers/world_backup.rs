@@ -0,0 +1,310 @@
+use crate::error::LXMCLResult;
+use crate::instance::helpers::misc::{get_instance_game_config, get_instance_subdir_paths};
+use crate::instance::models::misc::{Instance, InstanceError, InstanceSubdirType};
+use crate::instance::models::world::base::WorldBackupInfo;
+use crate::instance::models::world::level::Level;
+use crate::utils::fs::{add_path_to_zip, get_subdirectories};
+use chrono::Local;
+use quartz_nbt::io::Flavor;
+use quartz_nbt::serde::deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use zip::write::{ExtendedFileOptions, FileOptions};
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+// the lock file is held by the game while the world is open, it is never backed up or restored.
+const SESSION_LOCK_FILE_NAME: &str = "session.lock";
+const BACKUP_FILE_TIME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+// Snapshots are stored like the vanilla "Backup" function does, as zip files in `backups/`,
+// with all files under a top level directory named after the world.
+fn get_world_backup_dir(backups_dir: &Path, world_name: &str) -> PathBuf {
+  backups_dir.join(world_name)
+}
+
+fn to_unix_time(time: SystemTime) -> i64 {
+  time
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs() as i64)
+    .unwrap_or_default()
+}
+
+pub fn write_world_backup(world_dir: &Path, backups_dir: &Path) -> LXMCLResult<WorldBackupInfo> {
+  let world_name = world_dir
+    .file_name()
+    .ok_or(InstanceError::WorldNotExistError)?
+    .to_string_lossy()
+    .to_string();
+  if !world_dir.join("level.dat").is_file() {
+    return Err(InstanceError::LevelNotExistError.into());
+  }
+
+  let backup_dir = get_world_backup_dir(backups_dir, &world_name);
+  fs::create_dir_all(&backup_dir).map_err(|_| InstanceError::FolderCreationFailed)?;
+  let time_str = Local::now().format(BACKUP_FILE_TIME_FORMAT).to_string();
+  let mut file_path = backup_dir.join(format!("{time_str}.zip"));
+  let mut counter = 1;
+  while file_path.exists() {
+    counter += 1;
+    file_path = backup_dir.join(format!("{time_str}_{counter}.zip"));
+  }
+
+  // write to a temporary file, so an interrupted backup is never listed
+  let tmp_path = file_path.with_extension("zip.tmp");
+  let write_result = (|| -> LXMCLResult<()> {
+    let mut zip = ZipWriter::new(File::create(&tmp_path)?);
+    let options =
+      FileOptions::<ExtendedFileOptions>::default().compression_method(CompressionMethod::Deflated);
+    for entry in fs::read_dir(world_dir)? {
+      let entry = entry?;
+      if entry.file_name() == SESSION_LOCK_FILE_NAME {
+        continue;
+      }
+      add_path_to_zip(
+        &mut zip,
+        &entry.path(),
+        &Path::new(&world_name).join(entry.file_name()),
+        &options,
+      )?;
+    }
+    zip.finish()?;
+    Ok(())
+  })();
+  if let Err(e) = write_result {
+    let _ = fs::remove_file(&tmp_path);
+    return Err(e);
+  }
+  fs::rename(&tmp_path, &file_path)?;
+
+  load_world_backup_info(&file_path, &world_name)
+}
+
+fn load_world_backup_info(file_path: &Path, world_name: &str) -> LXMCLResult<WorldBackupInfo> {
+  let metadata = fs::metadata(file_path)?;
+  let file_name = file_path
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_default();
+  let created_at = chrono::NaiveDateTime::parse_from_str(
+    file_name
+      .trim_end_matches(".zip")
+      .get(..19)
+      .unwrap_or_default(),
+    BACKUP_FILE_TIME_FORMAT,
+  )
+  .ok()
+  .and_then(|time| time.and_local_timezone(Local).single())
+  .map(|time| time.timestamp())
+  .unwrap_or(to_unix_time(metadata.modified()?));
+
+  // read level.dat in the snapshot, to tell which play session it was taken after
+  let mut archive = ZipArchive::new(File::open(file_path)?)?;
+  let last_played_at = {
+    let mut nbt_bytes = Vec::new();
+    match archive.by_name(&format!("{world_name}/level.dat")) {
+      Ok(mut level_file) => {
+        level_file.read_to_end(&mut nbt_bytes)?;
+        deserialize::<Level>(&nbt_bytes, Flavor::GzCompressed)
+          .map(|(level, _)| level.data.last_played / 1000)
+          .unwrap_or_default()
+      }
+      Err(_) => 0,
+    }
+  };
+
+  Ok(WorldBackupInfo {
+    file_name,
+    world_name: world_name.to_string(),
+    created_at,
+    last_played_at,
+    size: metadata.len(),
+    file_path: file_path.to_path_buf(),
+  })
+}
+
+// whether the world is open in a running game, which holds the lock of `session.lock`
+// (FileChannel.tryLock in java).
+pub fn is_world_in_use(world_dir: &Path) -> bool {
+  match File::open(world_dir.join(SESSION_LOCK_FILE_NAME)) {
+    Ok(file) => is_file_locked(&file),
+    Err(_) => false,
+  }
+}
+
+#[cfg(unix)]
+fn is_file_locked(file: &File) -> bool {
+  use std::os::unix::io::AsRawFd;
+
+  // l_start = 0 and l_len = 0 ask for any lock on the whole file
+  let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+  lock.l_type = libc::F_WRLCK as libc::c_short;
+  lock.l_whence = libc::SEEK_SET as libc::c_short;
+  let result = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETLK, &mut lock) };
+  result == 0 && lock.l_type != libc::F_UNLCK as libc::c_short
+}
+
+#[cfg(windows)]
+fn is_file_locked(file: &File) -> bool {
+  // the locked range cannot be read by other processes
+  let mut reader = file;
+  reader.read(&mut [0u8; 1]).is_err()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_file_locked(_file: &File) -> bool {
+  false
+}
+
+// list the snapshots of a world, the newest first.
+pub fn list_world_backups(
+  backups_dir: &Path,
+  world_name: &str,
+) -> LXMCLResult<Vec<WorldBackupInfo>> {
+  let backup_dir = get_world_backup_dir(backups_dir, world_name);
+  let Ok(entries) = fs::read_dir(&backup_dir) else {
+    return Ok(Vec::new());
+  };
+  let mut backups = entries
+    .filter_map(Result::ok)
+    .map(|entry| entry.path())
+    .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "zip"))
+    .filter_map(|path| load_world_backup_info(&path, world_name).ok())
+    .collect::<Vec<_>>();
+  backups.sort_by(|a, b| {
+    b.created_at
+      .cmp(&a.created_at)
+      .then(b.file_name.cmp(&a.file_name))
+  });
+  Ok(backups)
+}
+
+// extract a snapshot into `target_dir` (a directory of the saves dir), replacing it if it exists.
+pub fn extract_world_backup(
+  backup_path: &Path,
+  world_name: &str,
+  target_dir: &Path,
+) -> LXMCLResult<()> {
+  let (Some(saves_dir), Some(target_name)) = (target_dir.parent(), target_dir.file_name()) else {
+    return Err(InstanceError::InvalidNameError.into());
+  };
+  // extract beside the target first, the target is left untouched if anything fails
+  let tmp_dir = saves_dir.join(format!(".{}.restoring", target_name.to_string_lossy()));
+  if tmp_dir.exists() {
+    fs::remove_dir_all(&tmp_dir)?;
+  }
+
+  let extract_result = (|| -> LXMCLResult<()> {
+    let mut archive = ZipArchive::new(File::open(backup_path)?)?;
+    for i in 0..archive.len() {
+      let mut file = archive.by_index(i)?;
+      let Some(path) = file.enclosed_name() else {
+        continue;
+      };
+      let Ok(relative_path) = path.strip_prefix(world_name) else {
+        continue;
+      };
+      if relative_path == Path::new(SESSION_LOCK_FILE_NAME) {
+        continue;
+      }
+      let outpath = tmp_dir.join(relative_path);
+      if file.is_dir() {
+        fs::create_dir_all(&outpath)?;
+      } else {
+        if let Some(parent) = outpath.parent() {
+          fs::create_dir_all(parent)?;
+        }
+        let mut outfile = File::create(&outpath)?;
+        std::io::copy(&mut file, &mut outfile)?;
+      }
+    }
+    if !tmp_dir.join("level.dat").is_file() {
+      return Err(InstanceError::LevelNotExistError.into());
+    }
+    Ok(())
+  })();
+  if let Err(e) = extract_result {
+    let _ = fs::remove_dir_all(&tmp_dir);
+    return Err(e);
+  }
+
+  if target_dir.exists() {
+    fs::remove_dir_all(target_dir).map_err(|_| InstanceError::FileMoveFailed)?;
+  }
+  fs::rename(&tmp_dir, target_dir).map_err(|_| InstanceError::FileMoveFailed)?;
+  Ok(())
+}
+
+// remove the snapshots beyond the newest `max_count`, or older than `max_age_days` (0 to ignore).
+// returns the removed snapshots.
+pub fn remove_expired_world_backups(
+  backups_dir: &Path,
+  world_name: &str,
+  max_count: usize,
+  max_age_days: u32,
+) -> LXMCLResult<Vec<WorldBackupInfo>> {
+  let min_created_at = SystemTime::now()
+    .checked_sub(Duration::from_secs(max_age_days as u64 * 24 * 60 * 60))
+    .map(to_unix_time)
+    .unwrap_or(i64::MIN);
+  let mut removed = Vec::new();
+  for (index, backup) in list_world_backups(backups_dir, world_name)?
+    .into_iter()
+    .enumerate()
+  {
+    let exceeds_count = max_count > 0 && index >= max_count;
+    let exceeds_age = max_age_days > 0 && backup.created_at < min_created_at;
+    if (exceeds_count || exceeds_age) && fs::remove_file(&backup.file_path).is_ok() {
+      removed.push(backup);
+    }
+  }
+  Ok(removed)
+}
+
+// take snapshots of the worlds played since `since`, if enabled in the instance's game config.
+pub fn backup_played_worlds(app: &AppHandle, instance_id: &str, since: SystemTime) {
+  let (world_backup_config, dirs) = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let Ok(state) = binding.lock() else {
+      return;
+    };
+    let Some(instance) = state.get(instance_id) else {
+      return;
+    };
+    (
+      get_instance_game_config(app, instance).world_backup,
+      get_instance_subdir_paths(
+        app,
+        instance,
+        &[&InstanceSubdirType::Saves, &InstanceSubdirType::Backups],
+      ),
+    )
+  };
+  if !world_backup_config.auto_backup_on_exit {
+    return;
+  }
+  let Some([saves_dir, backups_dir]) = dirs.as_deref() else {
+    return;
+  };
+
+  for world_dir in get_subdirectories(saves_dir).unwrap_or_default() {
+    let is_played = fs::metadata(world_dir.join("level.dat"))
+      .and_then(|metadata| metadata.modified())
+      .is_ok_and(|modified| modified >= since);
+    if !is_played {
+      continue;
+    }
+    if let Ok(backup) = write_world_backup(&world_dir, backups_dir) {
+      let _ = remove_expired_world_backups(
+        backups_dir,
+        &backup.world_name,
+        world_backup_config.max_count,
+        world_backup_config.max_age_days,
+      );
+    }
+  }
+}
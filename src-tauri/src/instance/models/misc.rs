@@ -1,9 +1,13 @@
-use crate::instance::constants::INSTANCE_CFG_FILE_NAME;
+use crate::account::models::PlayerType;
+use crate::instance::constants::{INSTANCE_CFG_FILE_NAME, OPTIONS_TEMPLATES_FILE_NAME};
 use crate::launcher_config::models::GameConfig;
-use crate::storage::{load_json_async, save_json_async};
+use crate::resource::models::OtherResourceInfo;
+use crate::storage::{load_json_async, save_json_async, Storage};
 use crate::utils::image::ImageWrapper;
+use crate::APP_DATA_DIR;
 use serde::{Deserialize, Serialize};
 use std::cmp::{Ord, Ordering, PartialOrd};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::str::FromStr;
 use strum_macros::Display;
@@ -11,6 +15,7 @@ use strum_macros::Display;
 #[derive(Debug, Deserialize, Serialize)]
 pub enum InstanceSubdirType {
   Assets,
+  Backups,
   Libraries,
   Mods,
   NativeLibraries,
@@ -77,6 +82,14 @@ pub enum ModLoaderStatus {
   Installed,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields, default)]
+pub struct OptiFine {
+  pub version: String, // e.g. HD_U_I6
+  pub filename: String,
+  pub status: ModLoaderStatus, // only the standalone patch goes through NotDownloaded and Installing
+}
+
 structstruck::strike! {
   #[strikethrough[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]]
   #[strikethrough[serde(rename_all = "camelCase", deny_unknown_fields, default)]]
@@ -95,13 +108,27 @@ structstruck::strike! {
       pub version: String,
       pub branch: Option<String>, // Optional branch name for mod loaders like Forge
     },
+    // standalone OptiFine patch, or OptiFine jar in a Forge instance's mods directory
+    pub optifine: Option<OptiFine>,
     // if true, use the spec_game_config, else use the global game config
     pub use_spec_game_config: bool,
     // if use_spec_game_config is false, this field is ignored
     pub spec_game_config: Option<GameConfig>,
+    // if None, launch with the globally selected player
+    pub player_binding: Option<PlayerBinding>,
   }
 }
 
+// The player to launch an instance with: a pinned player, or the selected player if it meets the
+// required type (and auth server), otherwise the first player that does.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields, default)]
+pub struct PlayerBinding {
+  pub player_id: Option<String>,
+  pub player_type: Option<PlayerType>,
+  pub auth_server_url: Option<String>, // only for 3rd-party players
+}
+
 impl Instance {
   pub fn get_json_cfg_path(&self) -> PathBuf {
     self.version_path.join(INSTANCE_CFG_FILE_NAME)
@@ -132,9 +159,11 @@ pub struct InstanceSummary {
   pub version: String,
   pub major_version: String,
   pub mod_loader: ModLoader,
+  pub optifine: Option<OptiFine>,
   pub support_quick_play: bool,
   pub use_spec_game_config: bool,
   pub is_version_isolated: bool,
+  pub player_binding: Option<PlayerBinding>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
@@ -148,6 +177,21 @@ pub struct GameServerInfo {
   pub players_online: usize,
   pub players_max: usize,
   pub online: bool, // if false, it may be offline in the query result or failed in the query.
+  pub latency: Option<u64>, // in milliseconds, only available if the server answered the ping
+  pub accept_textures: Option<bool>, // server resource packs, None for "prompt"
+}
+
+// a server entry in servers.dat, as edited in the launcher or imported from a JSON / CSV list.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GameServerEntry {
+  #[serde(default)]
+  pub name: String,
+  pub ip: String,
+  #[serde(default)]
+  pub icon: Option<String>, // base64 PNG
+  #[serde(default)]
+  pub accept_textures: Option<bool>, // None for "prompt"
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -220,11 +264,77 @@ pub struct ScreenshotInfo {
   pub time: u64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+pub enum ModDependencyIssueType {
+  #[default]
+  MissingDependency,
+  VersionMismatch,
+  DuplicateMod,
+  Conflict,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ModDependencyIssue {
+  pub issue_type: ModDependencyIssueType,
+  pub mod_id: String,
+  pub mod_name: String,
+  pub file_names: Vec<String>, // files of the mod(s) raising the issue
+  pub target_mod_id: Option<String>, // the dependency or the conflicting mod
+  pub required_version: Option<String>,
+  pub found_version: Option<String>,
+  // only for missing dependencies, a remote mod found by id on Modrinth or CurseForge
+  pub remote_resource: Option<OtherResourceInfo>,
+}
+
+// common settings in options.txt, `None` (or absent key bindings) for unset / unchanged.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameOptions {
+  pub language: Option<String>,
+  pub render_distance: Option<u32>,
+  pub gui_scale: Option<u32>,
+  // binding name (e.g. "key.jump") -> key name (e.g. "key.keyboard.space")
+  pub key_bindings: HashMap<String, String>,
+  pub resource_packs: Option<Vec<String>>,
+}
+
+// key bindings and video settings captured from an instance, to be applied to others.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OptionsTemplate {
+  pub name: String,
+  pub source_version: String,
+  pub created_at: i64,
+  // key bindings are always stored in the key name format (1.13+)
+  pub key_bindings: BTreeMap<String, String>,
+  pub video_settings: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OptionsTemplateList {
+  pub templates: Vec<OptionsTemplate>,
+}
+
+impl Storage for OptionsTemplateList {
+  fn file_path() -> PathBuf {
+    APP_DATA_DIR
+      .get()
+      .unwrap()
+      .join(OPTIONS_TEMPLATES_FILE_NAME)
+  }
+}
+
 #[derive(Debug, Display)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum InstanceError {
   InstanceNotFoundByID,
   ServerNbtReadError,
+  ServerNbtWriteError,
+  ServerNotFoundError,
+  ServerListParseError,
+  InvalidServerAddress,
   FileNotFoundError,
   InvalidSourcePath,
   FileCreationFailed,
@@ -236,6 +346,8 @@ pub enum InstanceError {
   WorldNotExistError,
   LevelParseError,
   LevelNotExistError,
+  LevelPatchInvalidError,
+  WorldInUseError,
   ConflictNameError,
   InvalidNameError,
   ClientJsonParseError,
@@ -11,3 +11,15 @@ pub struct WorldInfo {
   pub icon_src: PathBuf,
   pub dir_path: PathBuf,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct WorldBackupInfo {
+  pub file_name: String,
+  pub world_name: String,
+  pub created_at: i64,
+  // `LevelData.last_played` (in seconds) when the snapshot was taken
+  pub last_played_at: i64,
+  pub size: u64,
+  pub file_path: PathBuf,
+}
@@ -66,6 +66,45 @@ pub struct LevelData {
   pub was_modded: u8,
 }
 
+// Fields to be changed in level.dat, `None` (or an empty map) for unchanged.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LevelDataPatch {
+  pub game_rules: HashMap<String, String>,
+  pub difficulty: Option<u8>,
+  pub difficulty_locked: Option<bool>,
+  pub hardcore: Option<bool>,
+  pub allow_commands: Option<bool>,
+  pub day_time: Option<i64>,
+  pub clear_weather_time: Option<i32>,
+  pub raining: Option<bool>,
+  pub rain_time: Option<i32>,
+  pub thundering: Option<bool>,
+  pub thunder_time: Option<i32>,
+  pub world_border: Option<WorldBorderPatch>,
+  pub spawn_point: Option<SpawnPointPatch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WorldBorderPatch {
+  pub center_x: Option<f64>,
+  pub center_z: Option<f64>,
+  pub size: Option<f64>,
+  pub damage_per_block: Option<f64>,
+  pub safe_zone: Option<f64>,
+  pub warning_blocks: Option<f64>,
+  pub warning_time: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnPointPatch {
+  pub x: i32,
+  pub y: i32,
+  pub z: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct Version {
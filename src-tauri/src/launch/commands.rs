@@ -1,23 +1,31 @@
-use crate::account::helpers::misc::get_selected_player_info;
+use crate::account::helpers::misc::get_instance_player_info;
 use crate::account::helpers::{authlib_injector, microsoft};
 use crate::account::models::PlayerType;
 use crate::error::LXMCLResult;
+use crate::instance::helpers::asset_index::{load_asset_index, materialize_legacy_assets};
 use crate::instance::helpers::client_json::{replace_native_libraries, McClientInfo};
 use crate::instance::helpers::misc::{get_instance_game_config, get_instance_subdir_paths};
 use crate::instance::models::misc::{Instance, InstanceError, InstanceSubdirType, ModLoaderStatus};
 use crate::launch::helpers::command_generator::{
   export_full_launch_command, generate_launch_command, LaunchCommand,
 };
+use crate::launch::helpers::crash_analyzer::{analyze_crash_log_files, collect_crash_log_files};
 use crate::launch::helpers::file_validator::{
   extract_native_libraries, get_invalid_assets, get_invalid_library_files,
 };
 use crate::launch::helpers::jre_selector::select_java_runtime;
-use crate::launch::helpers::misc::get_separator;
+use crate::launch::helpers::misc::{
+  get_custom_command_variables, get_separator, parse_environment_variables,
+  replace_custom_variables,
+};
 use crate::launch::helpers::process_monitor::{
   kill_process, monitor_process, set_process_priority,
 };
-use crate::launch::models::{LaunchError, LaunchingState};
+use crate::launch::models::{
+  CrashAnalysisResult, LaunchError, LaunchingState, PlayerValidationResult,
+};
 use crate::launcher_config::helpers::java::refresh_and_update_javas;
+use crate::launcher_config::helpers::shared_store::get_shared_store_dir;
 use crate::launcher_config::models::{
   FileValidatePolicy, JavaInfo, LauncherConfig, LauncherVisiablity,
 };
@@ -26,13 +34,15 @@ use crate::storage::load_json_async;
 use crate::tasks::commands::schedule_progressive_task_group;
 use crate::utils::fs::create_zip_from_dirs;
 use crate::utils::logging::get_launcher_log_path;
-use crate::utils::shell::{execute_command_line, split_command_line};
+use crate::utils::shell::{build_command_line, quote_command_line_arg, split_command_line};
 use crate::utils::window::create_webview_window;
+use crate::IS_HEADLESS;
+use shlex::try_quote;
 use std::collections::HashMap;
 use std::fs;
 use std::io::prelude::*;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{mpsc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -96,6 +106,18 @@ pub async fn validate_game_files(
   launcher_config_state: State<'_, Mutex<LauncherConfig>>,
   launching_queue_state: State<'_, Mutex<Vec<LaunchingState>>>,
 ) -> LXMCLResult<()> {
+  match check_game_files(&app, &launcher_config_state, &launching_queue_state).await? {
+    Some(_) => Err(LaunchError::GameFilesIncomplete.into()),
+    None => Ok(()),
+  }
+}
+
+// returns the task group scheduled to download the incomplete files, None if all files are valid.
+pub async fn check_game_files(
+  app: &AppHandle,
+  launcher_config_state: &Mutex<LauncherConfig>,
+  launching_queue_state: &Mutex<Vec<LaunchingState>>,
+) -> LXMCLResult<Option<String>> {
   let (instance, mut client_info, validate_policy) = {
     let mut launching_queue = launching_queue_state.lock()?;
     let launching = launching_queue
@@ -114,11 +136,16 @@ pub async fn validate_game_files(
     )
   };
 
-  if instance.mod_loader.status != ModLoaderStatus::Installed {
+  if instance.mod_loader.status != ModLoaderStatus::Installed
+    || instance
+      .optifine
+      .as_ref()
+      .is_some_and(|optifine| optifine.status != ModLoaderStatus::Installed)
+  {
     return Err(LaunchError::ModLoaderNotInstalled.into());
   }
 
-  replace_native_libraries(&app, &mut client_info, &instance)
+  replace_native_libraries(app, &mut client_info, &instance)
     .await
     .map_err(|_| InstanceError::ClientJsonParseError)?;
 
@@ -133,16 +160,17 @@ pub async fn validate_game_files(
 
   // extract native libraries
   let dirs = get_instance_subdir_paths(
-    &app,
+    app,
     &instance,
     &[
+      &InstanceSubdirType::Root,
       &InstanceSubdirType::Libraries,
       &InstanceSubdirType::NativeLibraries,
       &InstanceSubdirType::Assets,
     ],
   )
   .ok_or(InstanceError::InstanceNotFoundByID)?;
-  let [libraries_dir, natives_dir, assets_dir] = dirs.as_slice() else {
+  let [root_dir, libraries_dir, natives_dir, assets_dir] = dirs.as_slice() else {
     return Err(InstanceError::InstanceNotFoundByID.into());
   };
   extract_native_libraries(&client_info, libraries_dir, natives_dir).await?;
@@ -151,43 +179,87 @@ pub async fn validate_game_files(
     let launcher_config = launcher_config_state.lock()?;
     get_source_priority_list(&launcher_config)
   };
+  let shared_store_dir = get_shared_store_dir(app);
+  let shared_store_dir = shared_store_dir.as_deref();
 
   // validate game files
-  let incomplete_files = match validate_policy {
-    FileValidatePolicy::Disable => return Ok(()), // skip
-    FileValidatePolicy::Normal => [
-      get_invalid_library_files(priority_list[0], libraries_dir, &client_info, false).await?,
-      get_invalid_assets(&app, &client_info, priority_list[0], assets_dir, false).await?,
-    ]
-    .concat(),
-    FileValidatePolicy::Full => [
-      get_invalid_library_files(priority_list[0], libraries_dir, &client_info, true).await?,
-      get_invalid_assets(&app, &client_info, priority_list[0], assets_dir, true).await?,
-    ]
-    .concat(),
+  let check_hash = match validate_policy {
+    FileValidatePolicy::Disable => {
+      // skip, but legacy versions still need the assets laid out by their names
+      lay_out_legacy_assets(app, &client_info, assets_dir, root_dir).await?;
+      return Ok(None);
+    }
+    FileValidatePolicy::Normal => false,
+    FileValidatePolicy::Full => true,
   };
+  let incomplete_files = [
+    get_invalid_library_files(
+      priority_list[0],
+      libraries_dir,
+      &client_info,
+      check_hash,
+      shared_store_dir,
+    )
+    .await?,
+    get_invalid_assets(
+      app,
+      &client_info,
+      priority_list[0],
+      assets_dir,
+      check_hash,
+      shared_store_dir,
+    )
+    .await?,
+  ]
+  .concat();
   if incomplete_files.is_empty() {
-    Ok(())
+    lay_out_legacy_assets(app, &client_info, assets_dir, root_dir).await?;
+    Ok(None)
   } else {
-    schedule_progressive_task_group(
-      app,
+    let task_group = schedule_progressive_task_group(
+      app.clone(),
       format!("patch-files?{}", client_info.id),
       incomplete_files,
       true,
     )
     .await?;
-    Err(LaunchError::GameFilesIncomplete.into())
+    Ok(Some(task_group.task_group))
   }
 }
 
-// Step 3: validate selected player, if its type is 3rd-party, load server meta for authlib.
-// returns Ok(false) if the access_token is expired, Ok(true) if the token is valid.
+async fn lay_out_legacy_assets(
+  app: &AppHandle,
+  client_info: &McClientInfo,
+  assets_dir: &Path,
+  root_dir: &Path,
+) -> LXMCLResult<()> {
+  let asset_index_path = assets_dir.join(format!("indexes/{}.json", client_info.asset_index.id));
+  let asset_index = load_asset_index(app, &asset_index_path, &client_info.asset_index.url).await?;
+  materialize_legacy_assets(
+    &asset_index,
+    &client_info.asset_index.id,
+    assets_dir,
+    root_dir,
+  )
+  .await
+}
+
+// Step 3: validate selected player (or the one bound to the instance),
+// if its type is 3rd-party, load server meta for authlib.
+// returns the id of the validated player, so an expired login can be refreshed for it.
 #[tauri::command]
 pub async fn validate_selected_player(
   app: AppHandle,
   launching_queue_state: State<'_, Mutex<Vec<LaunchingState>>>,
-) -> LXMCLResult<bool> {
-  let player = get_selected_player_info(&app)?;
+) -> LXMCLResult<PlayerValidationResult> {
+  let player_binding = launching_queue_state
+    .lock()?
+    .last()
+    .ok_or(LaunchError::LaunchingStateNotFound)?
+    .selected_instance
+    .player_binding
+    .clone();
+  let player = get_instance_player_info(&app, player_binding.as_ref())?;
 
   {
     let mut launching_queue = launching_queue_state.lock()?;
@@ -209,14 +281,18 @@ pub async fn validate_selected_player(
     }
   }
 
-  match player.player_type {
+  let is_valid = match player.player_type {
     PlayerType::ThirdParty => {
       authlib_injector::jar::check_authlib_jar(&app).await?;
-      authlib_injector::common::validate(&app, &player).await
+      authlib_injector::common::validate(&app, &player).await?
     }
-    PlayerType::Microsoft => microsoft::oauth::validate(&app, &player).await,
-    PlayerType::Offline => Ok(true),
-  }
+    PlayerType::Microsoft => microsoft::oauth::validate(&app, &player).await?,
+    PlayerType::Offline => true,
+  };
+  Ok(PlayerValidationResult {
+    player_id: player.id,
+    is_valid,
+  })
 }
 
 #[tauri::command]
@@ -226,7 +302,7 @@ pub async fn launch_game(
   quick_play_singleplayer: Option<String>,
   quick_play_multiplayer: Option<String>,
 ) -> LXMCLResult<()> {
-  let (id, selected_java, game_config, instance) = {
+  let (id, selected_java, game_config, instance, custom_variables) = {
     let mut launching_queue = launching_queue_state.lock()?;
     let launching = launching_queue
       .last_mut()
@@ -237,8 +313,11 @@ pub async fn launch_game(
       launching.selected_java.clone(),
       launching.game_config.clone(),
       launching.selected_instance.clone(),
+      get_custom_command_variables(&app, launching),
     )
   };
+  let custom_commands = &game_config.advanced.custom_commands;
+  let env_vars = parse_environment_variables(&game_config.advanced.jvm.environment_variable);
 
   let instance_id = instance.id.clone();
   let work_dir = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Root])
@@ -253,11 +332,12 @@ pub async fn launch_game(
     args: cmd_args,
   } = generate_launch_command(&app, quick_play_singleplayer, quick_play_multiplayer).await?;
 
-  let wrapper = game_config
-    .advanced
-    .custom_commands
-    .wrapper_launcher
-    .clone();
+  // values are quoted, as the wrapper is split like a shell command line
+  let quoted_variables: HashMap<String, String> = custom_variables
+    .iter()
+    .map(|(k, v)| (k.clone(), try_quote(v).unwrap_or(v.into()).to_string()))
+    .collect();
+  let wrapper = replace_custom_variables(&custom_commands.wrapper_launcher, &quoted_variables);
 
   let mut cmd_base = if let Some(mut c) = split_command_line(&wrapper)? {
     c.arg(&selected_java.exec_path);
@@ -269,10 +349,25 @@ pub async fn launch_game(
   let full_cmd = export_full_launch_command(&class_paths, &cmd_args, &selected_java.exec_path);
   println!("[Launch Command] {}", full_cmd);
 
-  let precall_cmd = game_config.advanced.custom_commands.precall_command.clone();
+  // the pre-launch and post-exit commands run with the same environment variables as the game,
+  // and the values are quoted for the system shell that runs them
+  let shell_variables: HashMap<String, String> = custom_variables
+    .iter()
+    .map(|(k, v)| (k.clone(), quote_command_line_arg(v)))
+    .collect();
+  let precall_cmd = replace_custom_variables(&custom_commands.precall_command, &shell_variables);
   if !precall_cmd.trim().is_empty() {
-    let _ = execute_command_line(&precall_cmd);
+    let _ = build_command_line(&precall_cmd)
+      .envs(env_vars.iter().cloned())
+      .status();
   }
+  let post_exit_cmd =
+    replace_custom_variables(&custom_commands.post_exit_command, &shell_variables);
+  let post_exit_cmd = (!post_exit_cmd.trim().is_empty()).then(|| {
+    let mut cmd = build_command_line(&post_exit_cmd);
+    cmd.envs(env_vars.iter().cloned());
+    cmd
+  });
 
   // execute launch command
   #[cfg(target_os = "windows")]
@@ -280,6 +375,7 @@ pub async fn launch_game(
 
   let child = cmd_base
     .current_dir(&work_dir)
+    .envs(env_vars)
     .env("CLASSPATH", class_paths.join(get_separator()))
     .args(cmd_args)
     .stdout(Stdio::piped())
@@ -307,26 +403,19 @@ pub async fn launch_game(
     id,
     child,
     instance_id,
-    game_config.display_game_log,
+    game_config.display_game_log && !*IS_HEADLESS,
     &game_config.game_window.custom_title,
     game_config.launcher_visibility.clone(),
     tx,
-    Some(
-      game_config
-        .advanced
-        .custom_commands
-        .post_exit_command
-        .clone(),
-    ),
+    post_exit_cmd,
   )
   .await?;
   let _ = rx.recv();
 
   if game_config.launcher_visibility != LauncherVisiablity::Always {
-    let _ = app
-      .get_webview_window("main")
-      .expect("no main window")
-      .hide();
+    if let Some(main_window) = app.get_webview_window("main") {
+      let _ = main_window.hide();
+    }
   }
 
   Ok(())
@@ -434,3 +523,34 @@ pub fn export_game_crash_info(
     zip_file_path.clone(),
   )
 }
+
+#[tauri::command]
+pub fn analyze_game_crash(
+  app: AppHandle,
+  launching_queue_state: State<'_, Mutex<Vec<LaunchingState>>>,
+  launching_id: u64,
+) -> LXMCLResult<CrashAnalysisResult> {
+  let game_log_path = app.path().resolve::<PathBuf>(
+    format!("GameLogs/game_log_{launching_id}.log").into(),
+    BaseDirectory::AppCache,
+  )?;
+
+  let instance = {
+    let launching_queue = launching_queue_state.lock()?;
+    launching_queue
+      .iter()
+      .find(|l| l.id == launching_id)
+      .ok_or(LaunchError::LaunchingStateNotFound)?
+      .selected_instance
+      .clone()
+  };
+  // crash reports and JVM fatal error logs are written into the working directory of the game
+  let game_dir = get_instance_subdir_paths(&app, &instance, &[&InstanceSubdirType::Root])
+    .and_then(|mut paths| paths.pop())
+    .ok_or(InstanceError::InstanceNotFoundByID)?;
+
+  Ok(analyze_crash_log_files(&collect_crash_log_files(
+    &game_dir,
+    &game_log_path,
+  )))
+}
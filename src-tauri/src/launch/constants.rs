@@ -1,3 +1,5 @@
 pub const GAME_PROCESS_OUTPUT_EVENT: &str = "launch:game-process-output";
+pub const GAME_PROCESS_EXIT_EVENT: &str = "launch:game-process-exit";
+pub const LAUNCH_STEP_PROGRESS_EVENT: &str = "launch:step-progress";
 
 pub const READY_FLAG: &[&str] = &["render thread", "lwjgl version", "lwjgl openal"];
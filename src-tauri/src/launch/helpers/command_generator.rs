@@ -1,12 +1,15 @@
 use crate::account::helpers::authlib_injector::jar::get_jar_path as get_authlib_injector_jar_path;
 use crate::account::models::{AccountError, PlayerType};
 use crate::error::{LXMCLError, LXMCLResult};
+use crate::instance::helpers::asset_index::{get_game_assets_dir, load_asset_index};
 use crate::instance::helpers::client_json::FeaturesInfo;
 use crate::instance::helpers::game_version::compare_game_versions;
 use crate::instance::helpers::misc::get_instance_subdir_paths;
 use crate::instance::models::misc::{InstanceError, InstanceSubdirType};
 use crate::launch::helpers::file_validator::get_nonnative_library_paths;
-use crate::launch::helpers::misc::{get_separator, replace_arguments};
+use crate::launch::helpers::misc::{
+  get_custom_command_variables, get_separator, replace_arguments, replace_custom_variables,
+};
 use crate::launch::models::{LaunchError, LaunchingState};
 use crate::launcher_config::models::*;
 use crate::utils::sys_info::get_memory_info;
@@ -25,6 +28,7 @@ pub struct LaunchArguments {
   // basic game params
   pub assets_root: String,
   pub assets_index_name: String,
+  pub game_assets: String, // for legacy versions, see `get_game_assets_dir`
   pub game_directory: String,
   pub version_name: String,
   pub version_type: String,
@@ -105,6 +109,7 @@ pub async fn generate_launch_command(
     .last()
     .ok_or(LaunchError::LaunchingStateNotFound)?
     .clone();
+  let custom_variables = get_custom_command_variables(app, &launching);
   let LaunchingState {
     selected_java,
     selected_instance,
@@ -160,6 +165,15 @@ pub async fn generate_launch_command(
     .collect();
   class_paths.push(client_jar_path.clone());
 
+  let asset_index_path = assets_dir.join(format!("indexes/{}.json", client_info.asset_index.id));
+  let asset_index = load_asset_index(app, &asset_index_path, &client_info.asset_index.url).await?;
+  let game_assets_dir = get_game_assets_dir(
+    &asset_index,
+    &client_info.asset_index.id,
+    assets_dir,
+    root_dir,
+  );
+
   let quickplay_server_url = match quick_play_multiplayer {
     Some(ref url) if !url.is_empty() => url.clone(),
     None if game_config.game_server.auto_join => game_config.game_server.server_url.clone(),
@@ -169,6 +183,7 @@ pub async fn generate_launch_command(
   let arguments_value = LaunchArguments {
     assets_root: assets_dir.to_string_lossy().to_string(),
     assets_index_name: client_info.asset_index.id,
+    game_assets: game_assets_dir.to_string_lossy().to_string(),
     game_directory: root_dir.to_string_lossy().to_string(),
 
     version_name: selected_instance.name.clone(),
@@ -184,7 +199,7 @@ pub async fn generate_launch_command(
     library_directory: libraries_dir.to_string_lossy().to_string(),
     classpath_separator: get_separator().to_string(),
 
-    auth_access_token: selected_player.access_token.unwrap_or_default(),
+    auth_access_token: selected_player.plain_access_token()?,
     auth_player_name: selected_player.name,
     user_type: "msa".to_string(), // TODO
     auth_uuid: selected_player.uuid.to_string(),
@@ -233,7 +248,12 @@ pub async fn generate_launch_command(
     }
 
     if !jvm.args.is_empty() {
-      cmd.extend(jvm.args.split_whitespace().map(|s| s.to_string()));
+      cmd.extend(
+        jvm
+          .args
+          .split_whitespace()
+          .map(|s| replace_custom_variables(s, &custom_variables)),
+      );
     }
   }
 
@@ -377,7 +397,11 @@ pub async fn generate_launch_command(
     .is_empty()
   {
     match shlex::split(&game_config.advanced.custom_commands.minecraft_argument) {
-      Some(mut extra_args) => cmd.append(&mut extra_args),
+      Some(extra_args) => cmd.extend(
+        extra_args
+          .iter()
+          .map(|arg| replace_custom_variables(arg, &custom_variables)),
+      ),
       None => {
         eprintln!("[Warn] Failed to parse minecraftArgument");
       }
@@ -0,0 +1,474 @@
+use crate::launch::models::{CrashAnalysisResult, CrashCause, CrashFinding};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const MAX_EVIDENCE_LENGTH: usize = 500;
+
+struct CrashRule {
+  cause: CrashCause,
+  // named groups `mod` and `dep` capture mod ids or names, `file` and `files` capture mod files,
+  // `java` captures the required java version, `class_version` captures a class file version.
+  patterns: Vec<Regex>,
+}
+
+fn rule(cause: CrashCause, patterns: &[&str]) -> CrashRule {
+  CrashRule {
+    cause,
+    patterns: patterns.iter().map(|p| Regex::new(p).unwrap()).collect(),
+  }
+}
+
+lazy_static! {
+  static ref CRASH_RULES: Vec<CrashRule> = vec![
+    rule(
+      CrashCause::WrongJavaVersion,
+      &[
+        r"UnsupportedClassVersionError: .*?class file version (?P<class_version>\d+)\.\d+",
+        r"Unsupported major\.minor version (?P<class_version>\d+)\.\d+",
+        // legacy forge launched by java 9+
+        r"ClassCastException: class jdk\.internal\.loader\.ClassLoaders\$AppClassLoader cannot be cast to class java\.net\.URLClassLoader",
+        // fabric and quilt dependency check on java itself
+        r"Mod '(?P<mod>[^']+)'[^\n]*? requires [^\n]*?(?:version )?(?P<java>\d+)[^\n]*? of (?:mod )?'?(?:Java|OpenJDK)",
+      ],
+    ),
+    rule(
+      CrashCause::OutOfMemory,
+      &[
+        r"java\.lang\.OutOfMemoryError[^\n]*",
+        r"Could not reserve enough space for \d+KB object heap",
+        r"There is insufficient memory for the Java Runtime Environment to continue",
+        r"Native memory allocation \(\w+\) failed to allocate[^\n]*",
+      ],
+    ),
+    rule(
+      CrashCause::MissingModDependency,
+      &[
+        // fabric and quilt
+        r"Mod '(?P<mod>[^']+)'[^\n]*? requires [^\n]*? of (?:mod )?'?(?P<dep>[^',(\n]+?)'?(?: \([^)\n]*\))?, which is missing",
+        // forge 1.13+
+        r"Mod ID: '(?P<dep>[^']+)', Requested by: '(?P<mod>[^']+)', Expected range: '[^']*', Actual version: '\[MISSING\]'",
+        // legacy forge
+        r"MissingModsException: Mod (?P<mod>\S+) \([^)]*\) requires \[(?P<dep>[^@\]\s]+)",
+      ],
+    ),
+    rule(
+      CrashCause::DuplicateMods,
+      &[
+        r"Found a duplicate mod (?P<mod>\S+) at \[(?P<files>[^\]]+)\]",
+        r"Mod ID: '(?P<mod>[^']+)' from mod files: (?P<files>[^\n]+)",
+        // fabric, e.g. "Duplicate mod ID: sodium!"
+        r"(?i)duplicate mod(?: id)?: '?(?P<mod>[\w\-.]+)'?",
+        r"DuplicateModsFoundException",
+      ],
+    ),
+    rule(
+      CrashCause::MixinFailure,
+      &[
+        r"Mixin apply for mod (?P<mod>[\w\-.]+) failed[^\n]*",
+        r"Mixin \[[^\]]+\] from mod (?P<mod>[\w\-.]+) failed injection check",
+        r"(?:MixinApplyError|MixinTransformerError|InvalidInjectionException|InvalidMixinException)[^\n]*?(?:(?P<config>[\w\-]+)\.mixins\.json|mixins\.(?P<config_alt>[\w\-]+)\.json)",
+        r"(?:MixinApplyError|MixinTransformerError|InvalidInjectionException|InvalidMixinException)[^\n]*",
+      ],
+    ),
+    rule(
+      CrashCause::OpenGlError,
+      &[
+        r"Pixel format not accelerated",
+        r"GLFW error 6554[23][^\n]*",
+        r"WGL: The driver does not appear to support OpenGL",
+        r"LWJGLException: Could not create context",
+        r"No OpenGL context found in the current thread",
+        r"Failed to create (?:the )?OpenGL context[^\n]*",
+        // native crash inside a graphics driver
+        r"(?m)^# C  \[(?:atio6axx|atioglxx|amdxc64|nvoglv(?:32|64)|ig\d+icd(?:32|64)|libnvidia-glcore|iris_dri|radeonsi_dri)[^\n]*",
+      ],
+    ),
+    rule(
+      CrashCause::CorruptModJar,
+      &[
+        r"Error analyzing \[(?P<file>[^\]]+)\]: java\.util\.zip\.ZipException[^\n]*",
+        r"Zip file (?P<file>\S+) failed to read properly",
+        r"Invalid or corrupt jarfile (?P<file>\S+)",
+        r"java\.util\.zip\.ZipException: (?:zip END header not found|invalid LOC header|invalid CEN header|error in opening zip file)[^\n]*",
+      ],
+    ),
+  ];
+}
+
+fn file_name_of(path: &str) -> String {
+  Path::new(path.trim())
+    .file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or(path.trim().to_string())
+}
+
+fn truncate_evidence(evidence: &str) -> String {
+  let evidence = evidence.trim();
+  if evidence.chars().count() > MAX_EVIDENCE_LENGTH {
+    format!(
+      "{}...",
+      evidence
+        .chars()
+        .take(MAX_EVIDENCE_LENGTH)
+        .collect::<String>()
+    )
+  } else {
+    evidence.to_string()
+  }
+}
+
+fn get_involved_mods(caps: &Captures) -> Vec<String> {
+  let mut mods = Vec::new();
+  for name in ["mod", "dep", "config", "config_alt"] {
+    if let Some(m) = caps.name(name) {
+      mods.push(m.as_str().trim().to_string());
+    }
+  }
+  if let Some(m) = caps.name("file") {
+    mods.push(file_name_of(m.as_str()));
+  }
+  if let Some(m) = caps.name("files") {
+    mods.extend(m.as_str().split(',').map(file_name_of));
+  }
+  mods.retain(|m| !m.is_empty());
+  mods
+}
+
+fn get_required_java_version(caps: &Captures) -> Option<usize> {
+  if let Some(m) = caps.name("java") {
+    return m.as_str().parse().ok();
+  }
+  if let Some(m) = caps.name("class_version") {
+    // class file version 52 corresponds to java 8, and so on
+    return m.as_str().parse::<usize>().ok()?.checked_sub(44);
+  }
+  if caps.get(0)?.as_str().contains("ClassCastException") {
+    return Some(8);
+  }
+  None
+}
+
+fn analyze_crash_text(text: &str, source_file: &str, findings: &mut Vec<CrashFinding>) {
+  // a log excerpt is attributed to the first rule matching it, e.g. a fabric dependency on Java
+  // itself is a wrong Java version, rather than a missing mod.
+  let mut claimed: Vec<(CrashCause, Range<usize>)> = Vec::new();
+  for rule in CRASH_RULES.iter() {
+    for pattern in &rule.patterns {
+      for caps in pattern.captures_iter(text) {
+        let range = caps.get(0).map(|m| m.range()).unwrap_or_default();
+        if claimed.iter().any(|(cause, claimed)| {
+          *cause != rule.cause && claimed.start < range.end && range.start < claimed.end
+        }) {
+          continue;
+        }
+        claimed.push((rule.cause, range));
+
+        let index = match findings.iter().position(|f| f.cause == rule.cause) {
+          Some(index) => index,
+          None => {
+            findings.push(CrashFinding {
+              cause: rule.cause,
+              mods: Vec::new(),
+              evidence: truncate_evidence(&caps[0]),
+              source_file: source_file.to_string(),
+              required_java_version: None,
+            });
+            findings.len() - 1
+          }
+        };
+        let finding = &mut findings[index];
+
+        for mod_name in get_involved_mods(&caps) {
+          if !finding.mods.contains(&mod_name) {
+            finding.mods.push(mod_name);
+          }
+        }
+        if finding.required_java_version.is_none() {
+          finding.required_java_version = get_required_java_version(&caps);
+        }
+      }
+    }
+  }
+}
+
+// newest file in `dir` satisfying `filter`, which is modified after `since` (if given).
+fn get_newest_file<F>(dir: &Path, since: Option<SystemTime>, filter: F) -> Option<PathBuf>
+where
+  F: Fn(&str) -> bool,
+{
+  fs::read_dir(dir)
+    .ok()?
+    .filter_map(Result::ok)
+    .filter(|entry| filter(&entry.file_name().to_string_lossy()))
+    .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?.modified().ok()?)))
+    .filter(|(_, modified)| since.map_or(true, |since| *modified >= since))
+    .max_by_key(|(_, modified)| *modified)
+    .map(|(path, _)| path)
+}
+
+// collect the game log, the latest crash report and JVM fatal error log produced by this launch.
+pub fn collect_crash_log_files(game_dir: &Path, game_log_path: &Path) -> Vec<PathBuf> {
+  // the game log is created when the game process starts
+  let since = fs::metadata(game_log_path)
+    .ok()
+    .and_then(|metadata| metadata.created().ok());
+
+  let mut files = Vec::new();
+  if game_log_path.exists() {
+    files.push(game_log_path.to_path_buf());
+  }
+  if let Some(path) = get_newest_file(&game_dir.join("crash-reports"), since, |name| {
+    name.ends_with(".txt")
+  }) {
+    files.push(path);
+  }
+  if let Some(path) = get_newest_file(game_dir, since, |name| {
+    name.starts_with("hs_err_pid") && name.ends_with(".log")
+  }) {
+    files.push(path);
+  }
+  files
+}
+
+pub fn analyze_crash_log_files(files: &[PathBuf]) -> CrashAnalysisResult {
+  let mut result = CrashAnalysisResult::default();
+  for path in files {
+    let Ok(bytes) = fs::read(path) else {
+      continue;
+    };
+    // logs are not always in utf-8 (e.g. on windows with non-english locale)
+    let text = String::from_utf8_lossy(&bytes);
+    let source_file = path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    analyze_crash_text(&text, &source_file, &mut result.findings);
+    result.analyzed_files.push(source_file);
+  }
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn analyze(text: &str) -> Vec<CrashFinding> {
+    let mut findings = Vec::new();
+    analyze_crash_text(text, "latest.log", &mut findings);
+    findings
+  }
+
+  // each snippet is expected to produce exactly one finding.
+  fn assert_finding(
+    text: &str,
+    cause: CrashCause,
+    mods: &[&str],
+    required_java_version: Option<usize>,
+  ) {
+    let findings = analyze(text);
+    assert_eq!(findings.len(), 1, "{text}");
+    assert_eq!(findings[0].cause, cause, "{text}");
+    assert_eq!(findings[0].mods, mods, "{text}");
+    assert_eq!(
+      findings[0].required_java_version, required_java_version,
+      "{text}"
+    );
+  }
+
+  #[test]
+  fn wrong_java_version() {
+    let cases: &[(&str, &[&str], Option<usize>)] = &[
+      (
+        "java.lang.UnsupportedClassVersionError: net/minecraft/client/main/Main has been compiled \
+         by a more recent version of the Java Runtime (class file version 65.0), this version of \
+         the Java Runtime only recognizes class file versions up to 52.0",
+        &[],
+        Some(21),
+      ),
+      (
+        "java.lang.UnsupportedClassVersionError: a/b/C : Unsupported major.minor version 52.0",
+        &[],
+        Some(8),
+      ),
+      (
+        "java.lang.ClassCastException: class jdk.internal.loader.ClassLoaders$AppClassLoader \
+         cannot be cast to class java.net.URLClassLoader",
+        &[],
+        Some(8),
+      ),
+    ];
+    for (text, mods, java) in cases {
+      assert_finding(text, CrashCause::WrongJavaVersion, mods, *java);
+    }
+  }
+
+  #[test]
+  fn fabric_java_dependency_is_not_a_missing_mod() {
+    // also matches the missing dependency pattern, the java rule comes first
+    assert_finding(
+      "Mod 'Sodium' (sodium) 0.5.3 requires version 17 or later of 'OpenJDK 64-Bit Server VM' \
+       (java), which is missing!",
+      CrashCause::WrongJavaVersion,
+      &["Sodium"],
+      Some(17),
+    );
+  }
+
+  #[test]
+  fn out_of_memory() {
+    for text in [
+      "java.lang.OutOfMemoryError: Java heap space",
+      "Error occurred during initialization of VM\nCould not reserve enough space for 4194304KB \
+       object heap",
+      "# There is insufficient memory for the Java Runtime Environment to continue.",
+      "# Native memory allocation (mmap) failed to allocate 1048576 bytes for committing reserved memory.",
+    ] {
+      assert_finding(text, CrashCause::OutOfMemory, &[], None);
+    }
+  }
+
+  #[test]
+  fn missing_mod_dependency() {
+    let cases: &[(&str, &[&str])] = &[
+      (
+        "Mod 'Mod Menu' (modmenu) 7.2.2 requires version 0.80.0 or later of mod 'Fabric API' \
+         (fabric-api), which is missing!",
+        &["Mod Menu", "Fabric API"],
+      ),
+      (
+        "Mod ID: 'geckolib', Requested by: 'mowziesmobs', Expected range: '[4.0,)', \
+         Actual version: '[MISSING]'",
+        &["mowziesmobs", "geckolib"],
+      ),
+      (
+        "net.minecraftforge.fml.common.MissingModsException: Mod journeymap (JourneyMap) \
+         requires [forge@[14.23.5.2847,)]",
+        &["journeymap", "forge"],
+      ),
+    ];
+    for (text, mods) in cases {
+      assert_finding(text, CrashCause::MissingModDependency, mods, None);
+    }
+  }
+
+  #[test]
+  fn duplicate_mods() {
+    let cases: &[(&str, &[&str])] = &[
+      (
+        "Found a duplicate mod jei at [mods/jei-1.12.2-4.15.jar, mods/jei-1.12.2-4.16.jar]",
+        &["jei", "jei-1.12.2-4.15.jar", "jei-1.12.2-4.16.jar"],
+      ),
+      (
+        "Mod ID: 'jei' from mod files: jei-15.2.jar, jei-15.3.jar",
+        &["jei", "jei-15.2.jar", "jei-15.3.jar"],
+      ),
+      (
+        "Duplicate mod ID: sodium! (sodium-0.5.3.jar, sodium-0.5.8.jar)",
+        &["sodium"],
+      ),
+      (
+        "net.minecraftforge.fml.common.DuplicateModsFoundException",
+        &[],
+      ),
+    ];
+    for (text, mods) in cases {
+      assert_finding(text, CrashCause::DuplicateMods, mods, None);
+    }
+  }
+
+  #[test]
+  fn duplicate_mods_pattern_ignores_unrelated_lines() {
+    for text in [
+      "[main/INFO]: Checking for duplicate mods in the mods folder",
+      "[main/DEBUG]: No duplicate mods found",
+    ] {
+      assert!(analyze(text).is_empty(), "{text}");
+    }
+  }
+
+  #[test]
+  fn mixin_failure() {
+    let cases: &[(&str, &[&str])] = &[
+      (
+        "Mixin apply for mod create failed create.mixins.json:ContraptionMixin from mod create",
+        &["create"],
+      ),
+      (
+        "Mixin [sodium.mixins.json:MixinWorldRenderer] from mod sodium failed injection check",
+        &["sodium"],
+      ),
+      (
+        "org.spongepowered.asm.mixin.injection.throwables.InvalidInjectionException: Critical \
+         injection failure in iris.mixins.json:MixinGameRenderer",
+        &["iris"],
+      ),
+      (
+        "org.spongepowered.asm.mixin.transformer.throwables.MixinTransformerError: An \
+         unexpected critical error was encountered in mixins.voxelmap.json",
+        &["voxelmap"],
+      ),
+    ];
+    for (text, mods) in cases {
+      assert_finding(text, CrashCause::MixinFailure, mods, None);
+    }
+  }
+
+  #[test]
+  fn open_gl_error() {
+    for text in [
+      "org.lwjgl.LWJGLException: Pixel format not accelerated",
+      "GLFW error 65542: WGL: The driver does not appear to support OpenGL",
+      "org.lwjgl.LWJGLException: Could not create context",
+      "java.lang.IllegalStateException: No OpenGL context found in the current thread.",
+      "Failed to create the OpenGL context",
+      "# Problematic frame:\n# C  [atio6axx.dll+0x1b3c5d]",
+    ] {
+      assert_finding(text, CrashCause::OpenGlError, &[], None);
+    }
+  }
+
+  #[test]
+  fn corrupt_mod_jar() {
+    let cases: &[(&str, &[&str])] = &[
+      (
+        "Error analyzing [/home/user/.minecraft/mods/broken.jar]: \
+         java.util.zip.ZipException: zip END header not found",
+        &["broken.jar"],
+      ),
+      (
+        "Zip file mods/broken.jar failed to read properly, it will be ignored",
+        &["broken.jar"],
+      ),
+      (
+        "Error: Invalid or corrupt jarfile mods/broken.jar",
+        &["broken.jar"],
+      ),
+      (
+        "java.util.zip.ZipException: invalid LOC header (bad signature)",
+        &[],
+      ),
+    ];
+    for (text, mods) in cases {
+      assert_finding(text, CrashCause::CorruptModJar, mods, None);
+    }
+  }
+
+  #[test]
+  fn findings_are_merged_by_cause() {
+    let findings = analyze(
+      "Mod ID: 'geckolib', Requested by: 'mowziesmobs', Expected range: '[4.0,)', \
+       Actual version: '[MISSING]'\n\
+       Mod ID: 'geckolib', Requested by: 'alexsmobs', Expected range: '[4.0,)', \
+       Actual version: '[MISSING]'\n\
+       java.lang.OutOfMemoryError: Java heap space",
+    );
+    assert_eq!(findings.len(), 2);
+    assert_eq!(findings[0].cause, CrashCause::OutOfMemory);
+    assert_eq!(findings[1].cause, CrashCause::MissingModDependency);
+    assert_eq!(findings[1].mods, ["mowziesmobs", "geckolib", "alexsmobs"]);
+  }
+}
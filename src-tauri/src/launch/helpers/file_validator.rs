@@ -6,6 +6,7 @@ use crate::instance::helpers::client_json::{
 use crate::instance::models::misc::InstanceError;
 use crate::launch::helpers::misc::get_natives_string;
 use crate::launch::models::LaunchError;
+use crate::launcher_config::helpers::shared_store::{add_to_shared_store, link_from_shared_store};
 use crate::resource::helpers::misc::{convert_url_to_target_source, get_download_api};
 use crate::resource::models::{ResourceType, SourceType};
 use crate::tasks::download::DownloadParam;
@@ -64,11 +65,22 @@ pub fn get_native_library_artifacts(client_info: &McClientInfo) -> Vec<Downloads
   artifacts.into_iter().collect()
 }
 
+// returns true if the file has been placed from the shared store.
+async fn try_link_from_shared_store(store_dir: Option<&Path>, sha1: &str, dest: &Path) -> bool {
+  match store_dir {
+    Some(store_dir) => link_from_shared_store(store_dir, sha1, dest)
+      .await
+      .unwrap_or(false),
+    None => false,
+  }
+}
+
 pub async fn get_invalid_library_files(
   source: SourceType,
   library_path: &Path,
   client_info: &McClientInfo,
   check_hash: bool,
+  shared_store_dir: Option<&Path>,
 ) -> LXMCLResult<Vec<PTaskParam>> {
   let mut artifacts = Vec::new();
   artifacts.extend(get_native_library_artifacts(client_info));
@@ -78,6 +90,11 @@ pub async fn get_invalid_library_files(
     let file_path = library_path.join(&artifact.path);
     let exists = fs::try_exists(&file_path).await?;
     if exists && (!check_hash || validate_sha1(file_path.clone(), artifact.sha1.clone()).is_ok()) {
+      if let (true, Some(store_dir)) = (check_hash, shared_store_dir) {
+        let _ = add_to_shared_store(store_dir, &artifact.sha1, &file_path).await;
+      }
+      Ok(None)
+    } else if try_link_from_shared_store(shared_store_dir, &artifact.sha1, &file_path).await {
       Ok(None)
     } else if artifact.url.is_empty() {
       return Err(LaunchError::GameFilesIncomplete.into());
@@ -98,6 +115,7 @@ pub async fn get_invalid_library_files(
         dest: file_path,
         filename: None,
         sha1: Some(artifact.sha1.clone()),
+        fallback_srcs: vec![],
       })))
     }
   });
@@ -317,6 +335,7 @@ pub async fn get_invalid_assets(
   source: SourceType,
   asset_path: &Path,
   check_hash: bool,
+  shared_store_dir: Option<&Path>,
 ) -> LXMCLResult<Vec<PTaskParam>> {
   let assets_download_api = get_download_api(source, ResourceType::Assets)?;
 
@@ -333,7 +352,12 @@ pub async fn get_invalid_assets(
       let exists = fs::try_exists(&dest).await?;
 
       if exists && (!check_hash || validate_sha1(dest.clone(), item.hash.clone()).is_ok()) {
+        if let (true, Some(store_dir)) = (check_hash, shared_store_dir) {
+          let _ = add_to_shared_store(store_dir, &item.hash, &dest).await;
+        }
         Ok::<Option<PTaskParam>, crate::error::LXMCLError>(None)
+      } else if try_link_from_shared_store(shared_store_dir, &item.hash, &dest).await {
+        Ok(None)
       } else {
         let src = assets_download_api
           .join(&path_in_repo)
@@ -343,6 +367,7 @@ pub async fn get_invalid_assets(
           dest,
           filename: None,
           sha1: Some(item.hash.clone()),
+          fallback_srcs: vec![],
         })))
       }
     }
@@ -1,10 +1,17 @@
 use crate::error::LXMCLResult;
 use crate::instance::helpers::game_version::compare_game_versions;
 use crate::instance::models::misc::Instance;
+use crate::launch::constants::LAUNCH_STEP_PROGRESS_EVENT;
 use crate::launch::models::LaunchError;
+use crate::launcher_config::helpers::java::{
+  build_mojang_java_download_params, refresh_and_update_javas,
+};
 use crate::launcher_config::models::{GameJava, JavaInfo};
+use crate::tasks::commands::schedule_progressive_task_group;
+use crate::tasks::wait::{wait_for_task_group, watch_task_group_events};
 use std::cmp::Ordering;
-use tauri::AppHandle;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager};
 
 pub async fn select_java_runtime(
   app: &AppHandle,
@@ -23,36 +30,87 @@ pub async fn select_java_runtime(
       .ok_or_else(|| LaunchError::SelectedJavaUnavailable.into());
   }
 
-  let mut min_version_req = get_minimum_java_version_by_game(app, instance).await;
-
-  if client_json_req > min_version_req {
-    min_version_req = client_json_req;
+  let min_version_req = get_required_java_version(app, instance, client_json_req, true).await;
+  if let Some(java) = find_suitable_java(java_list, min_version_req) {
+    return Ok(java);
   }
 
+  // no installed Java meets the requirement, download one from Mojang and try again.
+  provision_mojang_java(app, min_version_req).await?;
+  refresh_and_update_javas(app).await;
+  let java_list = app.state::<Mutex<Vec<JavaInfo>>>().lock()?.clone();
+  find_suitable_java(&java_list, min_version_req).ok_or_else(|| LaunchError::NoSuitableJava.into())
+}
+
+pub async fn get_required_java_version(
+  app: &AppHandle,
+  instance: &Instance,
+  client_json_req: i32,
+  fallback_fetch_remote: bool,
+) -> i32 {
+  get_minimum_java_version_by_game(app, instance, fallback_fetch_remote)
+    .await
+    .max(client_json_req)
+}
+
+pub fn find_suitable_java(java_list: &[JavaInfo], min_version_req: i32) -> Option<JavaInfo> {
   let mut suitable_candidates = Vec::new();
   for java in java_list {
     match java.major_version.cmp(&min_version_req) {
-      Ordering::Equal => return Ok(java.clone()),
+      Ordering::Equal => return Some(java.clone()),
       Ordering::Greater => suitable_candidates.push(java.clone()),
       _ => {}
     }
   }
 
-  if suitable_candidates.is_empty() {
-    Err(LaunchError::NoSuitableJava.into())
-  } else {
-    suitable_candidates.sort_by_key(|j| j.major_version);
-    Ok(suitable_candidates[0].clone())
+  suitable_candidates.sort_by_key(|j| j.major_version);
+  suitable_candidates.into_iter().next()
+}
+
+/// Schedule the download of a Mojang Java runtime, and wait until the task group finishes.
+/// The download progress is emitted to the launch process modal.
+async fn provision_mojang_java(app: &AppHandle, min_version_req: i32) -> LXMCLResult<()> {
+  let (runtime_version, download_params) = build_mojang_java_download_params(app, min_version_req)
+    .await
+    .map_err(|_| LaunchError::NoSuitableJava)?;
+  if download_params.is_empty() {
+    // the runtime files are all in place already
+    return Ok(());
   }
+
+  let (listener, task_group_events) = watch_task_group_events(app);
+  let result = async {
+    let task_group = schedule_progressive_task_group(
+      app.clone(),
+      format!("mojang-java?{}", runtime_version),
+      download_params,
+      true,
+    )
+    .await?
+    .task_group;
+    wait_for_task_group(app, &task_group, &task_group_events, |progress| {
+      let _ = app.emit_to("main", LAUNCH_STEP_PROGRESS_EVENT, progress);
+    })
+    .await
+  }
+  .await;
+  app.unlisten(listener);
+  result.map_err(|_| LaunchError::JavaDownloadFailed.into())
 }
 
 /// Get minimum java version requirement by game client version
 /// ref: https://zh.minecraft.wiki/w/Java%E7%89%88?variant=zh-cn#%E8%BD%AF%E4%BB%B6%E9%9C%80%E6%B1%82
-async fn get_minimum_java_version_by_game(app: &AppHandle, instance: &Instance) -> i32 {
+async fn get_minimum_java_version_by_game(
+  app: &AppHandle,
+  instance: &Instance,
+  fallback_fetch_remote: bool,
+) -> i32 {
   // only allow fallback remote fetch here in the launch process, as Java selection and command generation are used sequentially.
   // ref: https://github.com/UNIkeEN/SJMCL/pull/799
   // 1.20.5(24w14a)+
-  if compare_game_versions(app, &instance.version, "24w14a", true).await >= Ordering::Equal {
+  if compare_game_versions(app, &instance.version, "24w14a", fallback_fetch_remote).await
+    >= Ordering::Equal
+  {
     return 21;
   }
   // 1.18(1.18-pre2)+
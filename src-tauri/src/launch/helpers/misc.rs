@@ -1,6 +1,11 @@
+use crate::instance::helpers::misc::get_instance_subdir_paths;
+use crate::instance::models::misc::InstanceSubdirType;
+use crate::launch::models::LaunchingState;
 use lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
 
 pub fn replace_arguments(args: Vec<String>, map: &HashMap<String, String>) -> Vec<String> {
   lazy_static::lazy_static!(
@@ -32,6 +37,90 @@ pub fn replace_arguments(args: Vec<String>, map: &HashMap<String, String>) -> Ve
   cmd
 }
 
+// Variables available in the custom arguments and commands, e.g. `${instance_name}`.
+pub fn get_custom_command_variables(
+  app: &AppHandle,
+  launching: &LaunchingState,
+) -> HashMap<String, String> {
+  let instance = &launching.selected_instance;
+  let instance_dir = get_instance_subdir_paths(app, instance, &[&InstanceSubdirType::Root])
+    .and_then(|dirs| dirs.into_iter().next())
+    .unwrap_or_default();
+  let java_home = Path::new(&launching.selected_java.exec_path)
+    .parent()
+    .and_then(Path::parent)
+    .unwrap_or(Path::new(""));
+
+  HashMap::from([
+    ("instance_id".to_string(), instance.id.clone()),
+    ("instance_name".to_string(), instance.name.clone()),
+    (
+      "instance_dir".to_string(),
+      instance_dir.to_string_lossy().to_string(),
+    ),
+    (
+      "version_dir".to_string(),
+      instance.version_path.to_string_lossy().to_string(),
+    ),
+    ("game_version".to_string(), instance.version.clone()),
+    (
+      "mod_loader".to_string(),
+      instance.mod_loader.loader_type.to_string(),
+    ),
+    (
+      "mod_loader_version".to_string(),
+      instance.mod_loader.version.clone(),
+    ),
+    (
+      "player_name".to_string(),
+      launching
+        .selected_player
+        .as_ref()
+        .map(|player| player.name.clone())
+        .unwrap_or_default(),
+    ),
+    (
+      "java_path".to_string(),
+      launching.selected_java.exec_path.clone(),
+    ),
+    (
+      "java_home".to_string(),
+      java_home.to_string_lossy().to_string(),
+    ),
+  ])
+}
+
+// Unlike `replace_arguments`, unknown variables are kept as is, and the rest are still replaced.
+pub fn replace_custom_variables(text: &str, map: &HashMap<String, String>) -> String {
+  lazy_static::lazy_static!(
+    static ref PARAM_REGEX: Regex = Regex::new(r"\$\{([^}]+)\}").unwrap();
+  );
+  PARAM_REGEX
+    .replace_all(text, |caps: &regex::Captures| {
+      map
+        .get(&caps[1])
+        .cloned()
+        .unwrap_or_else(|| caps[0].to_string())
+    })
+    .into_owned()
+}
+
+// Parse custom environment variables, `KEY=VALUE` per line (several pairs may share a line),
+// values can be quoted like in a shell, and lines starting with `#` are ignored.
+pub fn parse_environment_variables(text: &str) -> Vec<(String, String)> {
+  text
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .filter_map(shlex::split)
+    .flatten()
+    .filter_map(|pair| {
+      let (key, value) = pair.split_once('=')?;
+      (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+    })
+    .collect()
+}
+
 pub fn get_natives_string(natives: &HashMap<String, String>) -> Option<String> {
   let target_os: String = if cfg!(target_os = "windows") {
     "windows".to_string()
@@ -59,3 +148,55 @@ pub fn get_separator() -> &'static str {
     ":"
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn replace_custom_variables_keeps_unknown() {
+    let map = HashMap::from([
+      ("instance_name".to_string(), "My World".to_string()),
+      ("version_name".to_string(), "1.20.1".to_string()),
+    ]);
+    assert_eq!(
+      replace_custom_variables(
+        "echo ${instance_name} ${version_name} ${unknown} $HOME",
+        &map
+      ),
+      "echo My World 1.20.1 ${unknown} $HOME"
+    );
+    assert_eq!(
+      replace_custom_variables("${}${instance_name", &map),
+      "${}${instance_name"
+    );
+  }
+
+  #[test]
+  fn parse_environment_variables_lines() {
+    let text = "
+      # comment
+      JAVA_TOOL_OPTIONS=\"-Xss4M -Dfoo=bar\"
+      A=1 B='two words'
+      EMPTY=
+      =no_key not_a_pair
+    ";
+    assert_eq!(
+      parse_environment_variables(text),
+      vec![
+        (
+          "JAVA_TOOL_OPTIONS".to_string(),
+          "-Xss4M -Dfoo=bar".to_string()
+        ),
+        ("A".to_string(), "1".to_string()),
+        ("B".to_string(), "two words".to_string()),
+        ("EMPTY".to_string(), String::new()),
+      ]
+    );
+    // an unclosed quote drops the line only
+    assert_eq!(
+      parse_environment_variables("A=\"1\nB=2"),
+      vec![("B".to_string(), "2".to_string())]
+    );
+  }
+}
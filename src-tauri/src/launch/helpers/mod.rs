@@ -1,4 +1,5 @@
 pub mod command_generator;
+pub mod crash_analyzer;
 pub mod file_validator;
 pub mod jre_selector;
 pub mod misc;
@@ -1,10 +1,11 @@
 use crate::error::LXMCLResult;
+use crate::instance::helpers::world_backup::backup_played_worlds;
 use crate::instance::models::misc::Instance;
 use crate::launch::constants::*;
-use crate::launch::models::{LaunchError, LaunchingState};
+use crate::launch::models::{GameProcessExitPayload, LaunchError, LaunchingState};
 use crate::launcher_config::models::{LauncherVisiablity, ProcessPriority};
-use crate::utils::shell::execute_command_line;
 use crate::utils::window::create_webview_window;
+use crate::IS_HEADLESS;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
@@ -14,8 +15,9 @@ use std::process::{Child, Command};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 use std::{fs, thread};
+use sysinfo::{Pid, ProcessesToUpdate, System};
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio;
@@ -44,6 +46,10 @@ impl<T: Read + Send + 'static> OutputPipe<T> {
             .emit_to(&self.label, GAME_PROCESS_OUTPUT_EVENT, &line);
         }
         writeln!(self.log_file.lock().unwrap(), "{line}").unwrap();
+        // in headless mode, the game log is printed to the terminal instead of the log window
+        if *IS_HEADLESS {
+          println!("{line}");
+        }
         // the first time when log contains 'render thread', 'lwjgl version', or 'lwjgl openal', send signal to launch command, close frontend modal.
         if !self.game_ready_flag.load(Ordering::SeqCst)
           && READY_FLAG.iter().any(|p| line.to_lowercase().contains(p))
@@ -91,8 +97,10 @@ pub async fn monitor_process(
   custom_title: &str,
   launcher_visibility: LauncherVisiablity,
   ready_tx: Sender<()>,
-  post_exit_command: Option<String>,
+  post_exit_command: Option<Command>,
 ) -> LXMCLResult<()> {
+  // worlds saved after this time were played in this session
+  let launched_at = SystemTime::now();
   // create unique log window
   let label = format!("game_log_{id}");
   let log_file_path = app.path().resolve::<PathBuf>(
@@ -173,8 +181,10 @@ pub async fn monitor_process(
   let stop_polling_flag = stop_polling_flag.clone();
 
   tokio::spawn(async move {
+    let mut exit_code = None;
     let exit_ok = match child.wait() {
       Ok(status) => {
+        exit_code = status.code();
         if let Some(h) = stdout {
           let _ = h.join();
         }
@@ -203,8 +213,9 @@ pub async fn monitor_process(
 
     stop_polling_flag.store(true, Ordering::SeqCst);
     drop(log_file);
-    // handle launcher main window visiablity
+    // handle launcher main window visiablity (there is no window in headless mode)
     match launcher_visibility {
+      _ if *IS_HEADLESS => {}
       LauncherVisiablity::RunningHidden => {
         let main_window = app.get_webview_window("main").expect("no main window");
         let _ = main_window.show();
@@ -226,7 +237,7 @@ pub async fn monitor_process(
 
     let start_time_lock = *start_time.lock().unwrap();
     if let Some(start_time) = start_time_lock {
-      record_play_time(app.clone(), start_time, instance_id_clone).await;
+      record_play_time(app.clone(), start_time, instance_id_clone.clone()).await;
     }
 
     if exit_ok {
@@ -245,8 +256,9 @@ pub async fn monitor_process(
       };
 
       if let Some(launching) = launching_option {
-        if launching.current_step == 0 {
-          // it was marked as manually cancelled, then remove from launching_queue and not show game error window
+        if launching.current_step == 0 || *IS_HEADLESS {
+          // it was marked as manually cancelled (or launched from the command line),
+          // then remove from launching_queue and not show game error window
           let launching_queue_state = app.state::<Mutex<Vec<LaunchingState>>>();
           let mut launching_queue = launching_queue_state.lock().unwrap();
           launching_queue.retain(|state| state.id != id);
@@ -258,14 +270,44 @@ pub async fn monitor_process(
       }
     }
 
-    if let Some(cmdline) = post_exit_command.as_ref().filter(|s| !s.trim().is_empty()) {
-      let _ = execute_command_line(cmdline);
+    if let Some(mut cmd) = post_exit_command {
+      let _ = cmd.status();
     }
+
+    let _ = app.emit(
+      GAME_PROCESS_EXIT_EVENT,
+      GameProcessExitPayload { id, exit_code },
+    );
+
+    // compressing the worlds may take a while, do not delay the exit handling above
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+      backup_played_worlds(&app, &instance_id_clone, launched_at);
+    })
+    .await;
   });
 
   Ok(())
 }
 
+// whether a game process launched from the instance is still alive.
+pub fn is_instance_running(app: &AppHandle, instance_id: &str) -> bool {
+  let pids = {
+    let launching_queue_state = app.state::<Mutex<Vec<LaunchingState>>>();
+    let launching_queue = launching_queue_state.lock().unwrap();
+    launching_queue
+      .iter()
+      .filter(|state| state.selected_instance.id == instance_id && state.pid != 0)
+      .map(|state| Pid::from_u32(state.pid))
+      .collect::<Vec<_>>()
+  };
+  if pids.is_empty() {
+    return false;
+  }
+  let mut system = System::new();
+  system.refresh_processes(ProcessesToUpdate::Some(&pids), true);
+  pids.iter().any(|pid| system.process(*pid).is_some())
+}
+
 pub fn kill_process(pid: u32) -> LXMCLResult<()> {
   // KNOWN ISSUE: kill process means exit abnormally, which will not close the game-log window automatically.
   #[cfg(any(target_os = "linux", target_os = "macos"))]
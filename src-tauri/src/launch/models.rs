@@ -11,12 +11,15 @@ use strum_macros::Display;
 pub enum LaunchError {
   ModLoaderNotInstalled,
   NoSuitableJava,
+  JavaDownloadFailed,
   SelectedJavaUnavailable,
   GameFilesIncomplete,
   SetProcessPriorityFailed,
   ChangeWindowTitleFailed,
   KillProcessFailed,
   LaunchingStateNotFound,
+  BoundPlayerNotFound,
+  NoPlayerMatchesBinding,
 }
 
 impl std::error::Error for LaunchError {}
@@ -38,3 +41,45 @@ pub struct LaunchingState {
   #[default = 0] // default means not set yet
   pub pid: u32,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerValidationResult {
+  pub player_id: String, // the selected player, or the one bound to the instance
+  pub is_valid: bool,    // false if the access_token is expired
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameProcessExitPayload {
+  pub id: u64,
+  pub exit_code: Option<i32>, // None if the process was terminated by a signal
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Hash)]
+pub enum CrashCause {
+  WrongJavaVersion,
+  OutOfMemory,
+  MissingModDependency,
+  DuplicateMods,
+  MixinFailure,
+  OpenGlError,
+  CorruptModJar,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashFinding {
+  pub cause: CrashCause,
+  pub mods: Vec<String>, // mod ids, names or file names involved, as they appear in the logs
+  pub evidence: String,  // the first matched log excerpt
+  pub source_file: String,
+  pub required_java_version: Option<usize>, // only for `WrongJavaVersion`, if determinable
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CrashAnalysisResult {
+  pub findings: Vec<CrashFinding>,
+  pub analyzed_files: Vec<String>,
+}
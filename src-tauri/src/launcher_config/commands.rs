@@ -5,9 +5,22 @@ use crate::launcher_config::helpers::java::{
   build_mojang_java_download_params, get_java_info_from_command, get_java_info_from_release_file,
   refresh_and_update_javas,
 };
-use crate::launcher_config::helpers::updater::{download_target_version, fetch_latest_version};
+use crate::launcher_config::helpers::managed_java::{
+  build_runtime_file_download_params, find_invalid_runtime_files, get_java_exec_paths_in_use,
+  get_managed_java_dir, get_managed_java_exec_path, get_managed_java_record_or_default,
+  get_managed_java_root_dir, load_or_fetch_managed_java_record,
+};
+use crate::launcher_config::helpers::shared_store::{
+  get_shared_store_dir, remove_unreferenced_objects,
+};
+#[cfg(target_os = "linux")]
+use crate::launcher_config::helpers::updater::install_update_linux;
+use crate::launcher_config::helpers::updater::{
+  download_target_version, fetch_latest_version, verify_update_signature,
+};
 use crate::launcher_config::models::{
-  GameDirectory, JavaInfo, LauncherConfig, LauncherConfigError, VersionMetaInfo,
+  GameDirectory, JavaInfo, LauncherConfig, LauncherConfigError, ManagedJavaInfo,
+  SharedStoreCleanupResult, VersionMetaInfo,
 };
 use crate::storage::Storage;
 use crate::tasks::{commands::schedule_progressive_task_group, monitor::TaskMonitor};
@@ -235,11 +248,73 @@ pub async fn validate_java(java_path: String) -> LXMCLResult<()> {
 
 #[tauri::command]
 pub async fn download_mojang_java(app: AppHandle, version: String) -> LXMCLResult<()> {
-  let download_params = build_mojang_java_download_params(&app, &version).await?;
+  let (runtime_version, download_params) =
+    build_mojang_java_download_params(&app, version.parse()?).await?;
+
+  schedule_progressive_task_group(
+    app,
+    format!("mojang-java?{}", runtime_version),
+    download_params,
+    true,
+  )
+  .await?;
+
+  Ok(())
+}
+
+#[tauri::command]
+pub async fn retrieve_managed_java_list(app: AppHandle) -> LXMCLResult<Vec<ManagedJavaInfo>> {
+  refresh_and_update_javas(&app).await;
+  let exec_paths_in_use = get_java_exec_paths_in_use(&app).await?;
+
+  let mut managed_javas = Vec::new();
+  for runtime_dir in get_subdirectories(get_managed_java_root_dir(&app)?).unwrap_or_default() {
+    let Some(record) = get_managed_java_record_or_default(&runtime_dir) else {
+      continue;
+    };
+    let exec_path = get_managed_java_exec_path(&runtime_dir, &record);
+    managed_javas.push(ManagedJavaInfo {
+      name: runtime_dir
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string(),
+      component: record.component,
+      major_version: record.major_version,
+      version_name: record.version_name,
+      is_unused: !exec_paths_in_use.contains(&exec_path),
+      exec_path,
+    });
+  }
+  managed_javas.sort_by_key(|java| java.major_version);
+  Ok(managed_javas)
+}
+
+// Returns the missing or corrupted files of the managed runtime.
+#[tauri::command]
+pub async fn verify_managed_java(app: AppHandle, name: String) -> LXMCLResult<Vec<String>> {
+  let runtime_dir = get_managed_java_dir(&app, &name)?;
+  let record = load_or_fetch_managed_java_record(&app, &runtime_dir).await?;
+  find_invalid_runtime_files(&runtime_dir, &record)
+}
+
+#[tauri::command]
+pub async fn repair_managed_java(app: AppHandle, name: String) -> LXMCLResult<()> {
+  let runtime_dir = get_managed_java_dir(&app, &name)?;
+  let record = load_or_fetch_managed_java_record(&app, &runtime_dir).await?;
+  let invalid_files = find_invalid_runtime_files(&runtime_dir, &record)?;
+
+  if invalid_files.is_empty() {
+    // links and executable bits are restored while refreshing
+    refresh_and_update_javas(&app).await;
+    return Ok(());
+  }
 
+  let download_params =
+    build_runtime_file_download_params(&runtime_dir, &record, Some(invalid_files.as_slice()));
   schedule_progressive_task_group(
     app,
-    format!("mojang-java?{}", version),
+    format!("mojang-java?{}", record.major_version),
     download_params,
     true,
   )
@@ -248,6 +323,14 @@ pub async fn download_mojang_java(app: AppHandle, version: String) -> LXMCLResul
   Ok(())
 }
 
+#[tauri::command]
+pub async fn remove_managed_java(app: AppHandle, name: String) -> LXMCLResult<()> {
+  let runtime_dir = get_managed_java_dir(&app, &name)?;
+  fs::remove_dir_all(&runtime_dir).map_err(|_| LauncherConfigError::FileDeletionFailed)?;
+  refresh_and_update_javas(&app).await;
+  Ok(())
+}
+
 #[tauri::command]
 pub async fn check_game_directory(app: AppHandle, dir: String) -> LXMCLResult<String> {
   let local_game_directories: Vec<_>;
@@ -327,6 +410,32 @@ pub async fn clear_download_cache(app: AppHandle) -> LXMCLResult<()> {
   Ok(())
 }
 
+#[tauri::command]
+pub async fn clean_shared_store(app: AppHandle) -> LXMCLResult<SharedStoreCleanupResult> {
+  let launcher_config = app.state::<Mutex<LauncherConfig>>();
+  let monitor = app.state::<Pin<Box<TaskMonitor>>>();
+
+  // files being downloaded are not yet linked into game directories
+  if monitor.has_active_download_tasks() {
+    return Err(LauncherConfigError::HasActiveDownloadTasks.into());
+  }
+
+  // nothing is added to (or linked from) a disabled store, it is left as is
+  let Some(store_dir) = get_shared_store_dir(&app) else {
+    return Ok(SharedStoreCleanupResult::default());
+  };
+  let game_dirs = launcher_config
+    .lock()?
+    .local_game_directories
+    .iter()
+    .map(|game_dir| game_dir.dir.clone())
+    .collect::<Vec<_>>();
+
+  // hashing the libraries may take a while
+  tauri::async_runtime::spawn_blocking(move || remove_unreferenced_objects(&store_dir, &game_dirs))
+    .await?
+}
+
 #[tauri::command]
 pub async fn check_launcher_update(app: AppHandle) -> LXMCLResult<VersionMetaInfo> {
   let config_binding = app.state::<Mutex<LauncherConfig>>();
@@ -340,30 +449,19 @@ pub async fn check_launcher_update(app: AppHandle) -> LXMCLResult<VersionMetaInf
     return Ok(VersionMetaInfo::default());
   }
 
-  if let Ok(Some((new_version, fname, release_notes, published_at))) =
-    fetch_latest_version(&app).await
-  {
-    if let (Ok(current), Ok(latest)) = (
-      semver::Version::parse(&current_version),
-      semver::Version::parse(&new_version),
-    ) {
-      return Ok(match latest.cmp(&current) {
-        std::cmp::Ordering::Greater => VersionMetaInfo {
-          version: new_version,
-          file_name: fname,
-          release_notes,
-          published_at,
-        },
-        std::cmp::Ordering::Equal => VersionMetaInfo {
-          version: "up2date".to_string(),
-          ..Default::default()
-        },
-        std::cmp::Ordering::Less => VersionMetaInfo::default(),
-      });
-    }
-  }
-
-  Ok(VersionMetaInfo::default())
+  Ok(match fetch_latest_version(&app, &current_version).await {
+    Ok(Some((new_version, fname, release_notes, published_at))) => VersionMetaInfo {
+      version: new_version,
+      file_name: fname,
+      release_notes,
+      published_at,
+    },
+    Ok(None) => VersionMetaInfo {
+      version: "up2date".to_string(),
+      ..Default::default()
+    },
+    Err(_) => VersionMetaInfo::default(),
+  })
 }
 
 #[tauri::command]
@@ -378,10 +476,12 @@ pub async fn download_launcher_update(app: AppHandle, version: VersionMetaInfo)
 
 #[tauri::command]
 pub async fn install_launcher_update(
-  _app: AppHandle,
-  _downloaded_filename: String,
+  app: AppHandle,
+  downloaded_filename: String,
   _restart: bool,
 ) -> LXMCLResult<()> {
+  verify_update_signature(&app, &downloaded_filename)?;
+
   #[cfg(target_os = "windows")]
   {
     return Ok(());
@@ -392,6 +492,6 @@ pub async fn install_launcher_update(
   }
   #[cfg(target_os = "linux")]
   {
-    return Ok(());
+    return install_update_linux(&app, downloaded_filename, _restart).await;
   }
 }
@@ -1,3 +1,18 @@
 pub const LAUNCHER_CFG_FILE_NAME: &str = "sjmcl.conf.json";
+// kept in the directory of a runtime downloaded from Mojang, see `ManagedJavaRecord`
+pub const MANAGED_JAVA_RECORD_FILE_NAME: &str = "sjmcl.runtime.json";
+
+// Mojang Java runtime components with their major versions, in ascending order.
+pub const MOJANG_JAVA_COMPONENTS: [(i32, &str); 5] = [
+  (8, "jre-legacy"),
+  (16, "java-runtime-alpha"),
+  (17, "java-runtime-gamma"),
+  (21, "java-runtime-delta"),
+  (25, "java-runtime-epsilon"),
+];
+
+// minisign public key (the base64 line of the .pub file) to verify the downloaded update packages
+pub const UPDATE_PUBLIC_KEY: &str = env!("LXMCL_UPDATE_PUBLIC_KEY");
+pub const UPDATE_SIGNATURE_SUFFIX: &str = ".minisig";
 
 pub const CONFIG_PARTIAL_UPDATE_EVENT: &str = "config:partial-update";
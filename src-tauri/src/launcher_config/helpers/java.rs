@@ -1,8 +1,14 @@
 use crate::error::{LXMCLError, LXMCLResult};
-use crate::launcher_config::models::{JavaInfo, LauncherConfig};
+use crate::launcher_config::constants::MOJANG_JAVA_COMPONENTS;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::launcher_config::helpers::managed_java::restore_mojang_java_runtime_files;
+use crate::launcher_config::helpers::managed_java::{
+  build_runtime_file_download_params, save_managed_java_record,
+};
+use crate::launcher_config::models::{JavaInfo, LauncherConfig, ManagedJavaRecord};
 use crate::resource::helpers::misc::{get_download_api, get_source_priority_list};
 use crate::resource::models::ResourceType;
-use crate::tasks::{download::DownloadParam, PTaskParam};
+use crate::tasks::PTaskParam;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -288,20 +294,26 @@ fn scan_java_paths_in_common_directories(app: &AppHandle) -> Vec<String> {
 
 fn scan_java_paths_in_sjmcl_data_directory(app: &AppHandle) -> Vec<String> {
   let mut java_paths = Vec::new();
-  #[cfg(any(target_os = "linux", target_os = "windows"))]
+  let Ok(runtime_dir) = app.path().app_data_dir().map(|p| p.join("runtime")) else {
+    return java_paths;
+  };
+  #[cfg(any(target_os = "macos", target_os = "linux"))]
   {
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-      java_paths.extend(search_java_homes_in_directory(app_data_dir.join("runtime")));
+    if let Ok(entries) = fs::read_dir(&runtime_dir) {
+      for entry in entries.flatten() {
+        let _ = restore_mojang_java_runtime_files(&entry.path());
+      }
     }
-    // TODO: test on the Linux.
+  }
+  #[cfg(any(target_os = "linux", target_os = "windows"))]
+  {
+    java_paths.extend(search_java_homes_in_directory(runtime_dir));
   }
   #[cfg(target_os = "macos")]
   {
-    if let Ok(rt) = app.path().app_data_dir().map(|p| p.join("runtime")) {
-      for v in [8, 11, 17, 21] {
-        java_paths.extend(search_java_homes_in_mac_java_virtual_machines(
-          rt.join(format!("java-{v}")),
-        ));
+    if let Ok(entries) = fs::read_dir(runtime_dir) {
+      for entry in entries.flatten() {
+        java_paths.extend(search_java_homes_in_mac_java_virtual_machines(entry.path()));
       }
     }
   }
@@ -457,10 +469,12 @@ pub fn parse_java_major_version(full_version: &str) -> (i32, bool) {
   (major_version, is_lts)
 }
 
-pub async fn build_mojang_java_download_params(
+// Fetch the manifest of the first Mojang Java runtime that meets the major version and is
+// available on this platform.
+pub async fn fetch_mojang_java_record(
   app: &AppHandle,
-  version: &str,
-) -> LXMCLResult<Vec<PTaskParam>> {
+  major_version: i32,
+) -> LXMCLResult<ManagedJavaRecord> {
   let config = app.state::<Mutex<LauncherConfig>>().lock()?.clone();
   let client = app.state::<reqwest::Client>();
 
@@ -473,12 +487,6 @@ pub async fn build_mojang_java_download_params(
     ("linux", "x86_64") => "linux",
     _ => "linux-i386",
   };
-  let runtime_type = match version {
-    "8" => "jre-legacy",
-    "21" => "java-runtime-delta",
-    _ => "java-runtime-gamma",
-  };
-
   let priority_list = get_source_priority_list(&config);
   let mut json: Option<Value> = None;
 
@@ -495,32 +503,49 @@ pub async fn build_mojang_java_download_params(
 
   let json =
     json.ok_or_else(|| LXMCLError("Failed to fetch Mojang Java runtime manifest".into()))?;
-  let manifest_url = json[platform][runtime_type][0]["manifest"]["url"]
-    .as_str()
-    .ok_or_else(|| LXMCLError("Failed to parse manifest URL".into()))?;
+  let (runtime_version, component, runtime) = MOJANG_JAVA_COMPONENTS
+    .iter()
+    .filter(|(version, _)| *version >= major_version)
+    .find_map(|(version, component)| {
+      let runtime = &json[platform][component][0];
+      runtime["manifest"]["url"]
+        .is_string()
+        .then_some((*version, *component, runtime))
+    })
+    .ok_or_else(|| LXMCLError("No Mojang Java runtime meets the requirement".into()))?;
+  let manifest_url = runtime["manifest"]["url"].as_str().unwrap_or_default();
 
   let manifest: Value = client.get(manifest_url).send().await?.json().await?;
+  if !manifest["files"].is_object() {
+    return Err(LXMCLError("Invalid files data".into()));
+  }
+
+  Ok(ManagedJavaRecord {
+    component: component.to_string(),
+    major_version: runtime_version,
+    version_name: runtime["version"]["name"]
+      .as_str()
+      .unwrap_or_default()
+      .to_string(),
+    platform: platform.to_string(),
+    manifest,
+  })
+}
+
+// Build download params of the first Mojang Java runtime that meets the major version and is
+// available on this platform, returns the major version of that runtime along with them.
+pub async fn build_mojang_java_download_params(
+  app: &AppHandle,
+  major_version: i32,
+) -> LXMCLResult<(i32, Vec<PTaskParam>)> {
+  let record = fetch_mojang_java_record(app, major_version).await?;
   let runtime_dir = app.path().resolve(
-    format!("runtime/java-{}", version),
+    format!("runtime/java-{}", record.major_version),
     tauri::path::BaseDirectory::AppData,
   )?;
+  fs::create_dir_all(&runtime_dir)?;
+  save_managed_java_record(&runtime_dir, &record)?;
 
-  let download_params: Vec<_> = manifest["files"]
-    .as_object()
-    .ok_or_else(|| LXMCLError("Invalid files data".into()))?
-    .iter()
-    .filter_map(|(path, info)| {
-      let raw = info["downloads"]["raw"].as_object()?;
-      let (url, sha1) = (raw["url"].as_str()?, raw["sha1"].as_str()?);
-
-      Some(PTaskParam::Download(DownloadParam {
-        src: url.parse().ok()?,
-        dest: runtime_dir.join(path),
-        filename: None,
-        sha1: Some(sha1.into()),
-      }))
-    })
-    .collect();
-
-  Ok(download_params)
+  let download_params = build_runtime_file_download_params(&runtime_dir, &record, None);
+  Ok((record.major_version, download_params))
 }
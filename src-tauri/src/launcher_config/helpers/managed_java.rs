@@ -0,0 +1,274 @@
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::instance::helpers::client_json::McClientInfo;
+use crate::instance::helpers::misc::get_instance_game_config;
+use crate::instance::models::misc::Instance;
+use crate::launch::helpers::jre_selector::{find_suitable_java, get_required_java_version};
+use crate::launcher_config::constants::{MANAGED_JAVA_RECORD_FILE_NAME, MOJANG_JAVA_COMPONENTS};
+use crate::launcher_config::helpers::java::{fetch_mojang_java_record, parse_java_major_version};
+use crate::launcher_config::models::{JavaInfo, LauncherConfigError, ManagedJavaRecord};
+use crate::storage::load_json_async;
+use crate::tasks::{download::DownloadParam, PTaskParam};
+use crate::utils::fs::validate_sha1;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+#[cfg(target_os = "windows")]
+const JAVA_EXEC_SUFFIX: &str = "bin/java.exe";
+#[cfg(not(target_os = "windows"))]
+const JAVA_EXEC_SUFFIX: &str = "bin/java";
+
+pub fn get_managed_java_root_dir(app: &AppHandle) -> LXMCLResult<PathBuf> {
+  Ok(app.path().app_data_dir()?.join("runtime"))
+}
+
+// Resolve the directory of a managed runtime by its name, e.g. java-17.
+pub fn get_managed_java_dir(app: &AppHandle, name: &str) -> LXMCLResult<PathBuf> {
+  let runtime_dir = get_managed_java_root_dir(app)?.join(name);
+  if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') || !runtime_dir.is_dir()
+  {
+    return Err(LauncherConfigError::JavaRuntimeNotFound.into());
+  }
+  Ok(runtime_dir)
+}
+
+pub fn load_managed_java_record(runtime_dir: &Path) -> LXMCLResult<ManagedJavaRecord> {
+  let content = fs::read(runtime_dir.join(MANAGED_JAVA_RECORD_FILE_NAME))?;
+  Ok(serde_json::from_slice(&content)?)
+}
+
+pub fn save_managed_java_record(runtime_dir: &Path, record: &ManagedJavaRecord) -> LXMCLResult<()> {
+  fs::write(
+    runtime_dir.join(MANAGED_JAVA_RECORD_FILE_NAME),
+    serde_json::to_vec(record)?,
+  )?;
+  Ok(())
+}
+
+// The record of runtimes downloaded by earlier launcher versions is missing, identify the runtime
+// by its `release` file and fetch the manifest again. the directory name is not reliable, as it
+// was named after the requested version (e.g. java-11 holds java-runtime-gamma).
+pub async fn load_or_fetch_managed_java_record(
+  app: &AppHandle,
+  runtime_dir: &Path,
+) -> LXMCLResult<ManagedJavaRecord> {
+  if let Ok(record) = load_managed_java_record(runtime_dir) {
+    return Ok(record);
+  }
+  let (major_version, version_name) = read_runtime_release_version(runtime_dir)
+    .ok_or(LauncherConfigError::JavaRuntimeUnidentified)?;
+  if get_mojang_java_component(major_version).is_none() {
+    return Err(LauncherConfigError::JavaRuntimeUnidentified.into());
+  }
+  let record = fetch_mojang_java_record(app, major_version).await?;
+  // the manifest only lists the latest build, older files cannot be checked against it
+  if record.major_version != major_version || record.version_name != version_name {
+    return Err(LauncherConfigError::JavaRuntimeUnidentified.into());
+  }
+  save_managed_java_record(runtime_dir, &record)?;
+  Ok(record)
+}
+
+fn get_mojang_java_component(major_version: i32) -> Option<&'static str> {
+  MOJANG_JAVA_COMPONENTS
+    .iter()
+    .find(|(version, _)| *version == major_version)
+    .map(|(_, component)| *component)
+}
+
+// the major version and the version name (as in the Mojang manifest, e.g. 17.0.8 or 8u51)
+// from the `release` file of the runtime.
+fn read_runtime_release_version(runtime_dir: &Path) -> Option<(i32, String)> {
+  let content = ["release", "jre.bundle/Contents/Home/release"]
+    .iter()
+    .find_map(|path| fs::read_to_string(runtime_dir.join(path)).ok())?;
+  let full_version = content
+    .lines()
+    .find_map(|line| line.strip_prefix("JAVA_VERSION="))?
+    .trim()
+    .trim_matches('"');
+  let (major_version, _) = parse_java_major_version(full_version);
+  let version_name = match full_version.strip_prefix("1.8.0_") {
+    Some(update) => format!("8u{update}"),
+    None => full_version.to_string(),
+  };
+  (major_version > 0).then_some((major_version, version_name))
+}
+
+// Used to list the managed runtimes, also for those without a record.
+pub fn get_managed_java_record_or_default(runtime_dir: &Path) -> Option<ManagedJavaRecord> {
+  if let Ok(record) = load_managed_java_record(runtime_dir) {
+    return Some(record);
+  }
+  let (major_version, _) = read_runtime_release_version(runtime_dir)?;
+  Some(ManagedJavaRecord {
+    component: get_mojang_java_component(major_version)
+      .unwrap_or_default()
+      .to_string(),
+    major_version,
+    ..Default::default()
+  })
+}
+
+pub fn get_managed_java_exec_path(runtime_dir: &Path, record: &ManagedJavaRecord) -> String {
+  let exec_path = match record.manifest["files"].as_object() {
+    Some(files) => files
+      .keys()
+      .filter(|path| path.ends_with(JAVA_EXEC_SUFFIX))
+      .min_by_key(|path| path.len())
+      .map(|path| runtime_dir.join(path)),
+    // guess the layout of runtimes without a record
+    None => [
+      JAVA_EXEC_SUFFIX.to_string(),
+      format!("jre.bundle/Contents/Home/{JAVA_EXEC_SUFFIX}"),
+    ]
+    .iter()
+    .map(|path| runtime_dir.join(path))
+    .find(|path| path.is_file()),
+  };
+  let exec_path = exec_path.and_then(|path| fs::canonicalize(path).ok());
+
+  #[cfg(target_os = "windows")]
+  {
+    exec_path
+      .map(|path| {
+        path
+          .to_string_lossy()
+          .trim_start_matches(r"\\?\")
+          .to_string()
+      })
+      .unwrap_or_default()
+  }
+
+  #[cfg(not(target_os = "windows"))]
+  {
+    exec_path
+      .map(|path| path.to_string_lossy().into_owned())
+      .unwrap_or_default()
+  }
+}
+
+// Build download params of the files in the manifest, or only the given ones (for repairing).
+pub fn build_runtime_file_download_params(
+  runtime_dir: &Path,
+  record: &ManagedJavaRecord,
+  only_paths: Option<&[String]>,
+) -> Vec<PTaskParam> {
+  let Some(files) = record.manifest["files"].as_object() else {
+    return Vec::new();
+  };
+  files
+    .iter()
+    .filter(|(path, _)| only_paths.map_or(true, |paths| paths.contains(*path)))
+    .filter_map(|(path, info)| {
+      let raw = info["downloads"]["raw"].as_object()?;
+      let (url, sha1) = (raw["url"].as_str()?, raw["sha1"].as_str()?);
+
+      Some(PTaskParam::Download(DownloadParam {
+        src: url.parse().ok()?,
+        dest: runtime_dir.join(path),
+        filename: None,
+        sha1: Some(sha1.into()),
+        fallback_srcs: vec![],
+      }))
+    })
+    .collect()
+}
+
+// Check the files against the SHA-1s in the manifest, returns paths of missing or corrupted ones.
+pub fn find_invalid_runtime_files(
+  runtime_dir: &Path,
+  record: &ManagedJavaRecord,
+) -> LXMCLResult<Vec<String>> {
+  let files = record.manifest["files"]
+    .as_object()
+    .ok_or_else(|| LXMCLError("Invalid files data".into()))?;
+
+  let mut invalid_files: Vec<String> = files
+    .iter()
+    .filter(|(_, info)| info["type"] == "file")
+    .filter(|(path, info)| {
+      let sha1 = info["downloads"]["raw"]["sha1"]
+        .as_str()
+        .unwrap_or_default();
+      validate_sha1(runtime_dir.join(path), sha1.to_string()).is_err()
+    })
+    .map(|(path, _)| path.clone())
+    .collect();
+  invalid_files.sort();
+  Ok(invalid_files)
+}
+
+// Links and executable bits in the manifest can't be handled by the download tasks,
+// restore them once the files of the runtime are downloaded.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub fn restore_mojang_java_runtime_files(runtime_dir: &Path) -> LXMCLResult<()> {
+  use std::os::unix::fs::{symlink, PermissionsExt};
+
+  let record = load_managed_java_record(runtime_dir)?;
+  let files = record.manifest["files"]
+    .as_object()
+    .ok_or_else(|| LXMCLError("Invalid files data".into()))?;
+
+  for (path, info) in files {
+    let path = runtime_dir.join(path);
+    match info["type"].as_str() {
+      Some("link") => {
+        let Some(target) = info["target"].as_str() else {
+          continue;
+        };
+        if fs::symlink_metadata(&path).is_err() {
+          if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+          }
+          symlink(target, &path)?;
+        }
+      }
+      Some("file") if info["executable"].as_bool() == Some(true) => {
+        let Ok(metadata) = fs::metadata(&path) else {
+          continue;
+        };
+        let mut permissions = metadata.permissions();
+        if permissions.mode() & 0o111 != 0o111 {
+          permissions.set_mode(permissions.mode() | 0o755);
+          fs::set_permissions(&path, permissions)?;
+        }
+      }
+      _ => {}
+    }
+  }
+  Ok(())
+}
+
+// Java executables that the instances would launch with, used to flag unused managed runtimes.
+pub async fn get_java_exec_paths_in_use(app: &AppHandle) -> LXMCLResult<HashSet<String>> {
+  let instances: Vec<Instance> = {
+    let binding = app.state::<Mutex<HashMap<String, Instance>>>();
+    let state = binding.lock()?;
+    state.values().cloned().collect()
+  };
+  let java_list = app.state::<Mutex<Vec<JavaInfo>>>().lock()?.clone();
+
+  let mut exec_paths = HashSet::new();
+  for instance in instances {
+    let game_java = get_instance_game_config(app, &instance).game_java;
+    if !game_java.auto {
+      exec_paths.insert(game_java.exec_path);
+      continue;
+    }
+    let client_path = instance
+      .version_path
+      .join(format!("{}.json", instance.name));
+    let client_json_req = load_json_async::<McClientInfo>(&client_path)
+      .await
+      .map(|client_info| client_info.java_version.major_version)
+      .unwrap_or_default();
+    let min_version_req = get_required_java_version(app, &instance, client_json_req, false).await;
+    if let Some(java) = find_suitable_java(&java_list, min_version_req) {
+      exec_paths.insert(java.exec_path);
+    }
+  }
+  Ok(exec_paths)
+}
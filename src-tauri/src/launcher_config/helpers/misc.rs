@@ -31,6 +31,13 @@ impl LauncherConfig {
       fs::create_dir_all(&self.download.cache.directory)?;
     }
 
+    // Set the default shared store directory if unset
+    if self.download.shared_store.directory.as_os_str().is_empty() {
+      self.download.shared_store.directory = app
+        .path()
+        .resolve::<PathBuf>("SharedStore".into(), BaseDirectory::AppData)?;
+    }
+
     // Random pick custom background image if enabled
     if self.appearance.background.random_custom {
       let app_handle = app.clone();
@@ -1,3 +1,5 @@
 pub mod java;
+pub mod managed_java;
 pub mod misc;
+pub mod shared_store;
 pub mod updater;
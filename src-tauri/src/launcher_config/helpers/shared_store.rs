@@ -0,0 +1,215 @@
+use crate::error::LXMCLResult;
+use crate::launcher_config::models::{LauncherConfig, SharedStoreCleanupResult};
+use sha1::{Digest, Sha1};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+// The shared store keeps one copy of each library and asset object across all game directories,
+// at `<store>/objects/<first two chars of sha1>/<sha1>`. Files in game directories are reflinked
+// (copy-on-write) from it, or copied if the file system does not support it. Hardlinks are not
+// used, as a file modified in place in a game directory would corrupt the stored object.
+
+// returns the store directory if the shared store is enabled.
+pub fn get_shared_store_dir(app: &AppHandle) -> Option<PathBuf> {
+  let config_binding = app.state::<Mutex<LauncherConfig>>();
+  let config_state = config_binding.lock().ok()?;
+  let shared_store = &config_state.download.shared_store;
+  if shared_store.enabled && !shared_store.directory.as_os_str().is_empty() {
+    Some(shared_store.directory.clone())
+  } else {
+    None
+  }
+}
+
+fn get_object_path(store_dir: &Path, sha1: &str) -> Option<PathBuf> {
+  if sha1.len() != 40 || !sha1.chars().all(|c| c.is_ascii_hexdigit()) {
+    return None;
+  }
+  let sha1 = sha1.to_ascii_lowercase();
+  Some(store_dir.join("objects").join(&sha1[..2]).join(sha1))
+}
+
+// copy to a temporary file first, so no partial file is left at `dest`
+fn copy_object(src: &Path, dest: &Path) -> io::Result<()> {
+  let mut tmp_path = dest.as_os_str().to_owned();
+  tmp_path.push(".tmp");
+  let tmp_path = PathBuf::from(tmp_path);
+  reflink_copy::reflink_or_copy(src, &tmp_path)?;
+  fs::rename(&tmp_path, dest)
+}
+
+fn link_object(store_dir: &Path, sha1: &str, dest: &Path) -> LXMCLResult<bool> {
+  let Some(object_path) = get_object_path(store_dir, sha1) else {
+    return Ok(false);
+  };
+  if !object_path.is_file() {
+    return Ok(false);
+  }
+  // objects hardlinked by earlier versions may have been modified through a game directory
+  if calculate_sha1(&object_path)? != sha1.to_ascii_lowercase() {
+    // the object is corrupted, drop it and let the caller download it again
+    fs::remove_file(&object_path)?;
+    return Ok(false);
+  }
+
+  if let Some(parent) = dest.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  if dest.exists() {
+    fs::remove_file(dest)?;
+  }
+  copy_object(&object_path, dest)?;
+  Ok(true)
+}
+
+fn store_object(store_dir: &Path, sha1: &str, src: &Path) -> LXMCLResult<()> {
+  let Some(object_path) = get_object_path(store_dir, sha1) else {
+    return Ok(());
+  };
+  if object_path.exists() {
+    return Ok(());
+  }
+  fs::create_dir_all(object_path.parent().unwrap())?;
+  copy_object(src, &object_path)?;
+  Ok(())
+}
+
+// place the object with the given sha1 at `dest` if it exists in the store and is intact.
+// returns Ok(false) if the store does not contain the object.
+pub async fn link_from_shared_store(
+  store_dir: &Path,
+  sha1: &str,
+  dest: &Path,
+) -> LXMCLResult<bool> {
+  let (store_dir, sha1, dest) = (
+    store_dir.to_path_buf(),
+    sha1.to_string(),
+    dest.to_path_buf(),
+  );
+  // hashing and copying may take a while, keep them off the async runtime
+  tauri::async_runtime::spawn_blocking(move || link_object(&store_dir, &sha1, &dest)).await?
+}
+
+// add a file, whose sha1 has been validated, to the store (no-op if already stored).
+pub async fn add_to_shared_store(store_dir: &Path, sha1: &str, src: &Path) -> LXMCLResult<()> {
+  let (store_dir, sha1, src) = (store_dir.to_path_buf(), sha1.to_string(), src.to_path_buf());
+  tauri::async_runtime::spawn_blocking(move || store_object(&store_dir, &sha1, &src)).await?
+}
+
+// add a downloaded library or asset object to the store, if the shared store is enabled.
+// other files (e.g. mods, game jars) are left as is.
+pub async fn add_downloaded_file_to_shared_store(
+  app: &AppHandle,
+  sha1: &str,
+  path: &Path,
+) -> LXMCLResult<()> {
+  let Some(store_dir) = get_shared_store_dir(app) else {
+    return Ok(());
+  };
+  let is_shareable = {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let config_state = config_binding.lock()?;
+    config_state.local_game_directories.iter().any(|game_dir| {
+      path.starts_with(game_dir.dir.join("libraries"))
+        || path.starts_with(game_dir.dir.join("assets").join("objects"))
+    })
+  };
+  if is_shareable {
+    add_to_shared_store(&store_dir, sha1, path).await?;
+  }
+  Ok(())
+}
+
+fn calculate_sha1(path: &Path) -> LXMCLResult<String> {
+  let mut file = fs::File::open(path)?;
+  let mut hasher = Sha1::new();
+  io::copy(&mut file, &mut hasher)?;
+  Ok(hex::encode(hasher.finalize()))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return;
+  };
+  for entry in entries.filter_map(Result::ok) {
+    let path = entry.path();
+    match entry.file_type() {
+      Ok(file_type) if file_type.is_dir() => collect_files(&path, files),
+      Ok(file_type) if file_type.is_file() => files.push(path),
+      _ => {}
+    }
+  }
+}
+
+// remove the objects which are not used by any file in the given game directories.
+pub fn remove_unreferenced_objects(
+  store_dir: &Path,
+  game_dirs: &[PathBuf],
+) -> LXMCLResult<SharedStoreCleanupResult> {
+  let mut object_files = Vec::new();
+  collect_files(&store_dir.join("objects"), &mut object_files);
+
+  let mut result = SharedStoreCleanupResult::default();
+  let mut unused_objects: HashMap<String, (PathBuf, u64)> = HashMap::new();
+  for path in object_files {
+    let name = path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    let size = fs::metadata(&path)
+      .map(|metadata| metadata.len())
+      .unwrap_or(0);
+    if name.ends_with(".tmp") {
+      // left by an interrupted copy
+      if fs::remove_file(&path).is_ok() {
+        result.freed_size += size;
+      }
+      continue;
+    }
+    unused_objects.insert(name, (path, size));
+  }
+
+  // asset objects are named by their sha1
+  for game_dir in game_dirs {
+    let mut asset_files = Vec::new();
+    collect_files(&game_dir.join("assets").join("objects"), &mut asset_files);
+    for path in asset_files {
+      if let Some(name) = path.file_name() {
+        unused_objects.remove(name.to_string_lossy().as_ref());
+      }
+    }
+  }
+
+  // libraries have to be hashed, only those having the same size as an unused object are checked
+  let unused_sizes: HashSet<u64> = unused_objects.values().map(|(_, size)| *size).collect();
+  for game_dir in game_dirs {
+    if unused_objects.is_empty() {
+      break;
+    }
+    let mut library_files = Vec::new();
+    collect_files(&game_dir.join("libraries"), &mut library_files);
+    for path in library_files {
+      let size = fs::metadata(&path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+      if !unused_sizes.contains(&size) {
+        continue;
+      }
+      if let Ok(sha1) = calculate_sha1(&path) {
+        unused_objects.remove(&sha1);
+      }
+    }
+  }
+
+  for (path, size) in unused_objects.into_values() {
+    if fs::remove_file(&path).is_ok() {
+      result.removed_count += 1;
+      result.freed_size += size;
+    }
+  }
+  Ok(result)
+}
@@ -1,25 +1,49 @@
 use crate::error::LXMCLResult;
+use crate::launcher_config::constants::{UPDATE_PUBLIC_KEY, UPDATE_SIGNATURE_SUFFIX};
 use crate::launcher_config::models::{LauncherConfig, LauncherConfigError};
 use crate::tasks::commands::schedule_progressive_task_group;
 use crate::tasks::download::DownloadParam;
 use crate::tasks::PTaskParam;
+use minisign_verify::{PublicKey, Signature};
 use serde_json::Value;
+use std::fs;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_http::reqwest;
 
-type SourceTuple = (&'static str, &'static str, fn(&str, &str) -> String);
+type SourceTuple = (&'static str, fn(&str, &str) -> String);
 const SOURCES: [SourceTuple; 1] = [(
-  "https://api.github.com/repos/Origin173/LXMCL/releases/latest",
-  "tag_name",
-  |ver, fname| {
+  "https://api.github.com/repos/Origin173/LXMCL/releases?per_page=50",
+  |tag, fname| {
     format!(
-      "https://github.com/Origin173/LXMCL/releases/download/v{}/{}",
-      ver, fname
+      "https://github.com/Origin173/LXMCL/releases/download/{}/{}",
+      tag, fname
     )
   },
 )];
 
+// The nightly build is republished under this tag, its assets are named by the build date
+// (e.g. LXMCL_nightly_20250101_linux_x86_64.AppImage), which is used as the version.
+const NIGHTLY_TAG: &str = "nightly";
+
+// Separates the notes of the skipped releases, the frontend splits them again for display.
+const RELEASE_NOTES_SEPARATOR: &str = "\n\n***\n\n";
+
+// Version of the running build, set by CI (e.g. nightly_20250101), or the package version.
+pub fn get_build_version() -> &'static str {
+  option_env!("LXMCL_BUILD_VERSION")
+    .filter(|ver| !ver.is_empty())
+    .unwrap_or(env!("CARGO_PKG_VERSION"))
+}
+
+fn get_release_tag(version: &str) -> String {
+  if version.starts_with(NIGHTLY_TAG) {
+    NIGHTLY_TAG.to_string()
+  } else {
+    format!("v{}", version)
+  }
+}
+
 // Generate the new version filename on remote origin according to the current os, arch and is_portable
 fn build_resource_filename(ver: &str, os: &str, arch: &str, is_portable: bool) -> String {
   let arch = if arch == "x86" { "i686" } else { arch };
@@ -31,7 +55,13 @@ fn build_resource_filename(ver: &str, os: &str, arch: &str, is_portable: bool) -
         ".msi"
       }
     }
-    "linux" => ".AppImage",
+    "linux" => {
+      if is_portable {
+        "_portable.tar.gz"
+      } else {
+        ".AppImage"
+      }
+    }
     "macos" => ".app.tar.gz",
     _ => "",
   };
@@ -50,17 +80,83 @@ fn build_local_new_filename(old_name: &str, old_version: &str, new_version: &str
   }
 }
 
+// Find the newest release on the stable or beta channel, with the notes of all skipped releases.
+fn find_semver_update(
+  releases: &[Value],
+  current_version: &str,
+  allow_prerelease: bool,
+) -> Option<(String, String, String)> {
+  let current = semver::Version::parse(current_version).ok()?;
+  let mut newer_releases: Vec<(semver::Version, &Value)> = releases
+    .iter()
+    .filter(|release| release["draft"] != true)
+    .filter(|release| allow_prerelease || release["prerelease"] != true)
+    .filter_map(|release| {
+      let tag = release["tag_name"].as_str()?;
+      let version = semver::Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+      (allow_prerelease || version.pre.is_empty()).then_some((version, release))
+    })
+    .filter(|(version, _)| *version > current)
+    .collect();
+  newer_releases.sort_by(|a, b| b.0.cmp(&a.0));
+
+  let (latest_version, latest_release) = newer_releases.first()?;
+  let release_notes = newer_releases
+    .iter()
+    .filter_map(|(_, release)| release["body"].as_str())
+    .map(str::trim)
+    .filter(|notes| !notes.is_empty())
+    .collect::<Vec<_>>()
+    .join(RELEASE_NOTES_SEPARATOR);
+  let published_at = latest_release["published_at"]
+    .as_str()
+    .unwrap_or_default()
+    .to_string();
+
+  Some((latest_version.to_string(), release_notes, published_at))
+}
+
+// The nightly build is offered unless the running one is the same or a later nightly build.
+fn find_nightly_update(
+  releases: &[Value],
+  current_version: &str,
+) -> Option<(String, String, String)> {
+  let release = releases
+    .iter()
+    .find(|release| release["tag_name"] == NIGHTLY_TAG)?;
+  let version = release["assets"]
+    .as_array()?
+    .iter()
+    .filter_map(|asset| asset["name"].as_str())
+    .find_map(|name| name.strip_prefix("LXMCL_nightly_")?.split('_').next())
+    .map(|date| format!("{}_{}", NIGHTLY_TAG, date))?;
+  if current_version.starts_with(NIGHTLY_TAG) && current_version >= version.as_str() {
+    return None;
+  }
+
+  let release_notes = release["body"].as_str().unwrap_or_default().to_string();
+  let published_at = release["published_at"]
+    .as_str()
+    .unwrap_or_default()
+    .to_string();
+
+  Some((version, release_notes, published_at))
+}
+
+// Returns None if the running launcher is up to date on the selected update channel.
 pub async fn fetch_latest_version(
   app: &AppHandle,
+  current_version: &str,
 ) -> LXMCLResult<Option<(String, String, String, String)>> {
   let config_binding = app.state::<Mutex<LauncherConfig>>();
-  let (os, arch, is_portable, is_china_mainland_ip) = {
+  let (os, arch, is_portable, is_china_mainland_ip, channel) = {
     let config_state = config_binding.lock()?;
     (
       config_state.basic_info.os_type.clone(),
       config_state.basic_info.arch.clone(),
       config_state.basic_info.is_portable,
       config_state.basic_info.is_china_mainland_ip,
+      config_state.general.functionality.update_channel.clone(),
     )
   };
   let client = app.state::<reqwest::Client>();
@@ -71,28 +167,26 @@ pub async fn fetch_latest_version(
     sources.reverse();
   }
 
-  for (endpoint, field, _) in sources {
+  for (endpoint, _) in sources {
     if let Ok(resp) = client.get(endpoint).send().await {
-      if let Ok(j) = resp.json::<Value>().await {
-        if let Some(mut ver) = j.get(field).and_then(|v| v.as_str()).map(|s| s.to_string()) {
-          if ver.starts_with('v') {
-            ver.remove(0);
-          }
-          let fname = build_resource_filename(&ver, os.as_str(), arch.as_str(), is_portable);
-
-          let release_notes = j
-            .get("body")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-            .to_string();
-          let published_at = j
-            .get("published_at")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-            .to_string();
-
-          return Ok(Some((ver, fname, release_notes, published_at)));
-        }
+      if let Ok(releases) = resp.json::<Vec<Value>>().await {
+        let update = match channel.as_str() {
+          "nightly" => find_nightly_update(&releases, get_build_version()),
+          "beta" => find_semver_update(&releases, current_version, true),
+          _ => find_semver_update(&releases, current_version, false),
+        };
+        let Some((ver, release_notes, published_at)) = update else {
+          return Ok(None);
+        };
+
+        // Linux packages (deb, rpm, AUR) are updated by the package manager, leave the name empty
+        let fname = if os == "linux" && !is_portable && std::env::var_os("APPIMAGE").is_none() {
+          String::new()
+        } else {
+          build_resource_filename(&ver, os.as_str(), arch.as_str(), is_portable)
+        };
+
+        return Ok(Some((ver, fname, release_notes, published_at)));
       }
     }
   }
@@ -121,20 +215,32 @@ pub async fn download_target_version(
     sources.reverse();
   }
 
-  for (endpoint, _, mk_url) in sources {
+  for (endpoint, mk_url) in sources {
     if let Ok(resp) = client.get(endpoint).send().await {
       if resp.status().is_success() {
-        let url = mk_url(&version, &fname);
+        let tag = get_release_tag(&version);
+        // the package goes first, the frontend installs the file of the first task in the group
+        let params = [
+          fname.clone(),
+          format!("{}{}", fname, UPDATE_SIGNATURE_SUFFIX),
+        ]
+        .into_iter()
+        .map(|filename| -> LXMCLResult<PTaskParam> {
+          Ok(PTaskParam::Download(DownloadParam {
+            src: url::Url::parse(&mk_url(&tag, &filename))
+              .map_err(|_| LauncherConfigError::FetchError)?,
+            dest: download_cache_dir.join(&filename),
+            filename: Some(filename),
+            sha1: None,
+            fallback_srcs: vec![],
+          }))
+        })
+        .collect::<LXMCLResult<Vec<_>>>()?;
 
         schedule_progressive_task_group(
           app.clone(),
           format!("launcher-update?{}", fname),
-          vec![PTaskParam::Download(DownloadParam {
-            src: url::Url::parse(&url).map_err(|_| LauncherConfigError::FetchError)?,
-            dest: download_cache_dir.join(&fname),
-            filename: Some(fname),
-            sha1: None,
-          })],
+          params,
           true,
         )
         .await?;
@@ -147,6 +253,35 @@ pub async fn download_target_version(
   Err(LauncherConfigError::FetchError.into())
 }
 
+// Check the downloaded package against its minisign signature with the embedded public key.
+pub fn verify_update_signature(app: &AppHandle, downloaded_filename: &str) -> LXMCLResult<()> {
+  let download_cache_dir = {
+    let config_binding = app.state::<Mutex<LauncherConfig>>();
+    let config_state = config_binding.lock()?;
+    config_state.download.cache.directory.clone()
+  };
+  let package_path = download_cache_dir.join(downloaded_filename);
+  let signature_path = download_cache_dir.join(format!(
+    "{}{}",
+    downloaded_filename, UPDATE_SIGNATURE_SUFFIX
+  ));
+
+  let verify = || -> Option<()> {
+    let public_key = PublicKey::from_base64(UPDATE_PUBLIC_KEY.lines().last()?.trim()).ok()?;
+    let signature = Signature::decode(&fs::read_to_string(&signature_path).ok()?).ok()?;
+    public_key
+      .verify(&fs::read(&package_path).ok()?, &signature, false)
+      .ok()?;
+    // the trusted comment is also signed, it binds the signature to the file name (and version)
+    signature
+      .trusted_comment()
+      .contains(&format!("file:{}", downloaded_filename))
+      .then_some(())
+  };
+
+  verify().ok_or_else(|| LauncherConfigError::UpdateSignatureInvalid.into())
+}
+
 #[cfg(target_os = "windows")]
 pub async fn install_update_windows(
   app: &AppHandle,
@@ -354,3 +489,173 @@ rm -rf "$TMPDIR" || true
   }
   Ok(())
 }
+
+#[cfg(target_os = "linux")]
+pub async fn install_update_linux(
+  app: &AppHandle,
+  downloaded_filename: String,
+  restart: bool,
+) -> LXMCLResult<()> {
+  use crate::error::LXMCLError;
+  use std::os::unix::fs::PermissionsExt;
+  use std::path::PathBuf;
+
+  let config_binding = app.state::<Mutex<LauncherConfig>>();
+  let (downloaded_path, new_version, is_portable) = {
+    let config_state = config_binding.lock()?;
+    (
+      config_state
+        .download
+        .cache
+        .directory
+        .join(&downloaded_filename),
+      downloaded_filename
+        .split('_')
+        .nth(1)
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| config_state.basic_info.launcher_version.clone()),
+      config_state.basic_info.is_portable,
+    )
+  };
+
+  // current_exe() of an AppImage points into its mount point, the runtime exports the image path.
+  let target = if is_portable {
+    std::env::current_exe()?
+  } else {
+    std::env::var_os("APPIMAGE")
+      .map(PathBuf::from)
+      .ok_or_else(|| LXMCLError("Not running from an AppImage".to_string()))?
+  };
+  let target_dir = target
+    .parent()
+    .ok_or_else(|| LXMCLError("No parent dir for exe".to_string()))?;
+  let target_name = target
+    .file_name()
+    .and_then(|s| s.to_str())
+    .ok_or_else(|| LXMCLError("Invalid exe name".to_string()))?
+    .to_string();
+
+  // stage the new binary beside the old one, so that the swap is a rename on the same file system
+  let staged = target_dir.join(format!(".{}.new", target_name));
+  let backup = target_dir.join(format!(".{}.old", target_name));
+  if is_portable {
+    extract_portable_binary(&downloaded_path, &target_name, &staged)?;
+  } else {
+    fs::copy(&downloaded_path, &staged)?;
+  }
+  let mode = fs::metadata(&target)?.permissions().mode();
+  fs::set_permissions(&staged, fs::Permissions::from_mode(mode | 0o755))?;
+
+  // keep the old binary until the new one passes the self-check
+  let _ = fs::remove_file(&backup);
+  if fs::hard_link(&target, &backup).is_err() {
+    fs::copy(&target, &backup)?;
+  }
+  if let Err(e) = fs::rename(&staged, &target) {
+    let _ = fs::remove_file(&staged);
+    let _ = fs::remove_file(&backup);
+    return Err(e.into());
+  }
+  if !check_binary_version(&target, &new_version).await {
+    fs::rename(&backup, &target)?;
+    return Err(LauncherConfigError::UpdateSelfCheckFailed.into());
+  }
+  let _ = fs::remove_file(&backup);
+  let _ = fs::remove_file(&downloaded_path);
+  let _ = fs::remove_file(format!(
+    "{}{}",
+    downloaded_path.display(),
+    UPDATE_SIGNATURE_SUFFIX
+  ));
+
+  if restart {
+    // wait for the current process to exit, or the single instance plugin would stop the new one
+    let pid = std::process::id().to_string();
+    let _ = build_detached_command("sh")
+      .arg("-c")
+      .arg(r#"while kill -0 "$0" 2>/dev/null; do sleep 0.2; done; exec "$1""#)
+      .arg(&pid)
+      .arg(&target)
+      .spawn()?;
+    app.exit(0);
+  }
+  Ok(())
+}
+
+// The portable tarball contains the launcher binary, possibly inside a top level directory.
+#[cfg(target_os = "linux")]
+fn extract_portable_binary(
+  archive: &std::path::Path,
+  exe_name: &str,
+  dest: &std::path::Path,
+) -> LXMCLResult<()> {
+  use crate::error::LXMCLError;
+
+  let extract_dir = dest.with_extension("extract");
+  let _ = fs::remove_dir_all(&extract_dir);
+  fs::create_dir_all(&extract_dir)?;
+
+  let result = (|| -> LXMCLResult<()> {
+    let status = std::process::Command::new("tar")
+      .arg("-xzf")
+      .arg(archive)
+      .arg("-C")
+      .arg(&extract_dir)
+      .status()?;
+    if !status.success() {
+      return Err(LXMCLError(
+        "Failed to extract the update package".to_string(),
+      ));
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&extract_dir)?.flatten() {
+      let path = entry.path();
+      if path.is_dir() {
+        files.extend(fs::read_dir(&path)?.flatten().map(|e| e.path()));
+      } else {
+        files.push(path);
+      }
+    }
+    // prefer the file named as the running binary
+    let binary = files
+      .into_iter()
+      .filter(|path| path.is_file())
+      .min_by_key(|path| path.file_name().and_then(|s| s.to_str()) != Some(exe_name))
+      .ok_or_else(|| LXMCLError("No launcher binary found in archive".to_string()))?;
+    fs::rename(binary, dest)?;
+    Ok(())
+  })();
+
+  let _ = fs::remove_dir_all(&extract_dir);
+  result
+}
+
+// Run `<binary> --version` and check that the expected version is reported.
+#[cfg(target_os = "linux")]
+async fn check_binary_version(binary: &std::path::Path, expected_version: &str) -> bool {
+  let output = tokio::time::timeout(
+    std::time::Duration::from_secs(30),
+    tokio::process::Command::from(build_detached_command(binary))
+      .arg("--version")
+      .kill_on_drop(true)
+      .output(),
+  )
+  .await;
+  match output {
+    Ok(Ok(output)) => {
+      output.status.success() && String::from_utf8_lossy(&output.stdout).contains(expected_version)
+    }
+    _ => false,
+  }
+}
+
+// Drop the variables exported by the AppImage runtime of the running launcher,
+// the new image must be started with its own.
+#[cfg(target_os = "linux")]
+fn build_detached_command<S: AsRef<std::ffi::OsStr>>(program: S) -> std::process::Command {
+  let mut command = std::process::Command::new(program);
+  for var in ["APPIMAGE", "APPDIR", "ARGV0", "OWD"] {
+    command.env_remove(var);
+  }
+  command
+}
@@ -6,6 +6,7 @@ use crate::utils::sys_info;
 use crate::{APP_DATA_DIR, EXE_DIR, IS_PORTABLE};
 use partial_derive::Partial;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use smart_default::SmartDefault;
 use std::path::PathBuf;
 use strum_macros::Display;
@@ -30,6 +31,28 @@ pub struct JavaInfo {
   pub is_user_added: bool,
 }
 
+// Kept in the directory of a Java runtime downloaded from Mojang, to verify and repair it later.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ManagedJavaRecord {
+  pub component: String, // e.g. java-runtime-gamma
+  pub major_version: i32,
+  pub version_name: String,
+  pub platform: String,
+  pub manifest: Value, // with file list, sha1 and download urls
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedJavaInfo {
+  pub name: String, // directory name under runtime/, e.g. java-17
+  pub component: String,
+  pub major_version: i32,
+  pub version_name: String, // empty if downloaded by an earlier launcher version
+  pub exec_path: String,    // empty if the executable is missing
+  pub is_unused: bool,      // no instance would launch with it
+}
+
 // Info about the latest release version fetched from remote, shown to the user to update.
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -40,6 +63,13 @@ pub struct VersionMetaInfo {
   pub published_at: String,
 }
 
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SharedStoreCleanupResult {
+  pub removed_count: usize,
+  pub freed_size: u64,
+}
+
 // https://github.com/HMCL-dev/HMCL/blob/d9e3816b8edf9e7275e4349d4fc67a5ef2e3c6cf/HMCLCore/src/main/java/org/jackhuang/hmcl/game/ProcessPriority.java#L20
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -117,6 +147,12 @@ structstruck::strike! {
     #[default(LauncherVisiablity::Always)]
     pub launcher_visibility: LauncherVisiablity,
     pub display_game_log: bool,
+    pub world_backup: struct {
+      pub auto_backup_on_exit: bool,
+      #[default = 10]
+      pub max_count: usize, // 0 for unlimited
+      pub max_age_days: u32, // 0 for unlimited
+    },
     pub advanced_options: struct {
       pub enabled: bool,
     },
@@ -230,6 +266,11 @@ structstruck::strike! {
       pub cache: struct {
         pub directory: PathBuf,
       },
+      // content-addressed store of libraries and assets shared by all game directories
+      pub shared_store: struct {
+        pub enabled: bool,
+        pub directory: PathBuf,
+      },
       pub proxy: struct ProxyConfig {
         pub enabled: bool,
         #[default(ProxyType::Http)]
@@ -252,6 +293,8 @@ structstruck::strike! {
         #[default = true]
         pub resource_translation: bool, // only available in zh-Hans
         pub skip_first_screen_options: bool,  // only available in zh-Hans
+        #[default = "stable"]
+        pub update_channel: String, // stable, beta or nightly
       }
     },
     pub global_game_config: GameConfig,
@@ -341,6 +384,10 @@ pub enum LauncherConfigError {
   JavaExecInvalid,
   HasActiveDownloadTasks,
   FileDeletionFailed,
+  JavaRuntimeNotFound,
+  JavaRuntimeUnidentified,
+  UpdateSelfCheckFailed,
+  UpdateSignatureInvalid,
 }
 
 impl std::error::Error for LauncherConfigError {}
@@ -1,4 +1,5 @@
 mod account;
+mod cli;
 mod discover;
 mod error;
 mod instance;
@@ -13,10 +14,12 @@ mod utils;
 
 use account::helpers::authlib_injector::info::refresh_and_update_auth_servers;
 use account::models::AccountInfo;
+use cli::{execute_cli_command, CliCommand};
 use instance::helpers::misc::refresh_and_update_instances;
 use instance::models::misc::Instance;
 use launch::models::LaunchingState;
 use launcher_config::helpers::java::refresh_and_update_javas;
+use launcher_config::helpers::updater::get_build_version;
 use launcher_config::models::{JavaInfo, LauncherConfig};
 use resource::helpers::mod_db::{initialize_mod_db, ModDataBase};
 use std::collections::HashMap;
@@ -43,8 +46,40 @@ static IS_PORTABLE: LazyLock<bool> = LazyLock::new(|| is_portable().unwrap_or(fa
 
 static APP_DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
+static CLI_COMMAND: LazyLock<Option<CliCommand>> =
+  LazyLock::new(|| CliCommand::parse(std::env::args().skip(1)));
+
+static IS_HEADLESS: LazyLock<bool> = LazyLock::new(|| CLI_COMMAND.is_some());
+
 pub async fn run() {
-  tauri::Builder::default()
+  // answered before the app is set up, the updater self-checks new binaries with it
+  if CLI_COMMAND.as_ref() == Some(&CliCommand::Version) {
+    println!("LXMCL {}", get_build_version());
+    return;
+  }
+
+  let mut context = tauri::generate_context!();
+  let mut builder = tauri::Builder::default();
+  if *IS_HEADLESS {
+    // no window is created in headless mode, the output goes to the terminal
+    context.config_mut().app.windows.clear();
+    #[cfg(target_os = "windows")]
+    unsafe {
+      use winapi::um::wincon::{AttachConsole, ATTACH_PARENT_PROCESS};
+      AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+  } else {
+    // the single instance plugin would forward the command line to the running launcher
+    builder = builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+      let main_window = app.get_webview_window("main").expect("no main window");
+
+      let _ = main_window.show(); // may hide by launcher_visibility settings
+                                  // FIXME: this show() seems no use in macOS build mode (ref: https://github.com/tauri-apps/tauri/issues/13400#issuecomment-2866462355).
+      let _ = main_window.set_focus();
+    }));
+  }
+
+  builder
     .plugin(tauri_plugin_clipboard_manager::init())
     .plugin(tauri_plugin_deep_link::init())
     .plugin(tauri_plugin_dialog::init())
@@ -53,13 +88,6 @@ pub async fn run() {
     .plugin(tauri_plugin_opener::init())
     .plugin(tauri_plugin_os::init())
     .plugin(tauri_plugin_process::init())
-    .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-      let main_window = app.get_webview_window("main").expect("no main window");
-
-      let _ = main_window.show(); // may hide by launcher_visibility settings
-                                  // FIXME: this show() seems no use in macOS build mode (ref: https://github.com/tauri-apps/tauri/issues/13400#issuecomment-2866462355).
-      let _ = main_window.set_focus();
-    }))
     .invoke_handler(tauri::generate_handler![
       launcher_config::commands::retrieve_launcher_config,
       launcher_config::commands::update_launcher_config,
@@ -73,8 +101,13 @@ pub async fn run() {
       launcher_config::commands::retrieve_java_list,
       launcher_config::commands::validate_java,
       launcher_config::commands::download_mojang_java,
+      launcher_config::commands::retrieve_managed_java_list,
+      launcher_config::commands::verify_managed_java,
+      launcher_config::commands::repair_managed_java,
+      launcher_config::commands::remove_managed_java,
       launcher_config::commands::check_game_directory,
       launcher_config::commands::clear_download_cache,
+      launcher_config::commands::clean_shared_store,
       launcher_config::commands::check_launcher_update,
       launcher_config::commands::download_launcher_update,
       launcher_config::commands::install_launcher_update,
@@ -88,6 +121,10 @@ pub async fn run() {
       account::commands::relogin_player_3rdparty_password,
       account::commands::add_player_from_selection,
       account::commands::update_player_skin_offline_preset,
+      account::commands::upload_player_texture,
+      account::commands::reset_player_texture,
+      account::commands::retrieve_player_cape_list,
+      account::commands::select_player_cape,
       account::commands::delete_player,
       account::commands::refresh_player,
       account::commands::retrieve_auth_server_list,
@@ -109,8 +146,19 @@ pub async fn run() {
       instance::commands::move_resource_to_instance,
       instance::commands::retrieve_world_list,
       instance::commands::retrieve_world_details,
+      instance::commands::update_world_level_data,
+      instance::commands::create_world_backup,
+      instance::commands::retrieve_world_backup_list,
+      instance::commands::restore_world_backup,
+      instance::commands::prune_world_backups,
       instance::commands::retrieve_game_server_list,
+      instance::commands::add_game_server,
+      instance::commands::update_game_server,
+      instance::commands::delete_game_server,
+      instance::commands::reorder_game_servers,
+      instance::commands::import_game_servers_to_instances,
       instance::commands::retrieve_local_mod_list,
+      instance::commands::check_mod_dependencies,
       instance::commands::retrieve_resource_pack_list,
       instance::commands::retrieve_server_resource_pack_list,
       instance::commands::retrieve_schematic_list,
@@ -119,9 +167,18 @@ pub async fn run() {
       instance::commands::toggle_mod_by_extension,
       instance::commands::create_launch_desktop_shortcut,
       instance::commands::finish_mod_loader_install,
+      instance::commands::install_optifine_to_instance,
+      instance::commands::install_liteloader_to_instance,
       instance::commands::check_change_mod_loader_availablity,
       instance::commands::change_mod_loader,
       instance::commands::retrieve_modpack_meta_info,
+      instance::commands::export_modpack,
+      instance::commands::retrieve_game_options,
+      instance::commands::update_game_options,
+      instance::commands::retrieve_options_template_list,
+      instance::commands::create_options_template,
+      instance::commands::delete_options_template,
+      instance::commands::apply_options_template,
       launch::commands::select_suitable_jre,
       launch::commands::validate_game_files,
       launch::commands::validate_selected_player,
@@ -131,9 +188,11 @@ pub async fn run() {
       launch::commands::retrieve_game_log,
       launch::commands::retrieve_game_launching_state,
       launch::commands::export_game_crash_info,
+      launch::commands::analyze_game_crash,
       resource::commands::fetch_game_version_list,
       resource::commands::fetch_game_version_specific,
       resource::commands::fetch_mod_loader_version_list,
+      resource::commands::fetch_optifine_version_list,
       resource::commands::fetch_resource_list_by_name,
       resource::commands::fetch_resource_version_packs,
       resource::commands::download_game_server,
@@ -190,7 +249,11 @@ pub async fn run() {
       let os = launcher_config.basic_info.platform.clone();
       app.manage(Mutex::new(launcher_config));
 
-      let account_info = AccountInfo::load().unwrap_or_default();
+      let mut account_info = AccountInfo::load().unwrap_or_default();
+      // tokens saved in plaintext by previous versions are encrypted on first start
+      if account_info.migrate_plaintext_tokens().unwrap_or(false) {
+        account_info.save().unwrap_or_default();
+      }
       app.manage(Mutex::new(account_info.clone()));
 
       // CAUC auth state for temporary session storage
@@ -215,6 +278,16 @@ pub async fn run() {
       let launching_queue = Vec::<LaunchingState>::new();
       app.manage(Mutex::new(launching_queue));
 
+      // in headless mode, execute the command line and exit, skipping the GUI background routines
+      if let Some(command) = CLI_COMMAND.clone() {
+        let app_handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+          let exit_code = execute_cli_command(&app_handle, command).await;
+          app_handle.exit(exit_code);
+        });
+        return Ok(());
+      }
+
       // OpenList 下载管理器已废弃，现在使用任务系统
       // let download_manager = openlist::commands::DownloadManager::new();
       // app.manage(download_manager);
@@ -235,6 +308,12 @@ pub async fn run() {
           .unwrap_or_default();
       });
 
+      // Refresh the tokens of online players in the background before they expire
+      let app_handle = app.handle().clone();
+      tauri::async_runtime::spawn(async move {
+        account::helpers::token_refresh::run_token_refresh_scheduler(&app_handle).await;
+      });
+
       // Refresh all instances
       let app_handle = app.handle().clone();
       tauri::async_runtime::spawn(async move {
@@ -282,6 +361,6 @@ pub async fn run() {
 
       Ok(())
     })
-    .run(tauri::generate_context!())
+    .run(context)
     .expect("error while running tauri application");
 }
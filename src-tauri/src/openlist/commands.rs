@@ -153,6 +153,7 @@ pub async fn openlist_download_modpack(
     dest: dest_file.clone(), // 使用完整路径
     filename: Some(file_name.clone()),
     sha1: None, // OpenList 暂不验证 SHA1
+    fallback_srcs: vec![],
   };
 
   // 创建任务组（使用文件名作为任务组名）
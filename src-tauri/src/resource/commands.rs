@@ -9,7 +9,10 @@ use crate::resource::helpers::curseforge::{
 };
 use crate::resource::helpers::loader_meta::fabric::get_fabric_meta_by_game_version;
 use crate::resource::helpers::loader_meta::forge::get_forge_meta_by_game_version;
+use crate::resource::helpers::loader_meta::liteloader::get_liteloader_meta_by_game_version;
 use crate::resource::helpers::loader_meta::neoforge::get_neoforge_meta_by_game_version;
+use crate::resource::helpers::loader_meta::optifine::get_optifine_meta_by_game_version;
+use crate::resource::helpers::loader_meta::quilt::get_quilt_meta_by_game_version;
 use crate::resource::helpers::misc::get_source_priority_list;
 use crate::resource::helpers::modrinth::{
   fetch_remote_resource_by_id_modrinth, fetch_remote_resource_by_local_modrinth,
@@ -17,9 +20,9 @@ use crate::resource::helpers::modrinth::{
 };
 use crate::resource::helpers::version_manifest::get_game_version_manifest;
 use crate::resource::models::{
-  GameClientResourceInfo, ModLoaderResourceInfo, ModUpdateQuery, OtherResourceFileInfo,
-  OtherResourceInfo, OtherResourceSearchQuery, OtherResourceSearchRes, OtherResourceSource,
-  OtherResourceVersionPack, OtherResourceVersionPackQuery, ResourceError,
+  GameClientResourceInfo, ModLoaderResourceInfo, ModUpdateQuery, OptiFineResourceInfo,
+  OtherResourceFileInfo, OtherResourceInfo, OtherResourceSearchQuery, OtherResourceSearchRes,
+  OtherResourceSource, OtherResourceVersionPack, OtherResourceVersionPackQuery, ResourceError,
 };
 use crate::tasks::commands::schedule_progressive_task_group;
 use crate::tasks::download::DownloadParam;
@@ -75,11 +78,30 @@ pub async fn fetch_mod_loader_version_list(
     ModLoaderType::NeoForge => {
       Ok(get_neoforge_meta_by_game_version(&app, &priority_list, &game_version).await?)
     }
+    ModLoaderType::Quilt => {
+      Ok(get_quilt_meta_by_game_version(&app, &priority_list, &game_version).await?)
+    }
+    ModLoaderType::LiteLoader => {
+      Ok(get_liteloader_meta_by_game_version(&app, &priority_list, &game_version).await?)
+    }
     // TODO here
     _ => Err(ResourceError::NoDownloadApi.into()),
   }
 }
 
+#[tauri::command]
+pub async fn fetch_optifine_version_list(
+  app: AppHandle,
+  game_version: String,
+  state: State<'_, Mutex<LauncherConfig>>,
+) -> LXMCLResult<Vec<OptiFineResourceInfo>> {
+  let priority_list = {
+    let state = state.lock()?;
+    get_source_priority_list(&state)
+  };
+  get_optifine_meta_by_game_version(&app, &priority_list, &game_version).await
+}
+
 #[tauri::command]
 pub async fn fetch_resource_list_by_name(
   app: AppHandle,
@@ -139,6 +161,7 @@ pub async fn download_game_server(
       dest: dest.clone().into(),
       filename: None,
       sha1: Some(download_info.sha1.clone()),
+      fallback_srcs: vec![],
     })],
     true,
   )
@@ -188,6 +211,7 @@ pub async fn update_mods(
       dest: file_path,
       filename: None,
       sha1: Some(query.sha1.clone()),
+      fallback_srcs: vec![],
     };
     download_tasks.push(PTaskParam::Download(download_param));
   }
@@ -148,7 +148,16 @@ pub async fn fetch_remote_resource_by_local_curseforge(
   app: &AppHandle,
   file_path: &str,
 ) -> LXMCLResult<OtherResourceFileInfo> {
-  let file_path = Path::new(file_path);
+  let cf_file = fetch_curseforge_file_by_local(app, Path::new(file_path)).await?;
+  Ok((&cf_file, None).into())
+}
+
+/// Look up the CurseForge file of a local file by its murmur2 fingerprint,
+/// the match is accepted only if the remote SHA-1 equals the local one.
+pub async fn fetch_curseforge_file_by_local(
+  app: &AppHandle,
+  file_path: &Path,
+) -> LXMCLResult<CurseForgeFileInfo> {
   if !file_path.exists() {
     return Err(ResourceError::ParseError.into());
   }
@@ -180,21 +189,20 @@ pub async fn fetch_remote_resource_by_local_curseforge(
   )
   .await?;
 
-  if let Some(exact_match) = fingerprint_response.data.exact_matches.first() {
-    let cf_file = &exact_match.file;
-
-    // Verify SHA1 hash matches between local and remote
-    if let Some(remote_sha1) = cf_file.hashes.iter().find(|h| h.algo == 1) {
-      if remote_sha1.value.to_lowercase() == local_sha1.to_lowercase() {
-        Ok((cf_file, None).into())
-      } else {
-        Err(ResourceError::ParseError.into())
-      }
-    } else {
-      Err(ResourceError::ParseError.into())
+  let exact_match = fingerprint_response
+    .data
+    .exact_matches
+    .into_iter()
+    .next()
+    .ok_or(ResourceError::ParseError)?;
+  let cf_file = exact_match.file;
+
+  // Verify SHA1 hash matches between local and remote
+  match cf_file.hashes.iter().find(|h| h.algo == 1) {
+    Some(remote_sha1) if remote_sha1.value.to_lowercase() == local_sha1.to_lowercase() => {
+      Ok(cf_file)
     }
-  } else {
-    Err(ResourceError::ParseError.into())
+    _ => Err(ResourceError::ParseError.into()),
   }
 }
 
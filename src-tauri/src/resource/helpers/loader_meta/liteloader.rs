@@ -0,0 +1,118 @@
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::instance::models::misc::ModLoaderType;
+use crate::resource::helpers::misc::get_download_api;
+use crate::resource::models::{ModLoaderResourceInfo, ResourceError, ResourceType, SourceType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+
+// https://dl.liteloader.com/versions/versions.json
+structstruck::strike! {
+  #[strikethrough[derive(Debug, Serialize, Deserialize, Default, Clone)]]
+  #[strikethrough[serde(rename_all = "camelCase", default)]]
+  pub struct LiteLoaderManifest {
+    pub versions: HashMap<String, pub struct LiteLoaderGameVersion {
+      pub repo: pub struct LiteLoaderRepo {
+        pub stream: String,
+        #[serde(rename = "type")]
+        pub type_: String,
+        pub url: String,
+        pub classifier: String,
+      },
+      // keyed by artefact name ("com.mumfrey:liteloader"), then by version (or "latest")
+      pub artefacts: HashMap<String, HashMap<String, LiteLoaderVersionInfo>>,
+      pub snapshots: HashMap<String, HashMap<String, LiteLoaderVersionInfo>>,
+    }>,
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LiteLoaderVersionInfo {
+  pub stream: String, // RELEASE or SNAPSHOT
+  pub file: String,
+  pub version: String,
+  pub md5: String,
+  pub timestamp: String,
+  pub tweak_class: String,
+  pub libraries: Vec<LiteLoaderLibrary>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct LiteLoaderLibrary {
+  pub name: String,
+  pub url: Option<String>,
+}
+
+impl LiteLoaderGameVersion {
+  pub fn all_versions(&self) -> impl Iterator<Item = &LiteLoaderVersionInfo> {
+    self
+      .artefacts
+      .values()
+      .chain(self.snapshots.values())
+      .flat_map(|versions| {
+        versions
+          .iter()
+          .filter(|(key, _)| key.as_str() != "latest")
+          .map(|(_, info)| info)
+      })
+  }
+}
+
+pub async fn fetch_liteloader_manifest(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+) -> LXMCLResult<LiteLoaderManifest> {
+  let client = app.state::<reqwest::Client>();
+  for source_type in priority_list.iter() {
+    let url = get_download_api(*source_type, ResourceType::Liteloader)?;
+    match client.get(url).send().await {
+      Ok(response) => {
+        if response.status().is_success() {
+          return response
+            .json::<LiteLoaderManifest>()
+            .await
+            .map_err(|_| ResourceError::ParseError.into());
+        } else {
+          continue;
+        }
+      }
+      Err(_) => continue,
+    }
+  }
+  Err(LXMCLError(String::new()))
+}
+
+pub async fn get_liteloader_meta_by_game_version(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+  game_version: &str,
+) -> LXMCLResult<Vec<ModLoaderResourceInfo>> {
+  let manifest = fetch_liteloader_manifest(app, priority_list).await?;
+  let Some(game_version_info) = manifest.versions.get(game_version) else {
+    return Ok(vec![]);
+  };
+
+  let mut versions = game_version_info.all_versions().collect::<Vec<_>>();
+  // the timestamp is a unix time in seconds
+  let timestamp_of =
+    |info: &LiteLoaderVersionInfo| info.timestamp.parse::<i64>().unwrap_or_default();
+  versions.sort_by_key(|info| std::cmp::Reverse(timestamp_of(info)));
+
+  Ok(
+    versions
+      .into_iter()
+      .map(|info| ModLoaderResourceInfo {
+        loader_type: ModLoaderType::LiteLoader,
+        version: info.version.clone(),
+        description: chrono::DateTime::from_timestamp(timestamp_of(info), 0)
+          .map(|time| time.to_rfc3339())
+          .unwrap_or_default(),
+        stable: info.stream == "RELEASE",
+        branch: None,
+      })
+      .collect(),
+  )
+}
@@ -1,3 +1,6 @@
 pub mod fabric;
 pub mod forge;
+pub mod liteloader;
 pub mod neoforge;
+pub mod optifine;
+pub mod quilt;
@@ -0,0 +1,59 @@
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::resource::helpers::misc::get_download_api;
+use crate::resource::models::{OptiFineResourceInfo, ResourceError, ResourceType, SourceType};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct OptiFineMetaItem {
+  pub mcversion: String,
+  pub patch: String,
+  #[serde(rename = "type")]
+  pub type_: String,
+  pub filename: String,
+  pub forge: Option<String>,
+}
+
+pub async fn get_optifine_meta_by_game_version(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+  game_version: &str,
+) -> LXMCLResult<Vec<OptiFineResourceInfo>> {
+  let client = app.state::<reqwest::Client>();
+  for source_type in priority_list.iter() {
+    // only the BMCLAPI mirror provides optifine meta
+    let Ok(root) = get_download_api(*source_type, ResourceType::Optifine) else {
+      continue;
+    };
+    let url = root.join(game_version)?;
+    match client.get(url).send().await {
+      Ok(response) => {
+        if response.status().is_success() {
+          if let Ok(manifest) = response.json::<Vec<OptiFineMetaItem>>().await {
+            return Ok(
+              manifest
+                .into_iter()
+                .map(|info| OptiFineResourceInfo {
+                  stable: !info.patch.starts_with("pre"),
+                  game_version: info.mcversion,
+                  type_: info.type_,
+                  patch: info.patch,
+                  filename: info.filename,
+                  forge: info.forge.filter(|forge| !forge.is_empty()),
+                })
+                .collect(),
+            );
+          } else {
+            return Err(ResourceError::ParseError.into());
+          }
+        } else {
+          continue;
+        }
+      }
+      Err(_) => continue,
+    }
+  }
+  Err(LXMCLError(String::new()))
+}
@@ -0,0 +1,66 @@
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::instance::models::misc::ModLoaderType;
+use crate::resource::helpers::misc::get_download_api;
+use crate::resource::models::{ModLoaderResourceInfo, ResourceError, ResourceType, SourceType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct QuiltMetaItem {
+  pub loader: QuiltLoaderInfo,
+  pub hashed: Value,
+  pub intermediary: Value,
+  pub launcher_meta: Value,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct QuiltLoaderInfo {
+  pub separator: String,
+  pub build: i64,
+  pub maven: String,
+  pub version: String,
+}
+
+pub async fn get_quilt_meta_by_game_version(
+  app: &AppHandle,
+  priority_list: &[SourceType],
+  game_version: &str,
+) -> LXMCLResult<Vec<ModLoaderResourceInfo>> {
+  let client = app.state::<reqwest::Client>();
+  for source_type in priority_list.iter() {
+    let url = get_download_api(*source_type, ResourceType::QuiltMeta)?
+      .join("v3/versions/loader/")?
+      .join(game_version)?;
+    match client.get(url).send().await {
+      Ok(response) => {
+        if response.status().is_success() {
+          if let Ok(manifest) = response.json::<Vec<QuiltMetaItem>>().await {
+            return Ok(
+              manifest
+                .into_iter()
+                .map(|info| ModLoaderResourceInfo {
+                  loader_type: ModLoaderType::Quilt,
+                  // quilt meta has no stable flag, pre-releases are marked in the version string
+                  stable: !(info.loader.version.contains("beta")
+                    || info.loader.version.contains("pre")),
+                  version: info.loader.version,
+                  description: String::new(),
+                  branch: None,
+                })
+                .collect(),
+            );
+          } else {
+            return Err(ResourceError::ParseError.into());
+          }
+        } else {
+          continue;
+        }
+      }
+      Err(_) => continue,
+    }
+  }
+  Err(LXMCLError(String::new()))
+}
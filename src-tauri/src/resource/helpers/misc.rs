@@ -41,7 +41,7 @@ pub fn get_download_api(source: SourceType, resource_type: ResourceType) -> LXMC
       ResourceType::ForgeInstall => Ok(Url::parse("https://maven.minecraftforge.net/net/minecraftforge/forge/")?),
       ResourceType::ForgeMeta => Err(ResourceError::NoDownloadApi.into()), // https://github.com/HMCL-dev/HMCL/pull/3259/files
       ResourceType::Liteloader => Ok(Url::parse("https://dl.liteloader.com/versions/versions.json")?),
-      ResourceType::Optifine => Err(ResourceError::NoDownloadApi.into()), // 
+      ResourceType::Optifine => Err(ResourceError::NoDownloadApi.into()), // optifine.net has no public api
       ResourceType::AuthlibInjector => Ok(Url::parse("https://authlib-injector.yushi.moe/")?),
       ResourceType::FabricMeta => Ok(Url::parse("https://meta.fabricmc.net/")?),
       ResourceType::FabricMaven => Ok(Url::parse("https://maven.fabricmc.net/")?),
@@ -69,7 +69,7 @@ pub fn get_download_api(source: SourceType, resource_type: ResourceType) -> LXMC
       ResourceType::FabricMaven => Ok(Url::parse("https://bmclapi2.bangbang93.com/maven/")?),
       ResourceType::NeoforgeMetaForge | ResourceType::NeoforgeMetaNeoforge => Ok(Url::parse("https://bmclapi2.bangbang93.com/neoforge/")?),
       ResourceType::NeoforgeInstall => Ok(Url::parse("https://bmclapi2.bangbang93.com/neoforge/version/")?),
-      ResourceType::Optifine => Err(ResourceError::NoDownloadApi.into()),
+      ResourceType::Optifine => Ok(Url::parse("https://bmclapi2.bangbang93.com/optifine/")?),
       ResourceType::QuiltMaven => Ok(Url::parse("https://bmclapi2.bangbang93.com/maven/")?),
       ResourceType::QuiltMeta => Ok(Url::parse("https://bmclapi2.bangbang93.com/quilt-meta/")?),
     },
@@ -132,6 +132,62 @@ pub fn convert_url_to_target_source(
   Ok(url.clone())
 }
 
+// resource types whose files are mirrored by other sources under the same relative path.
+const MIRRORED_RESOURCE_TYPES: [ResourceType; 7] = [
+  ResourceType::Assets,
+  ResourceType::Libraries,
+  ResourceType::ForgeMaven,
+  ResourceType::ForgeMavenNew,
+  ResourceType::FabricMaven,
+  ResourceType::NeoforgeMaven,
+  ResourceType::QuiltMaven,
+];
+
+// resource types served from the bare root of the mirrors, which every other mirror path shares,
+// so only urls under their official roots get alternatives.
+const ROOT_MIRRORED_RESOURCE_TYPES: [ResourceType; 2] =
+  [ResourceType::Launcher, ResourceType::LauncherMeta];
+
+// candidate urls of the same file on other download sources, in the order of `priority`.
+// a mirror may merge several official sources (e.g. all mavens in BMCLAPI), then all of them are returned.
+pub fn get_alternative_source_urls(url: &Url, priority: &[SourceType]) -> Vec<Url> {
+  let url_str = url.as_str();
+  let mut matched = Vec::new();
+  for src_type in SourceType::iter() {
+    let root_types: &[ResourceType] = if src_type == SourceType::Official {
+      &ROOT_MIRRORED_RESOURCE_TYPES
+    } else {
+      &[]
+    };
+    for resource_type in MIRRORED_RESOURCE_TYPES.iter().chain(root_types) {
+      if let Ok(src_api) = get_download_api(src_type, *resource_type) {
+        if url_str.starts_with(src_api.as_str()) {
+          matched.push((src_api.as_str().len(), *resource_type, src_type));
+        }
+      }
+    }
+  }
+  // only the most specific api prefix is used
+  let Some(longest) = matched.iter().map(|(len, _, _)| *len).max() else {
+    return Vec::new();
+  };
+
+  let mut urls = Vec::new();
+  for dst_type in priority {
+    for (_, resource_type, src_type) in matched.iter().filter(|(len, _, _)| *len == longest) {
+      if src_type == dst_type {
+        continue;
+      }
+      if let Ok(new_url) = convert_url_source_type(url, resource_type, src_type, dst_type) {
+        if new_url != *url && !urls.contains(&new_url) {
+          urls.push(new_url);
+        }
+      }
+    }
+  }
+  urls
+}
+
 pub fn version_pack_sort(a: &OtherResourceVersionPack, b: &OtherResourceVersionPack) -> Ordering {
   fn parse_version(version: &str) -> (Vec<u32>, String) {
     let mut version_numbers = Vec::new();
@@ -136,6 +136,7 @@ fn normalize_modrinth_loader(loader: &str) -> Option<String> {
     match loader.to_lowercase().as_str() {
       "forge" => Some("Forge".to_string()),
       "fabric" => Some("Fabric".to_string()),
+      "quilt" => Some("Quilt".to_string()),
       "neoforge" => Some("NeoForge".to_string()),
       "vanilla" => Some("Vanilla".to_string()),
       "iris" => Some("Iris".to_string()),
@@ -161,6 +162,7 @@ pub fn map_modrinth_file_to_version_pack(
     const ALLOWED_LOADERS: &[&str] = &[
       "forge",
       "fabric",
+      "quilt",
       "neoforge",
       "vanilla",
       "iris",
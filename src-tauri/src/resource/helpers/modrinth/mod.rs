@@ -192,9 +192,36 @@ pub async fn get_latest_fabric_api_mod_download(
 ) -> LXMCLResult<Option<DownloadParam>> {
   const FABRIC_API_MOD_ID: &str = "P7dR8mSH"; // Fabric API Mod Id in Modrinth
 
+  get_latest_api_mod_download(app, FABRIC_API_MOD_ID, "Fabric", game_version, mods_dir).await
+}
+
+pub async fn get_latest_quilted_fabric_api_mod_download(
+  app: &AppHandle,
+  game_version: &str,
+  mods_dir: PathBuf,
+) -> LXMCLResult<Option<DownloadParam>> {
+  const QUILTED_FABRIC_API_MOD_ID: &str = "qvIfYCYJ"; // QFAPI/QSL Mod Id in Modrinth
+
+  get_latest_api_mod_download(
+    app,
+    QUILTED_FABRIC_API_MOD_ID,
+    "Quilt",
+    game_version,
+    mods_dir,
+  )
+  .await
+}
+
+async fn get_latest_api_mod_download(
+  app: &AppHandle,
+  mod_id: &str,
+  mod_loader: &str,
+  game_version: &str,
+  mods_dir: PathBuf,
+) -> LXMCLResult<Option<DownloadParam>> {
   let query = OtherResourceVersionPackQuery {
-    resource_id: FABRIC_API_MOD_ID.to_string(),
-    mod_loader: "Fabric".to_string(),
+    resource_id: mod_id.to_string(),
+    mod_loader: mod_loader.to_string(),
     game_versions: vec![game_version.to_string()],
   };
 
@@ -227,5 +254,6 @@ pub async fn get_latest_fabric_api_mod_download(
     dest: dest_path,
     filename: Some(filename),
     sha1: Some(latest_file.sha1.clone()),
+    fallback_srcs: vec![],
   }))
 }
@@ -180,6 +180,24 @@ pub struct ModLoaderResourceInfo {
   pub branch: Option<String>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OptiFineResourceInfo {
+  pub game_version: String,
+  #[serde(rename = "type")]
+  pub type_: String, // e.g. HD_U
+  pub patch: String, // e.g. I6, pre releases start with "pre"
+  pub filename: String,
+  pub forge: Option<String>, // compatible Forge version, if any
+  pub stable: bool,
+}
+
+impl OptiFineResourceInfo {
+  pub fn version(&self) -> String {
+    format!("{}_{}", self.type_, self.patch)
+  }
+}
+
 #[derive(Debug, Display)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum ResourceError {
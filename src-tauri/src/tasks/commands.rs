@@ -1,10 +1,13 @@
 use crate::error::LXMCLResult;
+use crate::launcher_config::models::LauncherConfig;
+use crate::resource::helpers::misc::{get_alternative_source_urls, get_source_priority_list};
 use crate::tasks::download::DownloadTask;
 use crate::tasks::events::GEventStatus;
 use crate::tasks::monitor::TaskMonitor;
 use crate::tasks::{PTaskGroupDesc, PTaskParam, SJMCLFutureDesc, THandle};
 use crate::utils::fs::extract_filename;
 use std::pin::Pin;
+use std::sync::Mutex;
 use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
@@ -16,6 +19,11 @@ pub async fn schedule_progressive_task_group(
   with_timestamp: bool,
 ) -> LXMCLResult<PTaskGroupDesc> {
   let monitor = app.state::<Pin<Box<TaskMonitor>>>();
+  let priority_list = {
+    let launcher_config = app.state::<Mutex<LauncherConfig>>();
+    let launcher_config = launcher_config.lock()?;
+    get_source_priority_list(&launcher_config)
+  };
   let mut task_descs = Vec::new();
   let mut future_descs = Vec::new();
   let task_group = if with_timestamp {
@@ -37,6 +45,9 @@ pub async fn schedule_progressive_task_group(
             true,
           ));
         }
+        if param.fallback_srcs.is_empty() {
+          param.fallback_srcs = get_alternative_source_urls(&param.src, &priority_list);
+        }
         let task = DownloadTask::new(
           app.clone(),
           task_id,
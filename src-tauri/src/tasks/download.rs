@@ -1,26 +1,35 @@
 use crate::error::{LXMCLError, LXMCLResult};
 use crate::launcher_config::commands::retrieve_launcher_config;
+use crate::launcher_config::helpers::shared_store::add_downloaded_file_to_shared_store;
 use crate::tasks::streams::desc::{PDesc, PStatus};
 use crate::tasks::streams::reporter::Reporter;
 use crate::tasks::streams::ProgressStream;
 use crate::tasks::*;
 use crate::utils::fs::validate_sha1;
-use crate::utils::web::with_retry;
+use crate::utils::web::with_limited_retry;
 use async_speed_limit::Limiter;
 use futures::stream::TryStreamExt;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::future::Future;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
+use tauri::http::StatusCode;
 use tauri::{AppHandle, Manager, Url};
 use tauri_plugin_http::reqwest;
-use tauri_plugin_http::reqwest::header::RANGE;
-use tokio::io::AsyncSeekExt;
-use tokio_util::bytes;
-use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tauri_plugin_http::reqwest::header::{ACCEPT_RANGES, RANGE};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+// files larger than this are downloaded in parallel ranged chunks, if the source supports it
+const CHUNKED_DOWNLOAD_THRESHOLD: i64 = 8 * 1024 * 1024;
+const MIN_CHUNK_SIZE: i64 = 4 * 1024 * 1024;
+const MAX_CHUNK_COUNT: i64 = 8;
+const MAX_RETRIES_PER_REQUEST: u32 = 2;
+// consecutive attempts without progress on each source, before a range is given up
+const MAX_ATTEMPTS_PER_SOURCE: usize = 2;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -29,6 +38,21 @@ pub struct DownloadParam {
   pub dest: PathBuf,
   pub filename: Option<String>,
   pub sha1: Option<String>,
+  // candidate sources tried in order if `src` fails, filled from the source priority list if empty.
+  #[serde(default)]
+  pub fallback_srcs: Vec<Url>,
+}
+
+impl DownloadParam {
+  pub fn candidate_srcs(&self) -> Vec<Url> {
+    let mut srcs = vec![self.src.clone()];
+    for src in &self.fallback_srcs {
+      if !srcs.contains(src) {
+        srcs.push(src.clone());
+      }
+    }
+    srcs
+  }
 }
 
 pub struct DownloadTask {
@@ -119,56 +143,6 @@ impl DownloadTask {
     }
   }
 
-  async fn send_request(
-    app_handle: &AppHandle,
-    current: i64,
-    param: &DownloadParam,
-  ) -> LXMCLResult<reqwest::Response> {
-    let state = app_handle.state::<reqwest::Client>();
-    let client = with_retry(state.inner().clone());
-    let request = if current == 0 {
-      client.get(param.src.clone())
-    } else {
-      client
-        .get(param.src.clone())
-        .header(RANGE, format!("bytes={current}-"))
-    };
-
-    let response = request
-      .send()
-      .await
-      .map_err(|e| LXMCLError(format!("{:?}", e.source())))?;
-
-    let response = response
-      .error_for_status()
-      .map_err(|e| LXMCLError(format!("{:?}", e.source())))?;
-
-    Ok(response)
-  }
-
-  async fn create_resp_stream(
-    app_handle: &AppHandle,
-    current: i64,
-    param: &DownloadParam,
-  ) -> LXMCLResult<(
-    impl Stream<Item = Result<bytes::Bytes, std::io::Error>> + Send,
-    i64,
-  )> {
-    let resp = Self::send_request(app_handle, current, param).await?;
-    let total_progress = if current == 0 {
-      resp.content_length().unwrap() as i64
-    } else {
-      -1
-    };
-    Ok((
-      resp.bytes_stream().map(|res| match res {
-        Ok(bytes) => Ok(bytes),
-        Err(_) => Ok(bytes::Bytes::new()),
-      }),
-      total_progress,
-    ))
-  }
-
   async fn future_impl(
     self,
     app_handle: AppHandle,
@@ -177,42 +151,52 @@ impl DownloadTask {
     impl Future<Output = LXMCLResult<()>> + Send,
     Arc<RwLock<PTaskHandle>>,
   )> {
-    let current = self.p_handle.desc.current;
     let handle = Arc::new(RwLock::new(self.p_handle));
     let task_handle = handle.clone();
     let param = self.param.clone();
+    let dest_path = self.dest_path.clone();
     Ok((
       async move {
-        let (resp, total_progress) = Self::create_resp_stream(&app_handle, current, &param).await?;
-        let stream = ProgressStream::new(resp, task_handle.clone());
-        tokio::fs::create_dir_all(&self.dest_path.parent().unwrap()).await?;
-        let mut file = if current == 0 {
-          tokio::fs::File::create(&self.dest_path).await?
-        } else {
-          let mut f = tokio::fs::OpenOptions::new().open(&self.dest_path).await?;
-          f.seek(std::io::SeekFrom::Start(current as u64)).await?;
-          f
-        };
-        {
-          let mut task_handle = task_handle.write().unwrap();
-          task_handle.set_total(total_progress);
-          task_handle.mark_started();
-        }
-        if let Some(lim) = limiter {
-          tokio::io::copy(&mut lim.limit(stream.into_async_read()).compat(), &mut file).await?;
-        } else {
-          tokio::io::copy(&mut stream.into_async_read().compat(), &mut file).await?;
-        }
-        drop(file);
-        if task_handle.read().unwrap().status().is_cancelled() {
-          tokio::fs::remove_file(&self.dest_path).await?;
-          Ok(())
-        } else {
-          match param.sha1 {
-            Some(truth) => validate_sha1(param.dest, truth),
+        tokio::fs::create_dir_all(&dest_path.parent().unwrap()).await?;
+        let srcs = param.candidate_srcs();
+        let mut last_error = None;
+        // if the downloaded file is corrupted, download it again preferring the next source
+        for primary in 0..srcs.len() {
+          let context = DownloadContext {
+            app_handle: &app_handle,
+            srcs: [&srcs[primary..], &srcs[..primary]].concat(),
+            handle: task_handle.clone(),
+            dest: &dest_path,
+            limiter: limiter.clone(),
+            ranges_unsupported: AtomicBool::new(false),
+          };
+          let result = context.download().await;
+          if context.is_cancelled() {
+            let _ = tokio::fs::remove_file(&dest_path).await;
+            return Ok(());
+          }
+          result?;
+
+          let validation = match &param.sha1 {
+            Some(truth) => validate_sha1(dest_path.clone(), truth.clone()),
             None => Ok(()),
+          };
+          match validation {
+            Ok(()) => {
+              if let Some(truth) = &param.sha1 {
+                // not fatal, the file is still usable in the game directory
+                let _ = add_downloaded_file_to_shared_store(&app_handle, truth, &dest_path).await;
+              }
+              task_handle.write().unwrap().mark_completed();
+              return Ok(());
+            }
+            Err(e) => {
+              task_handle.write().unwrap().set_progress(0);
+              last_error = Some(e);
+            }
           }
         }
+        Err(last_error.unwrap_or(LXMCLError("no download source".to_string())))
       },
       handle,
     ))
@@ -229,3 +213,225 @@ impl DownloadTask {
     Self::future_impl(self, app_handle, limiter).await
   }
 }
+
+struct DownloadContext<'a> {
+  app_handle: &'a AppHandle,
+  srcs: Vec<Url>, // ordered candidate sources
+  handle: Arc<RwLock<PTaskHandle>>,
+  dest: &'a Path,
+  limiter: Option<Limiter>,
+  // set when a source ignores the range header of a chunk, then the file is downloaded in one stream
+  ranges_unsupported: AtomicBool,
+}
+
+impl DownloadContext<'_> {
+  fn is_cancelled(&self) -> bool {
+    self.handle.read().unwrap().status().is_cancelled()
+  }
+
+  async fn send_request(
+    &self,
+    src: &Url,
+    range: Option<(i64, Option<i64>)>,
+  ) -> LXMCLResult<reqwest::Response> {
+    let state = self.app_handle.state::<reqwest::Client>();
+    let client = with_limited_retry(state.inner().clone(), MAX_RETRIES_PER_REQUEST);
+    let request = match range {
+      None => client.get(src.clone()),
+      Some((start, end)) => client.get(src.clone()).header(
+        RANGE,
+        match end {
+          Some(end) => format!("bytes={start}-{}", end - 1),
+          None => format!("bytes={start}-"),
+        },
+      ),
+    };
+
+    let response = request
+      .send()
+      .await
+      .map_err(|e| LXMCLError(format!("{:?}", e.source())))?;
+
+    let response = response
+      .error_for_status()
+      .map_err(|e| LXMCLError(format!("{:?}", e.source())))?;
+
+    Ok(response)
+  }
+
+  // write the response body into `file` from its current position, counting the written bytes.
+  async fn write_response(
+    &self,
+    response: reqwest::Response,
+    file: &mut tokio::fs::File,
+    written: &mut i64,
+  ) -> LXMCLResult<()> {
+    let stream = ProgressStream::new(
+      response.bytes_stream().map_err(std::io::Error::other),
+      self.handle.clone(),
+    );
+    let mut stream = std::pin::pin!(stream);
+    while let Some(bytes) = stream.next().await {
+      let bytes = bytes?;
+      if let Some(limiter) = &self.limiter {
+        limiter.consume(bytes.len()).await;
+      }
+      file.write_all(&bytes).await?;
+      *written += bytes.len() as i64;
+    }
+    file.flush().await?;
+    Ok(())
+  }
+
+  // download the bytes in [start, end) into the same position of the file. if a source fails,
+  // continue from the last written byte with the next one. `whole_file` means this is the only
+  // range of the file, so a source without range support can restart it from the beginning.
+  async fn download_range(
+    &self,
+    start: i64,
+    end: Option<i64>,
+    whole_file: bool,
+    mut response: Option<reqwest::Response>,
+    src_index: usize,
+  ) -> LXMCLResult<()> {
+    let mut file = tokio::fs::OpenOptions::new()
+      .write(true)
+      .open(self.dest)
+      .await?;
+    let mut offset = start;
+    let mut src_index = src_index;
+    let mut failures = 0;
+    let mut last_error = None;
+
+    while failures < self.srcs.len() * MAX_ATTEMPTS_PER_SOURCE {
+      if self.is_cancelled() {
+        return Ok(());
+      }
+      let src = &self.srcs[src_index % self.srcs.len()];
+      let mut written = 0;
+      let result = async {
+        let (response, ranged) = match response.take() {
+          Some(response) => (response, false),
+          None => (self.send_request(src, Some((offset, end))).await?, true),
+        };
+        if ranged && response.status() != StatusCode::PARTIAL_CONTENT {
+          if !whole_file {
+            self.ranges_unsupported.store(true, Ordering::Relaxed);
+            return Err(LXMCLError(format!("{src} does not support range requests")));
+          }
+          // the source sends the whole file, start over
+          self.handle.write().unwrap().set_progress(0);
+          offset = 0;
+          file.set_len(0).await?;
+        }
+        file.seek(std::io::SeekFrom::Start(offset as u64)).await?;
+        self.write_response(response, &mut file, &mut written).await
+      }
+      .await;
+      offset += written;
+
+      if self.is_cancelled() {
+        return Ok(());
+      }
+      if !whole_file && self.ranges_unsupported.load(Ordering::Relaxed) {
+        // abort this chunk, the caller starts over without chunks
+        return result;
+      }
+      match result {
+        Ok(()) if end.map_or(true, |end| offset >= end) => return Ok(()),
+        Ok(()) => last_error = Some(LXMCLError(format!("incomplete response from {src}"))),
+        Err(e) => last_error = Some(e),
+      }
+      // keep trying as long as some progress is made (e.g. the connection is closed by timeout)
+      failures = if written > 0 { 0 } else { failures + 1 };
+      src_index += 1;
+    }
+
+    Err(last_error.unwrap_or(LXMCLError("no download source".to_string())))
+  }
+
+  // download the file from the first available source, split it into parallel chunks if it is large,
+  // or resume the download interrupted before (e.g. by restarting the launcher).
+  async fn download(&self) -> LXMCLResult<()> {
+    let (current, total) = {
+      let handle = self.handle.read().unwrap();
+      (handle.desc.current, handle.desc.total)
+    };
+    if current > 0 && total > 0 {
+      let file_len = tokio::fs::metadata(self.dest)
+        .await
+        .map(|metadata| metadata.len() as i64)
+        .unwrap_or_default();
+      // a chunked download has preallocated the whole file, it cannot be resumed
+      if file_len == current {
+        self.handle.write().unwrap().mark_started();
+        return self
+          .download_range(current, Some(total), true, None, 0)
+          .await;
+      }
+    }
+
+    let mut first_response = None;
+    let mut last_error = None;
+    for (index, src) in self.srcs.iter().enumerate() {
+      match self.send_request(src, None).await {
+        Ok(response) => {
+          first_response = Some((index, response));
+          break;
+        }
+        Err(e) => last_error = Some(e),
+      }
+    }
+    let Some((src_index, response)) = first_response else {
+      return Err(last_error.unwrap_or(LXMCLError("no download source".to_string())));
+    };
+
+    let size = response.content_length().map(|len| len as i64);
+    let accept_ranges = response
+      .headers()
+      .get(ACCEPT_RANGES)
+      .is_some_and(|value| value.as_bytes() == b"bytes");
+    {
+      let mut handle = self.handle.write().unwrap();
+      handle.set_progress(0);
+      if let Some(size) = size {
+        handle.set_total(size);
+      }
+      handle.mark_started();
+    }
+
+    let file = tokio::fs::File::create(self.dest).await?;
+    match size {
+      Some(size) if accept_ranges && size >= CHUNKED_DOWNLOAD_THRESHOLD => {
+        drop(response);
+        file.set_len(size as u64).await?;
+        drop(file);
+
+        let chunk_count = (size / MIN_CHUNK_SIZE).clamp(1, MAX_CHUNK_COUNT);
+        let chunk_size = (size as u64).div_ceil(chunk_count as u64) as i64;
+        let chunks = (0..chunk_count).map(|i| {
+          let start = i * chunk_size;
+          let end = (start + chunk_size).min(size);
+          self.download_range(start, Some(end), false, None, src_index)
+        });
+        match futures::future::try_join_all(chunks).await {
+          Err(_) if self.ranges_unsupported.load(Ordering::Relaxed) => {
+            // the source ignores ranges after all, download the whole file in one stream
+            self.handle.write().unwrap().set_progress(0);
+            tokio::fs::File::create(self.dest).await?;
+            self
+              .download_range(0, Some(size), true, None, src_index)
+              .await
+          }
+          result => result.map(|_| ()),
+        }
+      }
+      _ => {
+        drop(file);
+        self
+          .download_range(0, size, true, Some(response), src_index)
+          .await
+      }
+    }
+  }
+}
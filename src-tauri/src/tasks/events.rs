@@ -5,7 +5,7 @@ use tauri::{AppHandle, Emitter};
 use tokio::time::Duration;
 
 const TASK_PROGRESS_UPDATE_EVENT: &str = "task:progress-update";
-const TASK_GROUP_UPDATE_EVENT: &str = "task:group-update";
+pub const TASK_GROUP_UPDATE_EVENT: &str = "task:group-update";
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(tag = "status")]
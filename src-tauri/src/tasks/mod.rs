@@ -4,6 +4,7 @@ pub mod download;
 pub mod events;
 pub mod monitor;
 pub mod streams;
+pub mod wait;
 
 use crate::error::LXMCLResult;
 use download::DownloadParam;
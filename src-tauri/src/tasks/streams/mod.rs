@@ -68,11 +68,12 @@ where
     let p = self.project();
 
     p.stream.poll_next(cx).map(|opt| {
-      let mut h = p.handle.write().unwrap();
+      // the owner of the stream marks the task completed, as a task may consist of several streams
       if let Some(item) = &opt {
-        h.report_progress(cx, item.unit_size());
-      } else {
-        h.mark_completed();
+        p.handle
+          .write()
+          .unwrap()
+          .report_progress(cx, item.unit_size());
       }
       opt
     })
@@ -0,0 +1,127 @@
+use crate::error::{LXMCLError, LXMCLResult};
+use crate::tasks::events::{GEventStatus, TASK_GROUP_UPDATE_EVENT};
+use crate::tasks::monitor::TaskMonitor;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, EventId, Listener, Manager};
+
+// how long to wait for a scheduled task group to show up in the task monitor
+const TASK_GROUP_START_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskGroupUpdate {
+  task_group: String,
+  event: GEventStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGroupProgress {
+  pub finished_files: usize,
+  pub total_files: usize,
+  pub current_size: i64,
+  pub total_size: i64,
+}
+
+// record the last status of every task group, failed and cancelled groups are removed from the
+// monitor so their status is only known from the events.
+// listen before scheduling the group, it may fail before the wait starts.
+pub fn watch_task_group_events(
+  app: &AppHandle,
+) -> (EventId, Arc<Mutex<HashMap<String, GEventStatus>>>) {
+  let last_events = Arc::new(Mutex::new(HashMap::new()));
+  let listener = {
+    let last_events = last_events.clone();
+    app.listen_any(TASK_GROUP_UPDATE_EVENT, move |event| {
+      if let Ok(update) = serde_json::from_str::<TaskGroupUpdate>(event.payload()) {
+        last_events
+          .lock()
+          .unwrap()
+          .insert(update.task_group, update.event);
+      }
+    })
+  };
+  (listener, last_events)
+}
+
+// wait for the task group to finish, reporting its progress every second.
+pub async fn wait_for_task_group(
+  app: &AppHandle,
+  task_group: &str,
+  last_events: &Mutex<HashMap<String, GEventStatus>>,
+  mut on_progress: impl FnMut(&TaskGroupProgress),
+) -> LXMCLResult<()> {
+  let monitor = app.state::<Pin<Box<TaskMonitor>>>();
+  let started_at = Instant::now();
+  let mut seen = false;
+  let mut missing_polls = 0;
+  let mut total_files = None;
+  loop {
+    let group = monitor
+      .state_list()
+      .into_iter()
+      .find(|group| group.task_group == task_group);
+    let status = match &group {
+      Some(group) => Some(group.status.clone()),
+      None => last_events.lock()?.get(task_group).cloned(),
+    };
+
+    match status {
+      Some(GEventStatus::Completed) => return Ok(()),
+      Some(GEventStatus::Failed) => {
+        return Err(LXMCLError(format!(
+          "the download task group {task_group} failed"
+        )));
+      }
+      Some(GEventStatus::Cancelled) => {
+        return Err(LXMCLError(format!(
+          "the download task group {task_group} was cancelled"
+        )));
+      }
+      _ => {}
+    }
+
+    match group {
+      Some(group) => {
+        seen = true;
+        missing_polls = 0;
+        // completed tasks are removed from the group
+        let remaining = group.task_descs.len();
+        let total = *total_files.get_or_insert(remaining);
+        let (current_size, total_size) = group
+          .task_descs
+          .iter()
+          .fold((0, 0), |(current, size), desc| {
+            (current + desc.current, size + desc.total)
+          });
+        on_progress(&TaskGroupProgress {
+          finished_files: total.saturating_sub(remaining),
+          total_files: total,
+          current_size,
+          total_size,
+        });
+      }
+      None if seen => {
+        // the group is removed without a status event, give the event one more poll to arrive
+        missing_polls += 1;
+        if missing_polls > 1 {
+          return Err(LXMCLError(format!(
+            "the download task group {task_group} failed"
+          )));
+        }
+      }
+      None if started_at.elapsed() > TASK_GROUP_START_TIMEOUT => {
+        return Err(LXMCLError(format!(
+          "the download task group {task_group} did not start within {}s",
+          TASK_GROUP_START_TIMEOUT.as_secs()
+        )));
+      }
+      None => {}
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+  }
+}
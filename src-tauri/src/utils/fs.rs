@@ -3,6 +3,7 @@ use crate::IS_PORTABLE;
 use regex::Regex;
 use sha1::{Digest, Sha1};
 use std::ffi::OsStr;
+use std::io::{Seek, Write};
 use std::path::{Path, PathBuf};
 use std::{fs, io};
 use tauri::path::BaseDirectory;
@@ -426,3 +427,42 @@ pub fn create_zip_from_dirs(paths: Vec<PathBuf>, zip_file_path: PathBuf) -> LXMC
 
   Ok(zip_file_path.to_string_lossy().to_string())
 }
+
+/// Recursively adds a file or directory to an open zip archive under the given entry path.
+/// Entry names always use `/` as separator, regardless of the platform.
+///
+/// # Examples
+///
+/// ```rust
+/// add_path_to_zip(&mut zip, &game_root.join("config"), Path::new("overrides/config"), &options)?;
+/// ```
+pub fn add_path_to_zip<W: Write + Seek>(
+  zip: &mut ZipWriter<W>,
+  src: &Path,
+  entry_path: &Path,
+  options: &FileOptions<ExtendedFileOptions>,
+) -> LXMCLResult<()> {
+  let entry_name = entry_path
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy())
+    .collect::<Vec<_>>()
+    .join("/");
+
+  if src.is_dir() {
+    zip.add_directory(format!("{}/", entry_name), options.clone())?;
+    for entry in fs::read_dir(src)? {
+      let entry = entry?;
+      add_path_to_zip(
+        zip,
+        &entry.path(),
+        &entry_path.join(entry.file_name()),
+        options,
+      )?;
+    }
+  } else if src.is_file() {
+    zip.start_file(entry_name, options.clone())?;
+    let mut file = fs::File::open(src)?;
+    io::copy(&mut file, zip)?;
+  }
+  Ok(())
+}
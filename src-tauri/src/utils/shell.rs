@@ -1,24 +1,44 @@
 use crate::error::{LXMCLError, LXMCLResult};
-use std::io;
-use std::process::{Command, ExitStatus};
+use std::process::Command;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
-pub fn execute_command_line(cmdline: &str) -> io::Result<ExitStatus> {
+// Build a command that runs the command line with the system shell.
+pub fn build_command_line(cmdline: &str) -> Command {
   #[cfg(target_os = "windows")]
   {
     let mut cmd = Command::new("cmd");
-    cmd.arg("/C").arg(cmdline);
+    // cmd does not understand the escaping of `arg`, pass the command line as it is
+    cmd.arg("/C").raw_arg(cmdline);
     cmd.creation_flags(0x08000000);
-    cmd.status()
+    cmd
   }
 
   #[cfg(not(target_os = "windows"))]
   {
     let mut cmd = Command::new("/bin/sh");
     cmd.arg("-c").arg(cmdline);
-    cmd.status()
+    cmd
+  }
+}
+
+// Quote an argument to be spliced into a command line run by `build_command_line`.
+pub fn quote_command_line_arg(arg: &str) -> String {
+  #[cfg(target_os = "windows")]
+  {
+    // cmd keeps the quotes for the program to parse, `"` is not allowed in paths and names anyway.
+    // `%` would expand variables even inside quotes, so it is moved out of them as `^%`, which
+    // also breaks up any `%NAME%` (the name would include the quote and `^`, never defined).
+    format!("\"{}\"", arg.replace('"', "\"\"").replace('%', "\"^%\""))
+  }
+
+  #[cfg(not(target_os = "windows"))]
+  {
+    // only fails on a nul byte, which cannot be passed to the shell anyway
+    shlex::try_quote(arg)
+      .map(|quoted| quoted.into_owned())
+      .unwrap_or_default()
   }
 }
 
@@ -41,3 +61,27 @@ pub fn split_command_line(wrapper: &str) -> LXMCLResult<Option<Command>> {
 
   Ok(Some(cmd))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  #[cfg(target_os = "windows")]
+  fn quote_escapes_cmd_expansion() {
+    assert_eq!(quote_command_line_arg("C:\\a b"), "\"C:\\a b\"");
+    assert_eq!(
+      quote_command_line_arg("100%PATH%"),
+      "\"100\"^%\"PATH\"^%\"\""
+    );
+  }
+
+  #[test]
+  #[cfg(not(target_os = "windows"))]
+  fn quote_round_trips_through_split() {
+    for arg in ["plain", "with space", "it's $HOME `x`", ""] {
+      let quoted = quote_command_line_arg(arg);
+      assert_eq!(shlex::split(&quoted), Some(vec![arg.to_string()]));
+    }
+  }
+}
@@ -98,6 +98,16 @@ pub fn with_retry(client: Client) -> ClientWithMiddleware {
     .build()
 }
 
+// retry transient errors a few times only, for requests which can fall back to other sources.
+pub fn with_limited_retry(client: Client, max_retries: u32) -> ClientWithMiddleware {
+  ClientWithMiddlewareBuilder::new(client)
+    .with(RetryTransientMiddleware::new_with_policy_and_strategy(
+      ExponentialBackoff::builder().build_with_max_retries(max_retries),
+      LXMCLRetryableStrategy {},
+    ))
+    .build()
+}
+
 pub async fn is_china_mainland_ip(app: &AppHandle) -> Option<bool> {
   let client = app.state::<Client>();
 
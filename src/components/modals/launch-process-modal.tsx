@@ -32,6 +32,7 @@ import { useGlobalData } from "@/contexts/global-data";
 import { useSharedModals } from "@/contexts/shared-modal";
 import { useToast } from "@/contexts/toast";
 import { InstanceSummary } from "@/models/instance/misc";
+import { LaunchStepProgress, PlayerValidationResult } from "@/models/launch";
 import { ResponseError } from "@/models/response";
 import { AccountService } from "@/services/account";
 import { LaunchService } from "@/services/launch";
@@ -54,15 +55,25 @@ const LaunchProcessModal: React.FC<LaunchProcessModal> = ({
   const toast = useToast();
   const { config } = useLauncherConfig();
   const primaryColor = config.appearance.theme.primaryColor;
-  const { selectedPlayer, getInstanceList } = useGlobalData();
+  const { selectedPlayer, getInstanceList, getPlayerList } = useGlobalData();
   const { openSharedModal } = useSharedModals();
 
   const [launchingInstance, setLaunchingInstance] = useState<InstanceSummary>();
   const [errorPaused, setErrorPaused] = useState<boolean>(false);
   const [errorDesc, setErrorDesc] = useState<string>("");
   const [activeStep, setActiveStep] = useState<number>(0);
+  const [stepProgress, setStepProgress] = useState<LaunchStepProgress>();
   const previousStep = useRef<number>(-1);
 
+  useEffect(() => {
+    const unlisten = LaunchService.onLaunchStepProgress(setStepProgress);
+    return unlisten;
+  }, []);
+
+  useEffect(() => {
+    setStepProgress(undefined);
+  }, [activeStep]);
+
   useEffect(() => {
     setLaunchingInstance(
       getInstanceList()?.find((instance) => instance.id === instanceId)
@@ -108,8 +119,12 @@ const LaunchProcessModal: React.FC<LaunchProcessModal> = ({
       {
         label: "validateSelectedPlayer",
         function: () => LaunchService.validateSelectedPlayer(),
-        isOK: (data: boolean) => data,
-        onResCallback: (data: boolean) => {
+        isOK: (data: PlayerValidationResult) => data.isValid,
+        onResCallback: (data: PlayerValidationResult) => {
+          // the validated player may be bound to the instance, not the selected one
+          const player = getPlayerList()?.find(
+            (player) => player.id === data.playerId
+          );
           const reValidate = () =>
             LaunchService.validateSelectedPlayer().then((response) => {
               if (response.status === "success") {
@@ -119,25 +134,26 @@ const LaunchProcessModal: React.FC<LaunchProcessModal> = ({
                 setErrorDesc(response.details);
               }
             });
-          AccountService.refreshPlayer(selectedPlayer?.id || "").then(
-            (response) => {
-              if (response.status !== "success") {
-                openSharedModal("relogin", {
-                  player: selectedPlayer,
-                  onSuccess: () => {
-                    reValidate();
-                  },
-                  onError: () => {
-                    setErrorPaused(true);
-                    setErrorDesc(response.details);
-                    console.error(response.details);
-                  },
-                });
-              } else {
-                reValidate();
-              }
+          AccountService.refreshPlayer(data.playerId).then((response) => {
+            if (response.status === "success") {
+              reValidate();
+            } else if (!player) {
+              setErrorPaused(true);
+              setErrorDesc(response.details);
+            } else {
+              openSharedModal("relogin", {
+                player,
+                onSuccess: () => {
+                  reValidate();
+                },
+                onError: () => {
+                  setErrorPaused(true);
+                  setErrorDesc(response.details);
+                  console.error(response.details);
+                },
+              });
             }
-          );
+          });
         },
         onErrCallback: (error: ResponseError) => {},
       },
@@ -152,19 +168,23 @@ const LaunchProcessModal: React.FC<LaunchProcessModal> = ({
     ],
     [
       activeStep,
+      getPlayerList,
       handleCloseModalWithCancel,
       instanceId,
       openSharedModal,
       quickPlaySingleplayer,
       quickPlayMultiplayer,
       router,
-      selectedPlayer,
       toast,
     ]
   );
 
   useEffect(() => {
-    if (!selectedPlayer) {
+    // an instance bound to a player does not need the selected one
+    const playerBinding = getInstanceList()?.find(
+      (instance) => instance.id === instanceId
+    )?.playerBinding;
+    if (!selectedPlayer && !playerBinding) {
       toast({
         title: t("LaunchProcessModal.toast.noSelectedPlayer"),
         status: "warning",
@@ -200,6 +220,8 @@ const LaunchProcessModal: React.FC<LaunchProcessModal> = ({
   }, [
     activeStep,
     setActiveStep,
+    getInstanceList,
+    instanceId,
     launchProcessSteps,
     handleCloseModalWithCancel,
     props,
@@ -262,6 +284,14 @@ const LaunchProcessModal: React.FC<LaunchProcessModal> = ({
                       {errorDesc}
                     </StepDescription>
                   )}
+                  {!errorPaused && stepProgress && index === activeStep && (
+                    <StepDescription>
+                      {t("LaunchProcessModal.progress", {
+                        finished: stepProgress.finishedFiles,
+                        total: stepProgress.totalFiles,
+                      })}
+                    </StepDescription>
+                  )}
                 </Box>
                 <StepSeparator />
               </Step>
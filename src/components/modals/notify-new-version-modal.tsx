@@ -33,10 +33,11 @@ const NotifyNewVersionModal: React.FC<NotifyNewVersionModalProps> = ({
   const { config } = useLauncherConfig();
   const primaryColor = config.appearance.theme.primaryColor;
 
-  const isLinux = config.basicInfo.osType === "linux"; // for Linux, navigate to the website.
+  // Linux packages have no update file (updated by the package manager), navigate to the website.
+  const isExternal = !newVersion.fileName;
 
   const handleDownloadUpdate = () => {
-    if (isLinux) {
+    if (isExternal) {
       const lang = config.general.general.language === "zh-Hans" ? "zh" : "en";
       openUrl(`https://mc.sjtu.cn/sjmcl/${lang}`);
     } else {
@@ -57,11 +58,18 @@ const NotifyNewVersionModal: React.FC<NotifyNewVersionModalProps> = ({
   };
 
   const processReleaseNotes = (raw: string): string => {
-    const m = raw.match(/^([\s\S]*?)\r?\n\s*-{3,}\s*\r?\n([\s\S]*)$/); // match MD separator
-
-    // If user language is Chinese, swap to make Chinese part on top.
     const isZh = config.general.general.language.startsWith("zh");
-    return m && isZh ? `${m[2].trim()}\n---\n${m[1].trim()}` : raw;
+
+    // Notes of the skipped versions are joined by "***" (see updater.rs), process each of them.
+    return raw
+      .split(/\r?\n\s*\*{3}\s*\r?\n/)
+      .map((notes) => {
+        const m = notes.match(/^([\s\S]*?)\r?\n\s*-{3,}\s*\r?\n([\s\S]*)$/); // match MD separator
+
+        // If user language is Chinese, swap to make Chinese part on top.
+        return m && isZh ? `${m[2].trim()}\n---\n${m[1].trim()}` : notes;
+      })
+      .join("\n\n***\n\n");
   };
 
   return (
@@ -82,7 +90,7 @@ const NotifyNewVersionModal: React.FC<NotifyNewVersionModalProps> = ({
           <Button
             variant="solid"
             colorScheme={primaryColor}
-            rightIcon={isLinux ? <LuExternalLink /> : undefined}
+            rightIcon={isExternal ? <LuExternalLink /> : undefined}
             onClick={handleDownloadUpdate}
           >
             {t("General.download")}
@@ -1,17 +1,36 @@
 import { listen } from "@tauri-apps/api/event";
+import { downloadDir } from "@tauri-apps/api/path";
 import { useRouter } from "next/router";
 import { useCallback, useEffect, useMemo, useRef } from "react";
+import { useTranslation } from "react-i18next";
+import { useGlobalData } from "@/contexts/global-data";
 import { useSharedModals } from "@/contexts/shared-modal";
+import { useTaskContext } from "@/contexts/task";
 import { useToast } from "@/contexts/toast";
+import { OtherResourceType } from "@/enums/resource";
 import useDeepLink from "@/hooks/deep-link";
 import { useDragAndDrop } from "@/hooks/drag-and-drop";
 import useKeyboardShortcut from "@/hooks/keyboard-shortcut";
+import { TaskTypeEnums } from "@/models/task";
+import { InstanceService } from "@/services/instance";
+import { sanitizeFileName } from "@/utils/string";
+
+const isHttpUrl = (value: string) => {
+  try {
+    return ["http:", "https:"].includes(new URL(value).protocol);
+  } catch {
+    return false;
+  }
+};
 
 // Handle global keyboard shortcuts, DnD events, etc.
 const GlobalEventHandler: React.FC<{ children: React.ReactNode }> = ({
   children,
 }) => {
-  const { openSharedModal } = useSharedModals();
+  const { t } = useTranslation();
+  const { openSharedModal, openGenericConfirmDialog } = useSharedModals();
+  const { getInstanceList } = useGlobalData();
+  const { handleScheduleProgressiveTaskGroup } = useTaskContext();
   const toast = useToast();
   const router = useRouter();
   const isStandAlone = router.pathname.startsWith("/standalone");
@@ -197,51 +216,136 @@ const GlobalEventHandler: React.FC<{ children: React.ReactNode }> = ({
 
   // ---------------------- Deeplinks ---------------------
 
+  // lxmcl://<action>?<params>, every action must be confirmed by the user before running.
   // Note: These triggers appear to be ordinary strings on the surface,
   //       but they are actually syntactic sugar for JavaScript,
   //       being parsed into RegExp objects,
   //       which can affect the `Object.is()` comparison.
-  const addAuthServerTrigger = useMemo(
-    () => /^add-auth-server\/?(?:\?.*)?$/,
+  const deepLinkTrigger = useMemo(
+    () => /^(launch|install-modpack|add-server|add-auth-server)\/?(?:\?.*)?$/,
     []
   );
-  const launchTrigger = useMemo(() => /^launch\/?(?:\?.*)?$/, []);
-
-  const addAuthServerByDeeplink = useCallback(
-    (path: string | URL) => {
-      const url = new URL(path).searchParams.get("url") || "";
-      const decodeUrl = decodeURIComponent(url);
-      if (!isStandAlone && decodeUrl) {
-        openSharedModal("add-auth-server", { presetUrl: decodeUrl });
+
+  const handleDeepLink = useCallback(
+    (path: string, subpath: string) => {
+      if (isStandAlone) return;
+      const action = subpath.split(/[/?]/)[0];
+      const params = new URL(path).searchParams;
+      const toastInvalid = () =>
+        toast({
+          title: t("DeepLinkConfirmDialog.toast.invalid"),
+          status: "warning",
+        });
+      const findInstance = (id: string) =>
+        getInstanceList()?.find((instance) => instance.id === id);
+
+      switch (action) {
+        case "launch": {
+          // `id` is kept for the desktop shortcuts created by earlier versions
+          const instance = findInstance(
+            params.get("instance") || params.get("id") || ""
+          );
+          if (!instance) return toastInvalid();
+          openGenericConfirmDialog({
+            title: t("DeepLinkConfirmDialog.title"),
+            body: t("DeepLinkConfirmDialog.body.launch", {
+              name: instance.name,
+            }),
+            onOKCallback: () => {
+              openSharedModal("launch", { instanceId: instance.id });
+            },
+          });
+          break;
+        }
+        case "install-modpack": {
+          const src = params.get("src") || "";
+          const fileName = isHttpUrl(src)
+            ? decodeURIComponent(new URL(src).pathname.split("/").pop() || "")
+            : "";
+          if (!/\.(zip|mrpack)$/i.test(fileName)) return toastInvalid();
+          openGenericConfirmDialog({
+            title: t("DeepLinkConfirmDialog.title"),
+            body: t("DeepLinkConfirmDialog.body.installModpack", { src }),
+            onOKCallback: async () => {
+              // the import modal is opened once the download task completes
+              const dir = await downloadDir();
+              handleScheduleProgressiveTaskGroup(OtherResourceType.ModPack, [
+                {
+                  src,
+                  dest: `${dir}/${sanitizeFileName(fileName)}`,
+                  taskType: TaskTypeEnums.Download,
+                },
+              ]);
+              router.push("/downloads");
+            },
+          });
+          break;
+        }
+        case "add-server": {
+          const instance = findInstance(params.get("instance") || "");
+          const ip = (params.get("ip") || "").trim();
+          const name = (params.get("name") || "").trim() || ip;
+          if (!instance || !ip || /\s/.test(ip)) return toastInvalid();
+          openGenericConfirmDialog({
+            title: t("DeepLinkConfirmDialog.title"),
+            body: t("DeepLinkConfirmDialog.body.addServer", {
+              name,
+              ip,
+              instance: instance.name,
+            }),
+            onOKCallback: () => {
+              InstanceService.addGameServer(instance.id, { name, ip }).then(
+                (response) => {
+                  toast({
+                    title: response.message,
+                    description:
+                      response.status === "success"
+                        ? undefined
+                        : response.details,
+                    status: response.status,
+                  });
+                }
+              );
+            },
+          });
+          break;
+        }
+        case "add-auth-server": {
+          // the modal asks for confirmation itself
+          const url = params.get("url") || "";
+          if (!isHttpUrl(url)) return toastInvalid();
+          openSharedModal("add-auth-server", { presetUrl: url });
+          break;
+        }
+        default:
+          break;
       }
     },
-    [isStandAlone, openSharedModal]
+    [
+      getInstanceList,
+      handleScheduleProgressiveTaskGroup,
+      isStandAlone,
+      openGenericConfirmDialog,
+      openSharedModal,
+      router,
+      t,
+      toast,
+    ]
   );
 
-  const quickLaunchGame = useCallback(
-    (path: string | URL) => {
-      const id = new URL(path).searchParams.get("id") || "";
-      const decodeId = decodeURIComponent(id);
-      if (!isStandAlone && decodeId) {
-        // Delay the modal opening to ensure required app state/data (e.g. selected player in global-data context) is ready.
-        // This is important when the app is opened via deeplink.
-        // FIXME: find a better way to handle this.
-        setTimeout(() => {
-          openSharedModal("launch", { instanceId: decodeId });
-        }, 500);
-      }
+  const handleDeepLinkWhenReady = useCallback(
+    (path: string, subpath: string) => {
+      // Delay the handling to ensure required app state/data (e.g. instance list in global-data context) is ready.
+      // This is important when the app is opened via deeplink.
+      // FIXME: find a better way to handle this.
+      setTimeout(() => handleDeepLink(path, subpath), 500);
     },
-    [isStandAlone, openSharedModal]
+    [handleDeepLink]
   );
 
   useDeepLink({
-    trigger: addAuthServerTrigger,
-    onCall: addAuthServerByDeeplink,
-  });
-
-  useDeepLink({
-    trigger: launchTrigger,
-    onCall: quickLaunchGame,
+    trigger: deepLinkTrigger,
+    onCall: handleDeepLinkWhenReady,
   });
 
   return <>{children}</>;
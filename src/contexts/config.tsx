@@ -155,9 +155,11 @@ export const LauncherConfigContextProvider: React.FC<{
       return defaultVersionMetaInfo;
     }, []);
 
+  // check again when the update channel is switched
+  const updateChannel = config.general.functionality.updateChannel;
   useEffect(() => {
     handleCheckLauncherUpdate();
-  }, [handleCheckLauncherUpdate]);
+  }, [handleCheckLauncherUpdate, updateChannel]);
 
   return (
     <LauncherConfigContext.Provider
@@ -12,6 +12,10 @@ export enum InstanceError {
   InstanceNotFoundById = "INSTANCE_NOT_FOUND_BY_ID",
   ConflictNameError = "CONFLICT_NAME_ERROR",
   ServerNbtReadError = "SERVER_NBT_READ_ERROR",
+  ServerNbtWriteError = "SERVER_NBT_WRITE_ERROR",
+  ServerNotFoundError = "SERVER_NOT_FOUND_ERROR",
+  ServerListParseError = "SERVER_LIST_PARSE_ERROR",
+  InvalidServerAddress = "INVALID_SERVER_ADDRESS",
   FileNotFoundError = "FILE_NOT_FOUND_ERROR",
   InvalidSourcePath = "INVALID_SOURCE_PATH",
   FileCopyFailed = "FILE_COPY_FAILED",
@@ -20,6 +24,7 @@ export enum InstanceError {
   WorldNotExistError = "WORLD_NOT_EXSIT_ERROR",
   LevelNotExistError = "LEVEL_NOT_EXSIT_ERROR",
   LevelParseError = "LEVEL_PARSE_ERROR",
+  LevelPatchInvalidError = "LEVEL_PATCH_INVALID_ERROR",
 }
 
 export enum ConfigServiceError {
@@ -12,6 +12,14 @@ export interface Texture {
   preset?: PresetSkinType;
 }
 
+// cape owned by a Microsoft account
+export interface CapeInfo {
+  id: string;
+  alias: string;
+  image?: string; // missing if the image could not be fetched
+  isActive: boolean;
+}
+
 // player (frontend display format)
 export interface Player {
   id: string;
@@ -65,3 +73,9 @@ export interface DeviceAuthResponseInfo {
   interval?: number;
   expiresIn: number;
 }
+
+export interface TokenRefreshFailedPayload {
+  playerId: string;
+  playerName: string;
+  reason: string; // error code, e.g. "EXPIRED"
+}
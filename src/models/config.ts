@@ -24,6 +24,11 @@ export interface GameConfig {
   versionIsolation: boolean;
   launcherVisibility: string;
   displayGameLog: boolean;
+  worldBackup: {
+    autoBackupOnExit: boolean;
+    maxCount: number; // 0 for unlimited
+    maxAgeDays: number; // 0 for unlimited
+  };
   advancedOptions: {
     enabled: boolean;
   };
@@ -102,6 +107,10 @@ export interface LauncherConfig {
     cache: {
       directory: string;
     };
+    sharedStore: {
+      enabled: boolean;
+      directory: string;
+    };
     proxy: {
       enabled: boolean;
       selectedType: string;
@@ -119,6 +128,7 @@ export interface LauncherConfig {
       launchPageQuickSwitch: boolean;
       resourceTranslation: boolean;
       skipFirstScreenOptions: boolean;
+      updateChannel: string;
     };
   };
   localGameDirectories: GameDirectory[];
@@ -178,6 +188,11 @@ export const defaultGameConfig: GameConfig = {
   versionIsolation: true,
   launcherVisibility: "startHidden",
   displayGameLog: false,
+  worldBackup: {
+    autoBackupOnExit: false,
+    maxCount: 10,
+    maxAgeDays: 0,
+  },
   advancedOptions: {
     enabled: false,
   },
@@ -251,6 +266,10 @@ export const defaultConfig: LauncherConfig = {
     cache: {
       directory: "/mock/path/to/cache/",
     },
+    sharedStore: {
+      enabled: false,
+      directory: "/mock/path/to/shared-store/",
+    },
     proxy: {
       enabled: false,
       selectedType: "http",
@@ -268,6 +287,7 @@ export const defaultConfig: LauncherConfig = {
       launchPageQuickSwitch: true,
       resourceTranslation: true,
       skipFirstScreenOptions: false,
+      updateChannel: "stable",
     },
   },
   localGameDirectories: [{ name: "Current", dir: ".minecraft/" }],
@@ -311,6 +331,11 @@ export interface VersionMetaInfo {
   publishedAt?: string;
 }
 
+export interface SharedStoreCleanupResult {
+  removedCount: number;
+  freedSize: number; // in bytes
+}
+
 // empty release meta info indicating up-to-date or error.
 export const defaultVersionMetaInfo: VersionMetaInfo = {
   version: "",
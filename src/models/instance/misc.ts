@@ -1,5 +1,7 @@
+import { PlayerType } from "@/enums/account";
 import { ModLoaderType } from "@/enums/instance";
 import { OtherResourceSource } from "@/enums/resource";
+import { OtherResourceInfo } from "@/models/resource";
 
 export enum ModLoaderStatus {
   NotDownloaded = "NotDownloaded",
@@ -24,9 +26,21 @@ export interface InstanceSummary {
     version?: string;
     status: ModLoaderStatus;
   };
+  optifine?: {
+    version: string;
+    filename: string;
+    status: ModLoaderStatus;
+  };
   supportQuickPlay: boolean;
   useSpecGameConfig: boolean;
   isVersionIsolated: boolean;
+  playerBinding?: PlayerBinding;
+}
+
+export interface PlayerBinding {
+  playerId?: string;
+  playerType?: PlayerType;
+  authServerUrl?: string;
 }
 
 export interface ModpackMetaInfo {
@@ -51,6 +65,15 @@ export interface GameServerInfo {
   playersOnline?: number;
   playersMax?: number;
   online: boolean;
+  latency?: number;
+  acceptTextures?: boolean; // server resource packs, undefined for "prompt"
+}
+
+export interface GameServerEntry {
+  name: string;
+  ip: string;
+  icon?: string; // base64 PNG
+  acceptTextures?: boolean; // undefined for "prompt"
 }
 
 export interface LocalModInfo {
@@ -67,6 +90,24 @@ export interface LocalModInfo {
   potentialIncompatibility: boolean;
 }
 
+export enum ModDependencyIssueType {
+  MissingDependency = "MissingDependency",
+  VersionMismatch = "VersionMismatch",
+  DuplicateMod = "DuplicateMod",
+  Conflict = "Conflict",
+}
+
+export interface ModDependencyIssue {
+  issueType: ModDependencyIssueType;
+  modId: string;
+  modName: string;
+  fileNames: string[];
+  targetModId?: string;
+  requiredVersion?: string;
+  foundVersion?: string;
+  remoteResource?: OtherResourceInfo;
+}
+
 export interface ResourcePackInfo {
   name: string;
   description?: string;
@@ -89,3 +130,19 @@ export interface ScreenshotInfo {
   filePath: string;
   time: number; // UNIX timestamp
 }
+
+export interface GameOptions {
+  language?: string;
+  renderDistance?: number;
+  guiScale?: number; // 0 for "auto"
+  keyBindings: Record<string, string>; // e.g. "key.jump" -> "key.keyboard.space"
+  resourcePacks?: string[];
+}
+
+export interface OptionsTemplate {
+  name: string;
+  sourceVersion: string;
+  createdAt: number; // UNIX timestamp
+  keyBindings: Record<string, string>;
+  videoSettings: Record<string, string>;
+}
@@ -7,6 +7,15 @@ export interface WorldInfo {
   dirPath: string;
 }
 
+export interface WorldBackupInfo {
+  fileName: string;
+  worldName: string;
+  createdAt: number;
+  lastPlayedAt: number; // of the world when the backup was created
+  size: number;
+  filePath: string;
+}
+
 // level and player data
 export interface LevelData {
   allowCommands?: number;
@@ -48,6 +57,35 @@ export interface LevelData {
   wasModded: number;
 }
 
+// fields to be changed in level.dat, omitted for unchanged
+export interface LevelDataPatch {
+  gameRules?: Record<string, string>;
+  difficulty?: number; // 0: peaceful, 1: easy, 2: normal, 3: hard
+  difficultyLocked?: boolean;
+  hardcore?: boolean;
+  allowCommands?: boolean;
+  dayTime?: number;
+  clearWeatherTime?: number;
+  raining?: boolean;
+  rainTime?: number;
+  thundering?: boolean;
+  thunderTime?: number;
+  worldBorder?: {
+    centerX?: number;
+    centerZ?: number;
+    size?: number;
+    damagePerBlock?: number;
+    safeZone?: number;
+    warningBlocks?: number;
+    warningTime?: number;
+  };
+  spawnPoint?: {
+    x: number;
+    y: number;
+    z: number;
+  };
+}
+
 export interface Version {
   id: number;
   name: string;
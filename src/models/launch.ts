@@ -15,3 +15,38 @@ export interface LaunchingState {
   authServerMeta: string;
   pid: number;
 }
+
+export interface LaunchStepProgress {
+  finishedFiles: number;
+  totalFiles: number;
+  currentSize: number; // in bytes, of the files being downloaded
+  totalSize: number;
+}
+
+export interface PlayerValidationResult {
+  playerId: string; // the selected player, or the one bound to the instance
+  isValid: boolean; // false if the access token is expired
+}
+
+export enum CrashCause {
+  WrongJavaVersion = "WrongJavaVersion",
+  OutOfMemory = "OutOfMemory",
+  MissingModDependency = "MissingModDependency",
+  DuplicateMods = "DuplicateMods",
+  MixinFailure = "MixinFailure",
+  OpenGlError = "OpenGlError",
+  CorruptModJar = "CorruptModJar",
+}
+
+export interface CrashFinding {
+  cause: CrashCause;
+  mods: string[];
+  evidence: string;
+  sourceFile: string;
+  requiredJavaVersion?: number;
+}
+
+export interface CrashAnalysisResult {
+  findings: CrashFinding[];
+  analyzedFiles: string[];
+}
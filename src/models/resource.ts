@@ -74,6 +74,15 @@ export const defaultModLoaderResourceInfo: ModLoaderResourceInfo = {
   stable: true,
 };
 
+export interface OptiFineResourceInfo {
+  gameVersion: string;
+  type: string;
+  patch: string;
+  filename: string;
+  forge?: string;
+  stable: boolean;
+}
+
 export interface ModUpdateRecord {
   name: string;
   curVersion: string;
@@ -7,6 +7,15 @@ export interface JavaInfo {
   isUserAdded: boolean;
 }
 
+export interface ManagedJavaInfo {
+  name: string;
+  component: string;
+  majorVersion: number;
+  versionName: string;
+  execPath: string;
+  isUnused: boolean;
+}
+
 export interface MemoryInfo {
   total: number;
   used: number;
@@ -10,6 +10,7 @@ export interface DownloadTaskParam {
   dest: string; // destination path
   filename?: string; // destination filename
   sha1?: string;
+  fallbackSrcs?: string[]; // tried in order if src fails
 }
 
 export type TaskParam = DownloadTaskParam;
@@ -20,6 +21,7 @@ export interface DownloadTaskPayload {
   dest: string; // destination path
   filename: string; // destination filename
   sha1: string;
+  fallbackSrcs: string[];
 }
 
 export type TaskPayload = DownloadTaskPayload;
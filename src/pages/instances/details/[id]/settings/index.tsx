@@ -13,6 +13,7 @@ import { useRouter } from "next/router";
 import { useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import Editable from "@/components/common/editable";
+import { MenuSelector } from "@/components/common/menu-selector";
 import {
   OptionItemGroup,
   OptionItemGroupProps,
@@ -20,8 +21,11 @@ import {
 import GameSettingsGroups from "@/components/game-settings-groups";
 import { InstanceIconSelectorPopover } from "@/components/instance-icon-selector";
 import { useLauncherConfig } from "@/contexts/config";
+import { useGlobalData } from "@/contexts/global-data";
 import { useInstanceSharedData } from "@/contexts/instance";
 import { useToast } from "@/contexts/toast";
+import { PlayerType } from "@/enums/account";
+import { PlayerBinding } from "@/models/instance/misc";
 import { InstanceService } from "@/services/instance";
 import { isFileNameSanitized } from "@/utils/string";
 
@@ -31,6 +35,7 @@ const InstanceSettingsPage = () => {
   const { t } = useTranslation();
   const { config } = useLauncherConfig();
   const primaryColor = config.appearance.theme.primaryColor;
+  const { getPlayerList } = useGlobalData();
 
   const { id } = router.query;
   const instanceId = Array.isArray(id) ? id[0] : id;
@@ -44,6 +49,39 @@ const InstanceSettingsPage = () => {
   } = useInstanceSharedData();
   const useSpecGameConfig = summary?.useSpecGameConfig || false;
 
+  // the binding is either a pinned player or a required player type
+  const playerBindingOptions = [
+    {
+      value: "none",
+      label: t("InstanceSettingsPage.playerBinding.none"),
+    },
+    ...Object.values(PlayerType).map((type) => ({
+      value: `type:${type}`,
+      label: t("InstanceSettingsPage.playerBinding.anyOfType", {
+        type: t(`Enums.playerTypes.${type}`),
+      }),
+    })),
+    ...(getPlayerList() || []).map((player) => ({
+      value: `player:${player.id}`,
+      label: player.name,
+    })),
+  ];
+  const playerBindingValue = summary?.playerBinding?.playerId
+    ? `player:${summary.playerBinding.playerId}`
+    : summary?.playerBinding?.playerType
+      ? `type:${summary.playerBinding.playerType}`
+      : "none";
+
+  const handleUpdatePlayerBinding = (value: string) => {
+    let binding: PlayerBinding | null = null;
+    if (value.startsWith("player:")) {
+      binding = { playerId: value.slice("player:".length) };
+    } else if (value.startsWith("type:")) {
+      binding = { playerType: value.slice("type:".length) as PlayerType };
+    }
+    handleUpdateInstanceConfig("playerBinding", binding);
+  };
+
   const checkDirNameError = (value: string): number => {
     if (value.trim() === "") return 1;
     if (!isFileNameSanitized(value)) return 2;
@@ -122,6 +160,25 @@ const InstanceSettingsPage = () => {
             </HStack>
           ),
         },
+        {
+          title: t("InstanceSettingsPage.playerBinding.title"),
+          description: t("InstanceSettingsPage.playerBinding.description"),
+          children: (
+            <MenuSelector
+              options={playerBindingOptions}
+              value={playerBindingValue}
+              onSelect={(value) => handleUpdatePlayerBinding(value as string)}
+              placeholder={
+                playerBindingOptions.find(
+                  (option) => option.value === playerBindingValue
+                )?.label || t("InstanceSettingsPage.playerBinding.none")
+              }
+              buttonProps={{
+                flex: "0 0 auto",
+              }}
+            />
+          ),
+        },
         {
           title: t("InstanceSettingsPage.applySettings"),
           children: (
@@ -25,6 +25,7 @@ const GeneralSettingsPage = () => {
   const { openGenericConfirmDialog, closeSharedModal } = useSharedModals();
 
   const instancesNavTypes = ["instance", "directory", "hidden"];
+  const updateChannels = ["stable", "beta", "nightly"];
 
   const generalSettingGroups: OptionItemGroupProps[] = [
     {
@@ -141,6 +142,34 @@ const GeneralSettingsPage = () => {
             />
           ),
         },
+        {
+          title: t(
+            "GeneralSettingsPage.functions.settings.updateChannel.title"
+          ),
+          description: t(
+            "GeneralSettingsPage.functions.settings.updateChannel.description"
+          ),
+          children: (
+            <MenuSelector
+              options={updateChannels.map((channel) => ({
+                value: channel,
+                label: t(
+                  `GeneralSettingsPage.functions.settings.updateChannel.${channel}`
+                ),
+              }))}
+              value={generalConfigs.functionality.updateChannel}
+              onSelect={(value) =>
+                update("general.functionality.updateChannel", value as string)
+              }
+              placeholder={t(
+                `GeneralSettingsPage.functions.settings.updateChannel.${generalConfigs.functionality.updateChannel}`
+              )}
+              buttonProps={{
+                flex: "0 0 auto",
+              }}
+            />
+          ),
+        },
       ],
     },
     ...(config.general.general.language == "zh-Hans"
@@ -1,5 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
-import { AuthServer, DeviceAuthResponseInfo, Player } from "@/models/account";
+import { getCurrentWebview } from "@tauri-apps/api/webview";
+import {
+  AuthServer,
+  CapeInfo,
+  DeviceAuthResponseInfo,
+  Player,
+  TokenRefreshFailedPayload,
+} from "@/models/account";
 import { InvokeResponse } from "@/models/response";
 import { responseHandler } from "@/utils/response";
 
@@ -159,6 +166,70 @@ export class AccountService {
     });
   }
 
+  /**
+   * UPLOAD a skin or cape (PNG of 64x32, 64x64 or HD multiples) for a Microsoft or 3rd-party player.
+   * Microsoft players can only upload skins, capes are selected from the owned ones.
+   * @param {string} playerId - The player ID.
+   * @param {string} textureType - "SKIN" or "CAPE".
+   * @param {string} filePath - The path of the PNG file.
+   * @param {"default" | "slim"} model - The skin model (classic or slim), ignored for capes.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("account")
+  static async uploadPlayerTexture(
+    playerId: string,
+    textureType: string,
+    filePath: string,
+    model: "default" | "slim"
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("upload_player_texture", {
+      playerId,
+      textureType,
+      filePath,
+      model,
+    });
+  }
+
+  /**
+   * RESET the skin of a player to the default one, or remove (hide) its cape.
+   * @param {string} playerId - The player ID.
+   * @param {string} textureType - "SKIN" or "CAPE".
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("account")
+  static async resetPlayerTexture(
+    playerId: string,
+    textureType: string
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("reset_player_texture", { playerId, textureType });
+  }
+
+  /**
+   * RETRIEVE the capes owned by a Microsoft player.
+   * @param {string} playerId - The player ID.
+   * @returns {Promise<InvokeResponse<CapeInfo[]>>}
+   */
+  @responseHandler("account")
+  static async retrievePlayerCapeList(
+    playerId: string
+  ): Promise<InvokeResponse<CapeInfo[]>> {
+    return await invoke("retrieve_player_cape_list", { playerId });
+  }
+
+  /**
+   * SELECT the cape shown by a Microsoft player.
+   * @param {string} playerId - The player ID.
+   * @param {string} [capeId] - The ID of an owned cape, hide the cape if not provided.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("account")
+  static async selectPlayerCape(
+    playerId: string,
+    capeId?: string
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("select_player_cape", { playerId, capeId });
+  }
+
   /**
    * DELETE a player by player ID.
    * @param {string} playerId - The player ID of the player to be deleted.
@@ -219,4 +290,23 @@ export class AccountService {
   static async deleteAuthServer(url: string): Promise<InvokeResponse<void>> {
     return await invoke("delete_auth_server", { url });
   }
+
+  /**
+   * LISTEN to the background token refresh failures, the player needs to log in again then.
+   * @param callback The callback function to be called with the failed player.
+   */
+  static onTokenRefreshFailed(
+    callback: (payload: TokenRefreshFailedPayload) => void
+  ) {
+    const unlisten = getCurrentWebview().listen<TokenRefreshFailedPayload>(
+      "account:token-refresh-failed",
+      (event) => {
+        callback(event.payload);
+      }
+    );
+
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }
 }
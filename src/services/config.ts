@@ -1,8 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebview } from "@tauri-apps/api/webview";
-import { LauncherConfig, VersionMetaInfo } from "@/models/config";
+import {
+  LauncherConfig,
+  SharedStoreCleanupResult,
+  VersionMetaInfo,
+} from "@/models/config";
 import { InvokeResponse } from "@/models/response";
-import { JavaInfo } from "@/models/system-info";
+import { JavaInfo, ManagedJavaInfo } from "@/models/system-info";
 import { responseHandler } from "@/utils/response";
 
 /**
@@ -143,6 +147,49 @@ export class ConfigService {
     return await invoke("download_mojang_java", { version });
   }
 
+  /**
+   * RETRIEVE the list of Java runtimes downloaded from Mojang by the launcher.
+   * @returns {Promise<InvokeResponse<ManagedJavaInfo[]>>} A list of managed Java runtimes.
+   */
+  @responseHandler("config")
+  static async retrieveManagedJavaList(): Promise<
+    InvokeResponse<ManagedJavaInfo[]>
+  > {
+    return await invoke("retrieve_managed_java_list");
+  }
+
+  /**
+   * VERIFY the files of a managed Java runtime against its manifest.
+   * @param {string} name The directory name of the runtime, e.g. java-17.
+   * @returns {Promise<InvokeResponse<string[]>>} Paths of the missing or corrupted files.
+   */
+  @responseHandler("config")
+  static async verifyManagedJava(
+    name: string
+  ): Promise<InvokeResponse<string[]>> {
+    return await invoke("verify_managed_java", { name });
+  }
+
+  /**
+   * REPAIR a managed Java runtime by downloading its missing or corrupted files.
+   * @param {string} name The directory name of the runtime, e.g. java-17.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("config")
+  static async repairManagedJava(name: string): Promise<InvokeResponse<void>> {
+    return await invoke("repair_managed_java", { name });
+  }
+
+  /**
+   * REMOVE a managed Java runtime.
+   * @param {string} name The directory name of the runtime, e.g. java-17.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("config")
+  static async removeManagedJava(name: string): Promise<InvokeResponse<void>> {
+    return await invoke("remove_managed_java", { name });
+  }
+
   /**
    * CHECK whether the game directory is valid.
    * @param {string} dir The game directory to check.
@@ -164,6 +211,17 @@ export class ConfigService {
     return await invoke("clear_download_cache");
   }
 
+  /**
+   * CLEAN the shared library and asset store, removing objects not used by any game directory.
+   * @returns {Promise<InvokeResponse<SharedStoreCleanupResult>>} The number of removed objects and freed size in bytes.
+   */
+  @responseHandler("config")
+  static async cleanSharedStore(): Promise<
+    InvokeResponse<SharedStoreCleanupResult>
+  > {
+    return await invoke("clean_shared_store");
+  }
+
   /**
    * CHECK for launcher updates.
    * @returns {Promise<InvokeResponse<VersionMetaInfo>>} The latest release meta info if an update is available.
@@ -1,20 +1,31 @@
 import { invoke } from "@tauri-apps/api/core";
 import { InstanceSubdirType } from "@/enums/instance";
+import { OtherResourceSource } from "@/enums/resource";
 import { GameConfig, GameDirectory } from "@/models/config";
 import {
+  GameOptions,
+  GameServerEntry,
   GameServerInfo,
   InstanceSummary,
   LocalModInfo,
+  ModDependencyIssue,
   ModpackMetaInfo,
+  OptionsTemplate,
   ResourcePackInfo,
   SchematicInfo,
   ScreenshotInfo,
   ShaderPackInfo,
 } from "@/models/instance/misc";
-import { LevelData, WorldInfo } from "@/models/instance/world";
+import {
+  LevelData,
+  LevelDataPatch,
+  WorldBackupInfo,
+  WorldInfo,
+} from "@/models/instance/world";
 import {
   GameClientResourceInfo,
   ModLoaderResourceInfo,
+  OptiFineResourceInfo,
 } from "@/models/resource";
 import { InvokeResponse } from "@/models/response";
 import { responseHandler } from "@/utils/response";
@@ -43,7 +54,8 @@ export class InstanceService {
    * @param {GameClientResourceInfo} game - The game resource info of the instance.
    * @param {ModLoaderResourceInfo} modLoader - The mod loader info of the instance.
    * @param {string} [modpackPath] - Optional path to the modpack archive file.
-   * @param {boolean} [isInstallFabricApi] - Optional flag to indicate whether to install Fabric API (only valid when modLoader is Fabric).
+   * @param {boolean} [isInstallFabricApi] - Optional flag to indicate whether to install Fabric API or QFAPI (only valid when modLoader is Fabric or Quilt).
+   * @param {string} [optionsTemplateName] - Optional name of the settings template to apply to the new instance.
    * @returns {Promise<InvokeResponse<null>>}
    */
   @responseHandler("instance")
@@ -55,7 +67,8 @@ export class InstanceService {
     game: GameClientResourceInfo,
     modLoader: ModLoaderResourceInfo,
     modpackPath?: string,
-    isInstallFabricApi?: boolean
+    isInstallFabricApi?: boolean,
+    optionsTemplateName?: string
   ): Promise<InvokeResponse<null>> {
     return await invoke("create_instance", {
       directory,
@@ -66,6 +79,7 @@ export class InstanceService {
       modLoader,
       modpackPath,
       isInstallFabricApi,
+      optionsTemplateName,
     });
   }
 
@@ -240,6 +254,84 @@ export class InstanceService {
     });
   }
 
+  /**
+   * ADD a server to the instance's servers.dat.
+   * @param {string} instanceId - The instance ID.
+   * @param {GameServerEntry} server - The server to add.
+   * @param {number} [index] - Optional position to insert at, appended if not provided.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async addGameServer(
+    instanceId: string,
+    server: GameServerEntry,
+    index?: number
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("add_game_server", { instanceId, server, index });
+  }
+
+  /**
+   * UPDATE a server in the instance's servers.dat, other tags of the entry are kept.
+   * @param {string} instanceId - The instance ID.
+   * @param {number} index - The position of the server in the list.
+   * @param {GameServerEntry} server - The new server info.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async updateGameServer(
+    instanceId: string,
+    index: number,
+    server: GameServerEntry
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("update_game_server", { instanceId, index, server });
+  }
+
+  /**
+   * DELETE a server from the instance's servers.dat.
+   * @param {string} instanceId - The instance ID.
+   * @param {number} index - The position of the server in the list.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async deleteGameServer(
+    instanceId: string,
+    index: number
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("delete_game_server", { instanceId, index });
+  }
+
+  /**
+   * REORDER the servers in the instance's servers.dat.
+   * @param {string} instanceId - The instance ID.
+   * @param {number[]} order - The current positions of the servers, in the new order.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async reorderGameServers(
+    instanceId: string,
+    order: number[]
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("reorder_game_servers", { instanceId, order });
+  }
+
+  /**
+   * IMPORT a server list (JSON array or CSV with a header row) into the servers.dat of multiple instances.
+   * Servers with an existing address are updated in place, others are appended.
+   * @param {string} srcFilePath - The path of the JSON or CSV file.
+   * @param {string[]} tgtInstIds - The target instance IDs.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async importGameServersToInstances(
+    srcFilePath: string,
+    tgtInstIds: string[]
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("import_game_servers_to_instances", {
+      srcFilePath,
+      tgtInstIds,
+    });
+  }
+
   /**
    * RETRIEVE the list of local mods.
    * @param {string} instanceId - The instance ID to retrieve the local mods for.
@@ -254,6 +346,20 @@ export class InstanceService {
     });
   }
 
+  /**
+   * CHECK the dependencies of enabled mods, reporting missing, mismatched, duplicate and conflicting mods.
+   * @param {string} instanceId - The instance ID to check the mods for.
+   * @returns {Promise<InvokeResponse<ModDependencyIssue[]>>}
+   */
+  @responseHandler("instance")
+  static async checkModDependencies(
+    instanceId: string
+  ): Promise<InvokeResponse<ModDependencyIssue[]>> {
+    return await invoke("check_mod_dependencies", {
+      instanceId,
+    });
+  }
+
   /**
    * RETRIEVE the list of server resource packs.
    * @param {string} instanceId - The instance ID to retrieve the server resource packs for.
@@ -358,6 +464,106 @@ export class InstanceService {
     });
   }
 
+  /**
+   * UPDATE the level.dat of a world with a validated patch, the previous file is kept as level.dat_old.
+   * @param {string} instanceId - The instance ID of the world.
+   * @param {string} worldName - The name (directory) of the world.
+   * @param {LevelDataPatch} patch - The fields to be changed.
+   * @returns {Promise<InvokeResponse<LevelData>>} The updated level data.
+   */
+  @responseHandler("instance")
+  static async updateWorldLevelData(
+    instanceId: string,
+    worldName: string,
+    patch: LevelDataPatch
+  ): Promise<InvokeResponse<LevelData>> {
+    return await invoke("update_world_level_data", {
+      instanceId,
+      worldName,
+      patch,
+    });
+  }
+
+  /**
+   * CREATE a zip snapshot of a world.
+   * @param {string} instanceId - The instance ID of the world.
+   * @param {string} worldName - The name (directory) of the world.
+   * @returns {Promise<InvokeResponse<WorldBackupInfo>>} The created snapshot.
+   */
+  @responseHandler("instance")
+  static async createWorldBackup(
+    instanceId: string,
+    worldName: string
+  ): Promise<InvokeResponse<WorldBackupInfo>> {
+    return await invoke("create_world_backup", {
+      instanceId,
+      worldName,
+    });
+  }
+
+  /**
+   * RETRIEVE the list of snapshots of a world, the newest first.
+   * @param {string} instanceId - The instance ID of the world.
+   * @param {string} worldName - The name (directory) of the world.
+   * @returns {Promise<InvokeResponse<WorldBackupInfo[]>>}
+   */
+  @responseHandler("instance")
+  static async retrieveWorldBackupList(
+    instanceId: string,
+    worldName: string
+  ): Promise<InvokeResponse<WorldBackupInfo[]>> {
+    return await invoke("retrieve_world_backup_list", {
+      instanceId,
+      worldName,
+    });
+  }
+
+  /**
+   * RESTORE a snapshot of a world. The world to be overwritten is backed up first.
+   * @param {string} instanceId - The instance ID of the world.
+   * @param {string} worldName - The name (directory) of the world the snapshot belongs to.
+   * @param {string} backupFileName - The file name of the snapshot.
+   * @param {string} [targetWorldName] - The directory to restore into, the original world if not given.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async restoreWorldBackup(
+    instanceId: string,
+    worldName: string,
+    backupFileName: string,
+    targetWorldName?: string
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("restore_world_backup", {
+      instanceId,
+      worldName,
+      backupFileName,
+      targetWorldName,
+    });
+  }
+
+  /**
+   * PRUNE the snapshots of a world by retention count or age.
+   * @param {string} instanceId - The instance ID of the world.
+   * @param {string} worldName - The name (directory) of the world.
+   * @param {number} maxCount - The number of newest snapshots to keep, 0 for unlimited.
+   * @param {number} maxAgeDays - Remove snapshots older than this, 0 for unlimited.
+   * @returns {Promise<InvokeResponse<WorldBackupInfo[]>>} The removed snapshots.
+   */
+  @responseHandler("instance")
+  static async pruneWorldBackups(
+    instanceId: string,
+    worldName: string,
+    maxCount: number,
+    maxAgeDays: number
+  ): Promise<InvokeResponse<WorldBackupInfo[]>> {
+    return await invoke("prune_world_backups", {
+      instanceId,
+      worldName,
+      maxCount,
+      maxAgeDays,
+    });
+  }
+
   /**
    * CREATE a desktop shortcut for launching a specific instance.
    * @param {string} instanceId - The instance ID for which to create the shortcut.
@@ -386,6 +592,40 @@ export class InstanceService {
     });
   }
 
+  /**
+   * INSTALL OptiFine to the given instance, as a standalone patch (vanilla) or as a mod (Forge).
+   * @param {string} instanceId - The ID of the instance.
+   * @param {OptiFineResourceInfo} optifine - The OptiFine version to install.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async installOptiFineToInstance(
+    instanceId: string,
+    optifine: OptiFineResourceInfo
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("install_optifine_to_instance", {
+      instanceId,
+      optifine,
+    });
+  }
+
+  /**
+   * INSTALL LiteLoader on top of an installed (Legacy)Forge instance.
+   * @param {string} instanceId - The ID of the instance.
+   * @param {ModLoaderResourceInfo} liteloader - The LiteLoader version to install.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async installLiteLoaderToInstance(
+    instanceId: string,
+    liteloader: ModLoaderResourceInfo
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("install_liteloader_to_instance", {
+      instanceId,
+      liteloader,
+    });
+  }
+
   /**
    * CHECK whether the given instance supports mod loader change.
    * @param {string} instanceId - The instance ID to check.
@@ -404,7 +644,7 @@ export class InstanceService {
    * CHANGE the mod loader for a given instance.
    * @param {string} instanceId - The ID of the instance to update.
    * @param {ModLoaderResourceInfo} newModLoader - The new mod loader information.
-   * @param {boolean} [isInstallFabricApi] - Optional flag to indicate whether to install Fabric API (only valid when modLoader is Fabric).
+   * @param {boolean} [isInstallFabricApi] - Optional flag to indicate whether to install Fabric API or QFAPI (only valid when modLoader is Fabric or Quilt).
    * @returns {Promise<InvokeResponse<void>>}
    */
   @responseHandler("instance")
@@ -433,4 +673,119 @@ export class InstanceService {
       path,
     });
   }
+
+  /**
+   * EXPORT the instance as a Modrinth (.mrpack) or CurseForge modpack archive.
+   * @param {string} instanceId - The ID of the instance.
+   * @param {OtherResourceSource} modpackSource - The modpack format to export.
+   * @param {string} savePath - The path to save the modpack archive.
+   * @param {string} name - The name of the modpack.
+   * @param {string} version - The version of the modpack.
+   * @param {string} author - The author of the modpack.
+   * @param {string} [description] - Optional description (summary) of the modpack.
+   * @param {string[]} overridePaths - Paths relative to the game directory to put into overrides, e.g. "config".
+   * @returns {Promise<InvokeResponse<null>>}
+   */
+  @responseHandler("instance")
+  static async exportModpack(
+    instanceId: string,
+    modpackSource: OtherResourceSource,
+    savePath: string,
+    name: string,
+    version: string,
+    author: string,
+    description: string | undefined,
+    overridePaths: string[]
+  ): Promise<InvokeResponse<null>> {
+    return await invoke("export_modpack", {
+      instanceId,
+      modpackSource,
+      savePath,
+      name,
+      version,
+      author,
+      description,
+      overridePaths,
+    });
+  }
+
+  /**
+   * RETRIEVE the common settings in the instance's options.txt.
+   * @param {string} instanceId - The instance ID.
+   * @returns {Promise<InvokeResponse<GameOptions>>}
+   */
+  @responseHandler("instance")
+  static async retrieveGameOptions(
+    instanceId: string
+  ): Promise<InvokeResponse<GameOptions>> {
+    return await invoke("retrieve_game_options", { instanceId });
+  }
+
+  /**
+   * UPDATE the common settings in the instance's options.txt, other lines are kept as is.
+   * @param {string} instanceId - The instance ID.
+   * @param {GameOptions} options - The settings to be changed (unset fields are left unchanged).
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async updateGameOptions(
+    instanceId: string,
+    options: GameOptions
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("update_game_options", { instanceId, options });
+  }
+
+  /**
+   * RETRIEVE the list of settings templates.
+   * @returns {Promise<InvokeResponse<OptionsTemplate[]>>}
+   */
+  @responseHandler("instance")
+  static async retrieveOptionsTemplateList(): Promise<
+    InvokeResponse<OptionsTemplate[]>
+  > {
+    return await invoke("retrieve_options_template_list");
+  }
+
+  /**
+   * CREATE a settings template from the key bindings and video settings of an instance.
+   * @param {string} instanceId - The instance ID to capture the settings from.
+   * @param {string} name - The name of the template, a template with the same name is replaced.
+   * @returns {Promise<InvokeResponse<OptionsTemplate>>}
+   */
+  @responseHandler("instance")
+  static async createOptionsTemplate(
+    instanceId: string,
+    name: string
+  ): Promise<InvokeResponse<OptionsTemplate>> {
+    return await invoke("create_options_template", { instanceId, name });
+  }
+
+  /**
+   * DELETE a settings template.
+   * @param {string} templateName - The name of the template.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async deleteOptionsTemplate(
+    templateName: string
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("delete_options_template", { templateName });
+  }
+
+  /**
+   * APPLY a settings template to an existing instance, key names are translated for its game version.
+   * @param {string} instanceId - The instance ID.
+   * @param {string} templateName - The name of the template.
+   * @returns {Promise<InvokeResponse<void>>}
+   */
+  @responseHandler("instance")
+  static async applyOptionsTemplate(
+    instanceId: string,
+    templateName: string
+  ): Promise<InvokeResponse<void>> {
+    return await invoke("apply_options_template", {
+      instanceId,
+      templateName,
+    });
+  }
 }
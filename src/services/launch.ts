@@ -1,6 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebview } from "@tauri-apps/api/webview";
-import { LaunchingState } from "@/models/launch";
+import {
+  CrashAnalysisResult,
+  LaunchStepProgress,
+  LaunchingState,
+  PlayerValidationResult,
+} from "@/models/launch";
 import { InvokeResponse } from "@/models/response";
 import { responseHandler } from "@/utils/response";
 
@@ -32,11 +37,13 @@ export class LaunchService {
 
   /**
    * Launching Step 3: validate the selected player, prepare prefetched server meta for authlib-injector.
-   * The selected player ID is retrieved by the backend itself from the config state.
-   * @returns {Promise<InvokeResponse<boolean>>} false if the access token is expired.
+   * The player is resolved by the backend itself, from the instance's player binding or the selected player ID in the config state.
+   * @returns {Promise<InvokeResponse<PlayerValidationResult>>} The ID of the validated player, and whether its access token is valid.
    */
   @responseHandler("launch")
-  static async validateSelectedPlayer(): Promise<InvokeResponse<boolean>> {
+  static async validateSelectedPlayer(): Promise<
+    InvokeResponse<PlayerValidationResult>
+  > {
     return await invoke("validate_selected_player");
   }
 
@@ -119,6 +126,18 @@ export class LaunchService {
     return await invoke("export_game_crash_info", { launchingId, savePath });
   }
 
+  /**
+   * ANALYZE the game log, crash report and JVM error log of a crashed game, matching known causes.
+   * @param {number} launchingId The id of the launching state to analyze.
+   * @returns {Promise<InvokeResponse<CrashAnalysisResult>>} The findings and the analyzed files.
+   */
+  @responseHandler("launch")
+  static async analyzeGameCrash(
+    launchingId: number
+  ): Promise<InvokeResponse<CrashAnalysisResult>> {
+    return await invoke("analyze_game_crash", { launchingId });
+  }
+
   /**
    * LISTEN to the game log output line by line.
    * @param callback The callback function to be called when the game log is output.
@@ -135,4 +154,23 @@ export class LaunchService {
       unlisten.then((f) => f());
     };
   }
+
+  /**
+   * LISTEN to the download progress of the current launching step (e.g. the Java runtime download).
+   * @param callback The callback function to be called when the progress is updated.
+   */
+  static onLaunchStepProgress(
+    callback: (payload: LaunchStepProgress) => void
+  ) {
+    const unlisten = getCurrentWebview().listen<LaunchStepProgress>(
+      "launch:step-progress",
+      (event) => {
+        callback(event.payload);
+      }
+    );
+
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }
 }
@@ -6,6 +6,7 @@ import {
   GameClientResourceInfo,
   ModLoaderResourceInfo,
   ModUpdateQuery,
+  OptiFineResourceInfo,
   OtherResourceFileInfo,
   OtherResourceInfo,
   OtherResourceSearchRes,
@@ -56,6 +57,18 @@ export class ResourceService {
     });
   }
 
+  /**
+   * FETCH the list of OptiFine versions (only available from BMCLAPI).
+   * @param {string} gameVersion - The game version to fetch OptiFine versions for.
+   * @returns {Promise<InvokeResponse<OptiFineResourceInfo[]>>}
+   */
+  @responseHandler("resource")
+  static async fetchOptiFineVersionList(
+    gameVersion: string
+  ): Promise<InvokeResponse<OptiFineResourceInfo[]>> {
+    return await invoke("fetch_optifine_version_list", { gameVersion });
+  }
+
   /**
    * FETCH the list of resources according to the given parameters.
    * @returns {Promise<InvokeResponse<OtherResourceSearchRes>>}